@@ -112,7 +112,7 @@ impl EngineBuilder {
         let engine = Engine::new_with_config(&self.config);
 
         // init the cache store to make sure the plugin can registry package to the store
-        engine.runtime().cache().init(&engine);
+        engine.runtime().cache().init(&engine)?;
 
         // init plugins
         for plugin in self.plugins.iter() {
@@ -1,10 +1,15 @@
 use crate::{
-    Engine, Result,
+    Engine, Result, ShareLock,
+    config::ConfigRetention,
+    model,
     scheduler::{Process, Runtime, Task},
     store::Store,
 };
 use moka::sync::Cache as MokaCache;
-use std::sync::Arc;
+use std::{
+    path::Path,
+    sync::{Arc, RwLock},
+};
 use tracing::{debug, error, instrument};
 
 #[derive(Clone)]
@@ -12,6 +17,8 @@ pub struct Cache {
     cap: usize,
     procs: MokaCache<String, Arc<Process>>,
     store: Arc<Store>,
+    // the workflow schema validator with the packages version it is built from
+    schema: ShareLock<Option<(usize, Arc<jsonschema::Validator>)>>,
 }
 
 impl std::fmt::Debug for Cache {
@@ -29,6 +36,7 @@ impl Cache {
             cap,
             procs: MokaCache::new(cap as u64),
             store: Arc::new(Store::new()),
+            schema: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -45,13 +53,27 @@ impl Cache {
         self.procs.entry_count() as usize
     }
 
-    pub fn init(&self, _engine: &Engine) {
+    pub fn init(&self, engine: &Engine) -> Result<()> {
         debug!("cache::init");
-        self.store.init();
+        self.store.init(&engine.config())
     }
 
     pub fn close(&self) {}
 
+    /// the validator of the workflow schema, it is rebuilt only when the packages are changed
+    pub fn schema(&self) -> Result<Arc<jsonschema::Validator>> {
+        let ver = self.store.packages_ver();
+        if let Some((v, validator)) = &*self.schema.read().unwrap()
+            && *v == ver
+        {
+            return Ok(validator.clone());
+        }
+
+        let validator = Arc::new(model::validator(&self.store)?);
+        *self.schema.write().unwrap() = Some((ver, validator.clone()));
+        Ok(validator)
+    }
+
     #[instrument]
     pub fn push_proc(&self, proc: &Arc<Process>) {
         self.push_proc_pri(proc, true);
@@ -88,13 +110,36 @@ impl Cache {
     }
 
     #[instrument]
+    /// remove the process with its tasks and history
     pub fn remove(&self, pid: &str) -> Result<bool> {
         debug!("remove pid={pid}");
         self.procs.remove(pid);
         self.store.remove_proc(pid)?;
+        self.store.remove_history(pid)?;
         Ok(true)
     }
 
+    /// archive and purge the finished processes by the retention policy
+    /// at most the batch size of processes are purged, returns the purged count
+    #[instrument]
+    pub fn purge(&self, retention: &ConfigRetention, now: i64) -> Result<usize> {
+        debug!("purge");
+        let procs = self
+            .store
+            .expired_procs(retention, now, retention.batch_size())?;
+        for proc in procs.iter() {
+            if let Some(dir) = &retention.archive_dir {
+                self.store.archive_proc(proc, Path::new(dir))?;
+            }
+            if retention.archive_collection.unwrap_or_default() {
+                self.store.archive_proc_collection(proc)?;
+            }
+            self.remove(&proc.id)?;
+            self.store.remove_messages(&proc.id)?;
+        }
+        Ok(procs.len())
+    }
+
     #[instrument(skip(on_load))]
     pub fn restore<F: Fn(&Arc<Process>)>(&self, rt: &Arc<Runtime>, on_load: F) -> Result<()> {
         debug!("restore");
@@ -121,7 +166,7 @@ impl Cache {
         self.push_task_pri(task, true)
     }
 
-    #[cfg(test)]
+    /// remove the process from the cache and keep it in the store
     pub fn uncache(&self, pid: &str) {
         self.procs.remove(pid);
     }
@@ -1,13 +1,20 @@
 use crate::{
-    ActError, Error, Message, Result, Workflow,
+    ActError, Error, Message, Result, Vars, Workflow,
+    config::ConfigRetention,
     data::{self, MessageStatus},
     scheduler::{self, Node, Runtime, StatementBatch, TaskLifeCycle, TaskState},
     store::{Store, query::*},
     utils::{self, Id},
 };
-use std::{collections::HashMap, sync::Arc};
+use flate2::{Compression, write::GzEncoder};
+use serde_json::{Value as JsonValue, json};
+use std::{collections::HashMap, fs, io::Write, path::Path, sync::Arc};
 use tracing::debug;
 
+const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+/// the page size to query the expired processes
+const EXPIRED_PAGE_SIZE: usize = 300;
+
 impl Store {
     pub fn load(&self, cap: usize, rt: &Arc<Runtime>) -> Result<Vec<Arc<scheduler::Process>>> {
         debug!("load cap={}", cap);
@@ -90,6 +97,125 @@ impl Store {
         Ok(true)
     }
 
+    pub fn remove_messages(&self, pid: &str) -> Result<bool> {
+        debug!("remove_messages pid={}", pid);
+        let q = Query::new().push(Cond::and().push(Expr::eq("pid", pid.to_string())));
+        let messages = self.messages().query(&q)?;
+        for m in messages.rows {
+            self.messages().delete(&m.id)?;
+        }
+        Ok(true)
+    }
+
+    /// find the finished processes which are out of the retention days
+    /// at most limit processes are returned
+    pub fn expired_procs(
+        &self,
+        retention: &ConfigRetention,
+        now: i64,
+        limit: usize,
+    ) -> Result<Vec<data::Proc>> {
+        let mut cond = Cond::or();
+        for state in [
+            TaskState::Completed,
+            TaskState::Cancelled,
+            TaskState::Error,
+            TaskState::Skipped,
+            TaskState::Aborted,
+            TaskState::Removed,
+        ] {
+            cond = cond.push(Expr::eq("state", state.to_string()));
+        }
+
+        // the shortest retention is used as the query cutoff, the rules by model are checked later
+        let cutoff = now - retention.min_keep_days() * DAY_MILLIS;
+        let mut q = Query::new()
+            .push(cond)
+            .push(
+                Cond::and()
+                    .push(Expr::gt("end_time", 0))
+                    .push(Expr::le("end_time", cutoff)),
+            )
+            .set_order(&[("id".to_string(), false)])
+            .set_limit(EXPIRED_PAGE_SIZE);
+
+        let mut ret = Vec::new();
+        loop {
+            let procs = self.procs().query(&q)?;
+            let count = procs.rows.len();
+            ret.extend(procs.rows.into_iter().filter(|p| {
+                let state: TaskState = p.state.as_str().into();
+                let days = retention.keep_days(&p.mid, state.is_error());
+                state.is_completed() && p.end_time > 0 && p.end_time + days * DAY_MILLIS <= now
+            }));
+
+            if ret.len() >= limit {
+                ret.truncate(limit);
+                break;
+            }
+            if count < EXPIRED_PAGE_SIZE {
+                break;
+            }
+            let offset = q.offset() + EXPIRED_PAGE_SIZE;
+            q = q.set_offset(offset);
+        }
+        Ok(ret)
+    }
+
+    /// archive the process with its tasks and messages to a compressed json file
+    pub fn archive_proc(&self, proc: &data::Proc, dir: &Path) -> Result<()> {
+        debug!("archive_proc pid={} dir={:?}", proc.id, dir);
+        let value = self.archive_value(proc)?;
+
+        fs::create_dir_all(dir).map_err(|err| ActError::Store(err.to_string()))?;
+        let file = fs::File::create(dir.join(format!("{}.json.gz", proc.id)))
+            .map_err(|err| ActError::Store(err.to_string()))?;
+        let mut encoder = GzEncoder::new(file, Compression::default());
+        encoder
+            .write_all(value.to_string().as_bytes())
+            .and_then(|_| encoder.finish().map(|_| ()))
+            .map_err(|err| ActError::Store(err.to_string()))?;
+
+        Ok(())
+    }
+
+    /// archive the process with its tasks and messages to the archives collection
+    pub fn archive_proc_collection(&self, proc: &data::Proc) -> Result<()> {
+        debug!("archive_proc_collection pid={}", proc.id);
+        let value = self.archive_value(proc)?;
+        let archive = data::Archive {
+            id: proc.id.clone(),
+            mid: proc.mid.clone(),
+            name: proc.name.clone(),
+            state: proc.state.clone(),
+            data: value.to_string(),
+            end_time: proc.end_time,
+            create_time: utils::time::time_millis(),
+            timestamp: utils::time::timestamp(),
+        };
+
+        let archives = self.archives();
+        if archives.exists(&archive.id)? {
+            archives.update(&archive)?;
+        } else {
+            archives.create(&archive)?;
+        }
+        Ok(())
+    }
+
+    fn archive_value(&self, proc: &data::Proc) -> Result<JsonValue> {
+        let q = Query::new().push(Cond::and().push(Expr::eq("pid", proc.id.clone())));
+        let tasks = self.tasks().query(&q)?;
+        let messages = self.messages().query(&q)?;
+        let history = self.history().query(&q)?;
+        Ok(json!({
+            "proc": proc,
+            "tasks": tasks.rows,
+            "messages": messages.rows,
+            "history": history.rows,
+        }))
+    }
+
     pub fn set_message(&self, id: &str, status: MessageStatus) -> Result<()> {
         if let Ok(mut message) = self.messages().find(id) {
             message.status = status;
@@ -198,17 +324,82 @@ impl Store {
         let data: data::Task = task.into_data()?;
         let id = Id::new(&task.pid, &task.id);
         match collection.find(&id.id()) {
-            Ok(_) => {
+            Ok(prev) => {
                 collection.update(&data)?;
+                if self.is_task_history() {
+                    self.add_task_history(Some(&prev), &data)?;
+                }
             }
             Err(_) => {
                 collection.create(&data)?;
+                if self.is_task_history() {
+                    self.add_task_history(None, &data)?;
+                }
             }
         }
 
         Ok(())
     }
 
+    /// append the task state transition and vars mutation to the history
+    fn add_task_history(&self, prev: Option<&data::Task>, task: &data::Task) -> Result<()> {
+        let collection = self.history();
+        let prev_state = prev.map(|t| t.state.clone()).unwrap_or_default();
+        if prev_state != task.state {
+            let value = json!({ "prev": prev_state });
+            collection.create(&data::History::new(
+                &task.pid,
+                &task.tid,
+                data::HistoryKind::State,
+                &task.state,
+                &value.to_string(),
+            ))?;
+        }
+
+        let prev_data = match prev {
+            Some(t) => serde_json::from_str::<Vars>(&t.data)
+                .map_err(|err| ActError::Store(err.to_string()))?,
+            None => Vars::new(),
+        };
+        let data = serde_json::from_str::<Vars>(&task.data)
+            .map_err(|err| ActError::Store(err.to_string()))?;
+
+        let mut changes = Vars::new();
+        for (key, value) in data.iter() {
+            if prev_data.get_value(key) != Some(value) {
+                changes.set(key, value.clone());
+            }
+        }
+        for (key, _) in prev_data.iter() {
+            if !data.contains_key(key) {
+                changes.set(key, serde_json::Value::Null);
+            }
+        }
+        // ignore the internal vars such as $params
+        changes.retain(|key, _| !key.starts_with('$'));
+        if !changes.is_empty() {
+            collection.create(&data::History::new(
+                &task.pid,
+                &task.tid,
+                data::HistoryKind::Vars,
+                "",
+                &changes.to_string(),
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn remove_history(&self, pid: &str) -> Result<bool> {
+        debug!("remove_history pid={}", pid);
+        let q = Query::new().push(Cond::and().push(Expr::eq("pid", pid.to_string())));
+        let items = self.history().query(&q)?;
+        for item in items.rows {
+            self.history().delete(&item.id)?;
+        }
+        Ok(true)
+    }
+
     pub fn upsert_proc(&self, proc: &Arc<scheduler::Process>) -> Result<()> {
         debug!("upsert process: {}", proc.id());
         let collection = self.procs();
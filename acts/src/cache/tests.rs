@@ -1,5 +1,8 @@
 use crate::{
-    EngineBuilder, Workflow, data,
+    EngineBuilder, Workflow,
+    config::{ConfigRetention, ConfigRetentionRule},
+    data,
+    query::{Cond, Expr, Query},
     scheduler::{NodeTree, Process, TaskState},
     utils,
 };
@@ -81,6 +84,8 @@ async fn cache_remove() {
         let pid = utils::longid();
         let proc = Process::new(&pid, &rt);
         cache.push_proc(&proc);
+        let history = data::History::new(&pid, "$", data::HistoryKind::State, "running", "{}");
+        cache.store().history().create(&history).unwrap();
         pids.push(pid);
     }
 
@@ -94,6 +99,10 @@ async fn cache_remove() {
 
         let exists = cache.store().procs().exists(pid).unwrap();
         assert!(!exists);
+
+        // the history is removed with the process
+        let q = Query::new().push(Cond::and().push(Expr::eq("pid", pid.clone())));
+        assert_eq!(cache.store().history().query(&q).unwrap().count, 0);
     }
     assert_eq!(cache.count(), 0);
 }
@@ -314,3 +323,257 @@ async fn cache_restore_less_cap() {
         .unwrap();
     assert_eq!(cache.count(), 3);
 }
+
+#[tokio::test]
+async fn cache_purge_expired() {
+    let engine = EngineBuilder::new().build().await.unwrap().start();
+    let model = Workflow::new()
+        .with_id("m1")
+        .with_step(|step| step.with_name("step1"));
+    let rt = engine.runtime();
+    let cache = rt.cache();
+
+    let now = utils::time::time_millis();
+    let day = 24 * 60 * 60 * 1000;
+    let items = [
+        (TaskState::Completed, now - 10 * day, true),
+        (TaskState::Completed, now - day, false),
+        (TaskState::Error, now - 10 * day, false),
+        (TaskState::Running, 0, false),
+    ];
+    let mut pids = Vec::new();
+    for (state, end_time, _) in &items {
+        let pid = utils::longid();
+        let proc = data::Proc {
+            id: pid.clone(),
+            name: "test".to_string(),
+            mid: "m1".to_string(),
+            state: state.to_string(),
+            start_time: 0,
+            end_time: *end_time,
+            timestamp: 0,
+            model: model.to_json().unwrap(),
+            env: "{}".to_string(),
+            err: None,
+        };
+        cache.store().procs().create(&proc).unwrap();
+        pids.push(pid);
+    }
+
+    let retention = ConfigRetention {
+        keep_days: Some(7),
+        keep_error_days: Some(30),
+        ..Default::default()
+    };
+    let count = cache.purge(&retention, now).unwrap();
+    assert_eq!(count, 1);
+    for (index, (_, _, purged)) in items.iter().enumerate() {
+        let exists = cache.store().procs().exists(&pids[index]).unwrap();
+        assert_eq!(exists, !purged);
+    }
+}
+
+#[tokio::test]
+async fn cache_purge_model_rule() {
+    let engine = EngineBuilder::new().build().await.unwrap().start();
+    let rt = engine.runtime();
+    let cache = rt.cache();
+
+    let now = utils::time::time_millis();
+    let day = 24 * 60 * 60 * 1000;
+    let mut pids = Vec::new();
+    for mid in ["m1", "m2"] {
+        let pid = utils::longid();
+        let proc = data::Proc {
+            id: pid.clone(),
+            name: "test".to_string(),
+            mid: mid.to_string(),
+            state: TaskState::Completed.to_string(),
+            start_time: 0,
+            end_time: now - 3 * day,
+            timestamp: 0,
+            model: "{}".to_string(),
+            env: "{}".to_string(),
+            err: None,
+        };
+        cache.store().procs().create(&proc).unwrap();
+        pids.push(pid);
+    }
+
+    let mut retention = ConfigRetention {
+        keep_days: Some(7),
+        ..Default::default()
+    };
+    retention.models.insert(
+        "m2".to_string(),
+        ConfigRetentionRule {
+            keep_days: Some(1),
+            keep_error_days: None,
+        },
+    );
+    let count = cache.purge(&retention, now).unwrap();
+    assert_eq!(count, 1);
+    assert!(cache.store().procs().exists(&pids[0]).unwrap());
+    assert!(!cache.store().procs().exists(&pids[1]).unwrap());
+}
+
+#[tokio::test]
+async fn cache_purge_batch_size() {
+    let engine = EngineBuilder::new().build().await.unwrap().start();
+    let rt = engine.runtime();
+    let cache = rt.cache();
+
+    let now = utils::time::time_millis();
+    for _ in 0..3 {
+        let proc = data::Proc {
+            id: utils::longid(),
+            name: "test".to_string(),
+            mid: "m1".to_string(),
+            state: TaskState::Completed.to_string(),
+            start_time: 0,
+            end_time: now - 1,
+            timestamp: 0,
+            model: "{}".to_string(),
+            env: "{}".to_string(),
+            err: None,
+        };
+        cache.store().procs().create(&proc).unwrap();
+    }
+
+    // the rest are purged by the next call
+    let retention = ConfigRetention {
+        keep_days: Some(0),
+        batch_size: Some(2),
+        ..Default::default()
+    };
+    assert_eq!(cache.purge(&retention, now).unwrap(), 2);
+    assert_eq!(cache.purge(&retention, now).unwrap(), 1);
+    assert_eq!(cache.purge(&retention, now).unwrap(), 0);
+}
+
+#[tokio::test]
+async fn cache_purge_archive() {
+    let engine = EngineBuilder::new().build().await.unwrap().start();
+    let rt = engine.runtime();
+    let cache = rt.cache();
+
+    let dir = std::env::temp_dir().join(utils::longid());
+    let now = utils::time::time_millis();
+    let pid = utils::longid();
+    let proc = data::Proc {
+        id: pid.clone(),
+        name: "test".to_string(),
+        mid: "m1".to_string(),
+        state: TaskState::Completed.to_string(),
+        start_time: 0,
+        end_time: now - 1,
+        timestamp: 0,
+        model: "{}".to_string(),
+        env: "{}".to_string(),
+        err: None,
+    };
+    cache.store().procs().create(&proc).unwrap();
+    cache
+        .store()
+        .messages()
+        .create(&data::Message {
+            id: utils::longid(),
+            pid: pid.clone(),
+            ..Default::default()
+        })
+        .unwrap();
+
+    let retention = ConfigRetention {
+        keep_days: Some(0),
+        archive_dir: Some(dir.to_string_lossy().to_string()),
+        ..Default::default()
+    };
+    let count = cache.purge(&retention, now).unwrap();
+    assert_eq!(count, 1);
+    assert!(!cache.store().procs().exists(&pid).unwrap());
+    assert!(dir.join(format!("{pid}.json.gz")).exists());
+
+    let q = Query::new().push(Cond::and().push(Expr::eq("pid", pid.clone())));
+    assert_eq!(cache.store().messages().query(&q).unwrap().count, 0);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn cache_purge_pages() {
+    let engine = EngineBuilder::new().build().await.unwrap().start();
+    let rt = engine.runtime();
+    let cache = rt.cache();
+
+    let now = utils::time::time_millis();
+    let day = 24 * 60 * 60 * 1000;
+    let mut kept = Vec::new();
+    for index in 0..700 {
+        let pid = utils::longid();
+        let end_time = if index % 2 == 0 { now - 10 * day } else { now };
+        let proc = data::Proc {
+            id: pid.clone(),
+            name: "test".to_string(),
+            mid: "m1".to_string(),
+            state: TaskState::Completed.to_string(),
+            start_time: 0,
+            end_time,
+            timestamp: 0,
+            model: "{}".to_string(),
+            env: "{}".to_string(),
+            err: None,
+        };
+        cache.store().procs().create(&proc).unwrap();
+        if end_time == now {
+            kept.push(pid);
+        }
+    }
+
+    let retention = ConfigRetention {
+        keep_days: Some(7),
+        batch_size: Some(1000),
+        ..Default::default()
+    };
+    let count = cache.purge(&retention, now).unwrap();
+    assert_eq!(count, 350);
+    for pid in kept.iter() {
+        assert!(cache.store().procs().exists(pid).unwrap());
+    }
+}
+
+#[tokio::test]
+async fn cache_purge_archive_collection() {
+    let engine = EngineBuilder::new().build().await.unwrap().start();
+    let rt = engine.runtime();
+    let cache = rt.cache();
+
+    let now = utils::time::time_millis();
+    let pid = utils::longid();
+    let proc = data::Proc {
+        id: pid.clone(),
+        name: "test".to_string(),
+        mid: "m1".to_string(),
+        state: TaskState::Completed.to_string(),
+        start_time: 0,
+        end_time: now - 1,
+        timestamp: 0,
+        model: "{}".to_string(),
+        env: "{}".to_string(),
+        err: None,
+    };
+    cache.store().procs().create(&proc).unwrap();
+
+    let retention = ConfigRetention {
+        keep_days: Some(0),
+        archive_collection: Some(true),
+        ..Default::default()
+    };
+    let count = cache.purge(&retention, now).unwrap();
+    assert_eq!(count, 1);
+    assert!(!cache.store().procs().exists(&pid).unwrap());
+
+    let archive = cache.store().archives().find(&pid).unwrap();
+    assert_eq!(archive.mid, "m1");
+    assert_eq!(archive.end_time, now - 1);
+    let value: serde_json::Value = serde_json::from_str(&archive.data).unwrap();
+    assert_eq!(value["proc"]["id"], pid);
+}
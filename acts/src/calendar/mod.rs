@@ -0,0 +1,318 @@
+#[cfg(test)]
+mod tests;
+
+use crate::{ActError, Config, Result, ShareLock, TimeoutLimit, TimeoutUnit, utils};
+use chrono::{Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, RwLock},
+};
+
+/// the calendar name to use when the workflow does not set it
+pub const DEFAULT_CALENDAR: &str = "default";
+
+/// the max days to search the next working day
+const MAX_SEARCH_DAYS: i64 = 3660;
+
+/// Clock trait to get the current time
+/// It can be replaced by `Extender::set_clock` to make the time deterministic
+///
+/// # Example
+/// ```rust
+///   use acts::Clock;
+///   pub struct FixedClock(i64);
+///   impl Clock for FixedClock {
+///     fn now(&self) -> i64 {
+///         self.0
+///     }
+///   }
+/// ```
+pub trait Clock: Send + Sync {
+    /// the current time in milliseconds
+    fn now(&self) -> i64;
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> i64 {
+        utils::time::time_millis()
+    }
+}
+
+/// the working calendar to calculate the business units such as `3bd` and `4bh`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Calendar {
+    /// the work start time in the format of 'HH:MM'
+    pub work_start: String,
+
+    /// the work end time in the format of 'HH:MM'
+    pub work_end: String,
+
+    /// the weekend days from 1 (monday) to 7 (sunday)
+    pub weekends: Vec<u32>,
+
+    /// the holidays in the format of 'YYYY-MM-DD'
+    pub holidays: Vec<String>,
+
+    /// the utc offset such as '+08:00'
+    pub timezone: String,
+}
+
+/// the parsed calendar rules
+struct Rules {
+    start: NaiveTime,
+    end: NaiveTime,
+    tz: FixedOffset,
+    holidays: HashSet<NaiveDate>,
+}
+
+impl Default for Calendar {
+    fn default() -> Self {
+        Self {
+            work_start: "09:00".to_string(),
+            work_end: "18:00".to_string(),
+            weekends: vec![6, 7],
+            holidays: Vec::new(),
+            timezone: "+00:00".to_string(),
+        }
+    }
+}
+
+impl Calendar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_work_hours(mut self, start: &str, end: &str) -> Self {
+        self.work_start = start.to_string();
+        self.work_end = end.to_string();
+        self
+    }
+
+    pub fn with_weekends(mut self, days: &[u32]) -> Self {
+        self.weekends = days.to_vec();
+        self
+    }
+
+    pub fn with_holiday(mut self, date: &str) -> Self {
+        self.holidays.push(date.to_string());
+        self
+    }
+
+    pub fn with_timezone(mut self, tz: &str) -> Self {
+        self.timezone = tz.to_string();
+        self
+    }
+
+    /// check the calendar settings
+    pub fn check(&self) -> Result<()> {
+        self.rules().map(|_| ())
+    }
+
+    /// check if the date is not a weekend or holiday
+    pub fn is_work_day(&self, date: &str) -> Result<bool> {
+        let rules = self.rules()?;
+        let date = parse_date(date)?;
+        Ok(self.is_work_date(&rules, &date))
+    }
+
+    /// calculate the deadline in milliseconds from the start time
+    pub fn deadline(&self, start: i64, limit: &TimeoutLimit) -> Result<i64> {
+        if !limit.is_business() {
+            return Ok(start + limit.as_secs() * 1000);
+        }
+        if limit.value <= 0 {
+            return Ok(start);
+        }
+
+        let rules = self.rules()?;
+        let local = rules
+            .tz
+            .timestamp_millis_opt(start)
+            .single()
+            .ok_or(ActError::Runtime(format!(
+                "invalid calendar time '{start}'"
+            )))?
+            .naive_local();
+
+        let mut time = self.next_work_time(&rules, local)?;
+        match limit.unit {
+            TimeoutUnit::BusinessHour => {
+                let mut remaining = limit.value * 60 * 60 * 1000;
+                loop {
+                    let end = time.date().and_time(rules.end);
+                    let available = (end - time).num_milliseconds();
+                    if remaining <= available {
+                        time += Duration::milliseconds(remaining);
+                        break;
+                    }
+                    remaining -= available;
+                    time = self.next_work_time(&rules, end)?;
+                }
+            }
+            _ => {
+                for _ in 0..limit.value {
+                    let date = self.next_work_date(&rules, time.date() + Duration::days(1))?;
+                    time = date.and_time(time.time());
+                }
+            }
+        }
+
+        let deadline = rules
+            .tz
+            .from_local_datetime(&time)
+            .single()
+            .ok_or(ActError::Runtime(format!("invalid calendar time '{time}'")))?;
+        Ok(deadline.timestamp_millis())
+    }
+
+    fn rules(&self) -> Result<Rules> {
+        let start = parse_time(&self.work_start)?;
+        let end = parse_time(&self.work_end)?;
+        if start >= end {
+            return Err(ActError::Config(format!(
+                "calendar work_start '{}' should be before work_end '{}'",
+                self.work_start, self.work_end
+            )));
+        }
+
+        if let Some(day) = self.weekends.iter().find(|d| !(1..=7).contains(*d)) {
+            return Err(ActError::Config(format!(
+                "calendar weekend '{day}' should be from 1 to 7"
+            )));
+        }
+        if self.weekends.iter().collect::<HashSet<_>>().len() == 7 {
+            return Err(ActError::Config(
+                "calendar weekends should not be all of the days".to_string(),
+            ));
+        }
+
+        let tz = self.timezone.parse::<FixedOffset>().map_err(|err| {
+            ActError::Config(format!(
+                "calendar timezone '{}' parse error with '{err}'",
+                self.timezone
+            ))
+        })?;
+        let holidays = self
+            .holidays
+            .iter()
+            .map(|d| parse_date(d))
+            .collect::<Result<HashSet<_>>>()?;
+
+        Ok(Rules {
+            start,
+            end,
+            tz,
+            holidays,
+        })
+    }
+
+    fn is_work_date(&self, rules: &Rules, date: &NaiveDate) -> bool {
+        let weekday = date.weekday().number_from_monday();
+        !self.weekends.contains(&weekday) && !rules.holidays.contains(date)
+    }
+
+    fn next_work_date(&self, rules: &Rules, date: NaiveDate) -> Result<NaiveDate> {
+        let mut date = date;
+        for _ in 0..MAX_SEARCH_DAYS {
+            if self.is_work_date(rules, &date) {
+                return Ok(date);
+            }
+            date += Duration::days(1);
+        }
+        Err(ActError::Runtime(format!(
+            "cannot find the working day after '{date}'"
+        )))
+    }
+
+    /// move the time to the working hours
+    fn next_work_time(&self, rules: &Rules, time: NaiveDateTime) -> Result<NaiveDateTime> {
+        let mut date = time.date();
+        let mut time = time.time();
+        if time >= rules.end {
+            date += Duration::days(1);
+            time = rules.start;
+        }
+
+        let work_date = self.next_work_date(rules, date)?;
+        if work_date != date || time < rules.start {
+            time = rules.start;
+        }
+
+        Ok(work_date.and_time(time))
+    }
+}
+
+/// the calendars and the clock used by the runtime
+pub struct Calendars {
+    calendars: ShareLock<HashMap<String, Calendar>>,
+    clock: ShareLock<Arc<dyn Clock>>,
+}
+
+impl std::fmt::Debug for Calendars {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Calendars")
+            .field("calendars", &self.calendars)
+            .finish()
+    }
+}
+
+impl Calendars {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            calendars: Arc::new(RwLock::new(config.calendars())),
+            clock: Arc::new(RwLock::new(Arc::new(SystemClock))),
+        }
+    }
+
+    pub fn register(&self, name: &str, calendar: &Calendar) {
+        let mut calendars = self.calendars.write().unwrap();
+        calendars.insert(name.to_string(), calendar.clone());
+    }
+
+    pub fn get(&self, name: &str) -> Option<Calendar> {
+        let calendars = self.calendars.read().unwrap();
+        match calendars.get(name) {
+            Some(calendar) => Some(calendar.clone()),
+            None if name == DEFAULT_CALENDAR => Some(Calendar::default()),
+            None => None,
+        }
+    }
+
+    pub fn set_clock(&self, clock: Arc<dyn Clock>) {
+        *self.clock.write().unwrap() = clock;
+    }
+
+    pub fn now(&self) -> i64 {
+        self.clock.read().unwrap().now()
+    }
+
+    /// calculate the deadline by the calendar name, the default calendar is used when not set
+    pub fn deadline(&self, name: Option<&str>, start: i64, limit: &TimeoutLimit) -> Result<i64> {
+        if !limit.is_business() {
+            return Ok(start + limit.as_secs() * 1000);
+        }
+
+        let name = name.unwrap_or(DEFAULT_CALENDAR);
+        let calendar = self
+            .get(name)
+            .ok_or(ActError::Runtime(format!("cannot find calendar '{name}'")))?;
+        calendar.deadline(start, limit)
+    }
+}
+
+fn parse_time(value: &str) -> Result<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H:%M").map_err(|err| {
+        ActError::Config(format!("calendar time '{value}' parse error with '{err}'"))
+    })
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|err| {
+        ActError::Config(format!("calendar date '{value}' parse error with '{err}'"))
+    })
+}
@@ -0,0 +1,222 @@
+use super::{Calendar, Calendars, Clock};
+use crate::{Config, TimeoutLimit, config::ConfigData};
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use std::sync::Arc;
+
+fn millis(time: &str) -> i64 {
+    NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M")
+        .unwrap()
+        .and_utc()
+        .timestamp_millis()
+}
+
+fn deadline(calendar: &Calendar, start: &str, limit: &str) -> i64 {
+    calendar
+        .deadline(millis(start), &TimeoutLimit::parse(limit).unwrap())
+        .unwrap()
+}
+
+struct FixedClock(i64);
+impl Clock for FixedClock {
+    fn now(&self) -> i64 {
+        self.0
+    }
+}
+
+#[test]
+fn calendar_wall_clock_units() {
+    let calendar = Calendar::new();
+    assert_eq!(
+        deadline(&calendar, "2026-10-16 17:00", "2h"),
+        millis("2026-10-16 19:00")
+    );
+    assert_eq!(
+        deadline(&calendar, "2026-10-16 17:00", "1d"),
+        millis("2026-10-17 17:00")
+    );
+}
+
+#[test]
+fn calendar_business_days_skip_weekends() {
+    let calendar = Calendar::new();
+    assert_eq!(
+        deadline(&calendar, "2026-10-16 10:00", "1bd"),
+        millis("2026-10-19 10:00")
+    );
+    assert_eq!(
+        deadline(&calendar, "2026-10-16 10:00", "3bd"),
+        millis("2026-10-21 10:00")
+    );
+}
+
+#[test]
+fn calendar_business_days_after_work() {
+    let calendar = Calendar::new();
+    assert_eq!(
+        deadline(&calendar, "2026-10-16 20:00", "1bd"),
+        millis("2026-10-20 09:00")
+    );
+    assert_eq!(
+        deadline(&calendar, "2026-10-17 10:00", "1bd"),
+        millis("2026-10-20 09:00")
+    );
+}
+
+#[test]
+fn calendar_business_hours() {
+    let calendar = Calendar::new();
+    assert_eq!(
+        deadline(&calendar, "2026-10-16 16:00", "4bh"),
+        millis("2026-10-19 11:00")
+    );
+    assert_eq!(
+        deadline(&calendar, "2026-10-19 07:00", "2bh"),
+        millis("2026-10-19 11:00")
+    );
+    assert_eq!(
+        deadline(&calendar, "2026-10-19 09:00", "9bh"),
+        millis("2026-10-19 18:00")
+    );
+}
+
+#[test]
+fn calendar_business_zero() {
+    let calendar = Calendar::new();
+    assert_eq!(
+        deadline(&calendar, "2026-10-17 10:00", "0bd"),
+        millis("2026-10-17 10:00")
+    );
+}
+
+#[test]
+fn calendar_holidays() {
+    let calendar = Calendar::new().with_holiday("2026-12-25");
+    assert_eq!(
+        deadline(&calendar, "2026-12-24 10:00", "1bd"),
+        millis("2026-12-28 10:00")
+    );
+    assert!(!calendar.is_work_day("2026-12-25").unwrap());
+    assert!(!calendar.is_work_day("2026-12-26").unwrap());
+    assert!(calendar.is_work_day("2026-12-24").unwrap());
+}
+
+#[test]
+fn calendar_weekends() {
+    let calendar = Calendar::new().with_weekends(&[5, 6]);
+    assert_eq!(
+        deadline(&calendar, "2026-10-15 10:00", "1bd"),
+        millis("2026-10-18 10:00")
+    );
+}
+
+#[test]
+fn calendar_work_hours() {
+    let calendar = Calendar::new().with_work_hours("08:00", "12:00");
+    assert_eq!(
+        deadline(&calendar, "2026-10-19 11:00", "2bh"),
+        millis("2026-10-20 09:00")
+    );
+}
+
+#[test]
+fn calendar_timezone() {
+    let calendar = Calendar::new().with_timezone("+08:00");
+
+    // it is 2026-10-16 09:00 in +08:00
+    assert_eq!(
+        deadline(&calendar, "2026-10-16 01:00", "1bd"),
+        millis("2026-10-19 01:00")
+    );
+    assert_eq!(
+        deadline(&calendar, "2026-10-16 01:00", "9bh"),
+        millis("2026-10-16 10:00")
+    );
+}
+
+#[test]
+fn calendar_check_error() {
+    assert!(Calendar::new().check().is_ok());
+    assert!(
+        Calendar::new()
+            .with_work_hours("18:00", "09:00")
+            .check()
+            .is_err()
+    );
+    assert!(
+        Calendar::new()
+            .with_work_hours("9am", "18:00")
+            .check()
+            .is_err()
+    );
+    assert!(Calendar::new().with_weekends(&[8]).check().is_err());
+    assert!(
+        Calendar::new()
+            .with_weekends(&[1, 2, 3, 4, 5, 6, 7])
+            .check()
+            .is_err()
+    );
+    assert!(Calendar::new().with_timezone("cst").check().is_err());
+    assert!(Calendar::new().with_holiday("12/25").check().is_err());
+}
+
+#[test]
+fn calendar_from_config() {
+    let table = toml::from_str::<toml::Table>(
+        r#"
+        [calendars.cn]
+        work_start = "09:30"
+        weekends = [6, 7]
+        holidays = ["2026-10-01"]
+        timezone = "+08:00"
+        "#,
+    )
+    .unwrap();
+    let data = ConfigData::deserialize(table.clone()).unwrap();
+    let config = Config { data, table };
+
+    let calendars = Calendars::new(&config);
+    let calendar = calendars.get("cn").unwrap();
+    assert_eq!(calendar.work_start, "09:30");
+    assert_eq!(calendar.work_end, "18:00");
+    assert_eq!(calendar.holidays, vec!["2026-10-01"]);
+    assert_eq!(calendar.timezone, "+08:00");
+}
+
+#[test]
+fn calendar_calendars_get() {
+    let calendars = Calendars::new(&Config::default());
+    assert_eq!(calendars.get("default").unwrap(), Calendar::default());
+    assert!(calendars.get("cn").is_none());
+
+    let calendar = Calendar::new().with_holiday("2026-10-01");
+    calendars.register("cn", &calendar);
+    assert_eq!(calendars.get("cn").unwrap(), calendar);
+}
+
+#[test]
+fn calendar_calendars_deadline() {
+    let calendars = Calendars::new(&Config::default());
+    let limit = TimeoutLimit::parse("1bd").unwrap();
+    let start = millis("2026-10-16 10:00");
+
+    assert_eq!(
+        calendars.deadline(None, start, &limit).unwrap(),
+        millis("2026-10-19 10:00")
+    );
+    assert!(calendars.deadline(Some("cn"), start, &limit).is_err());
+
+    // the wall-clock units do not need the calendar
+    let limit = TimeoutLimit::parse("1d").unwrap();
+    assert_eq!(
+        calendars.deadline(Some("cn"), start, &limit).unwrap(),
+        millis("2026-10-17 10:00")
+    );
+}
+
+#[test]
+fn calendar_calendars_clock() {
+    let calendars = Calendars::new(&Config::default());
+    calendars.set_clock(Arc::new(FixedClock(100)));
+    assert_eq!(calendars.now(), 100);
+}
@@ -1,5 +1,6 @@
+use crate::Calendar;
 use serde::Deserialize;
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 use toml::Table;
 
 #[derive(Debug, Clone)]
@@ -14,6 +15,50 @@ pub struct ConfigLog {
     pub level: String,
 }
 
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ConfigRetentionRule {
+    // days to keep the finished processes
+    pub keep_days: Option<i64>,
+    // days to keep the error processes, it is keep_days by default
+    pub keep_error_days: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ConfigRetention {
+    // days to keep the finished processes
+    pub keep_days: Option<i64>,
+    // days to keep the error processes, it is keep_days by default
+    pub keep_error_days: Option<i64>,
+
+    // the interval to check the expired processes
+    pub interval_secs: Option<i64>,
+
+    // the max processes to purge in one tick, the rest are purged in the next ticks
+    pub batch_size: Option<usize>,
+
+    // archive the processes as compressed json files to the dir before purging
+    // the processes will be purged directly when not set
+    pub archive_dir: Option<String>,
+
+    // archive the processes to the archives collection before purging
+    pub archive_collection: Option<bool>,
+
+    // overrides the retention days by model id
+    #[serde(default)]
+    pub models: HashMap<String, ConfigRetentionRule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfigMemStore {
+    // the dir to save the snapshot and journal files
+    pub dir: String,
+    // the interval to write the snapshot and truncate the journal
+    pub snapshot_interval_secs: Option<i64>,
+    // sync the journal file to disk on every write, default to true
+    // the writes are left to the os to flush when setting to false, the snapshot is always synced
+    pub sync_journal: Option<bool>,
+}
+
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct ConfigData {
     pub cache_cap: Option<i64>,
@@ -25,8 +70,21 @@ pub struct ConfigData {
     // do not remove process and tasks on complete
     pub keep_processes: Option<bool>,
 
+    // record the task state transitions and vars mutations to the history, default to true
+    // the history is read once more and written on each task update when enabled
+    pub task_history: Option<bool>,
+
+    // retention policy for the finished processes
+    pub retention: Option<ConfigRetention>,
+
+    // persist the mem store to local dir
+    pub mem_store: Option<ConfigMemStore>,
+
     // log config
     pub log: Option<ConfigLog>,
+
+    // the working calendars by name to calculate the business units
+    pub calendars: Option<HashMap<String, Calendar>>,
 }
 
 impl Default for Config {
@@ -81,6 +139,21 @@ impl Config {
     pub fn tick_interval_secs(&self) -> i64 {
         self.data.tick_interval_secs.unwrap_or(15)
     }
+    pub fn task_history(&self) -> bool {
+        self.data.task_history.unwrap_or(true)
+    }
+
+    pub fn retention(&self) -> Option<&ConfigRetention> {
+        self.data.retention.as_ref()
+    }
+
+    pub fn mem_store(&self) -> Option<&ConfigMemStore> {
+        self.data.mem_store.as_ref()
+    }
+
+    pub fn calendars(&self) -> HashMap<String, Calendar> {
+        self.data.calendars.clone().unwrap_or_default()
+    }
 
     pub fn log(&self) -> ConfigLog {
         self.data.log.clone().unwrap_or(ConfigLog {
@@ -89,3 +162,53 @@ impl Config {
         })
     }
 }
+
+impl ConfigMemStore {
+    pub fn snapshot_interval_secs(&self) -> i64 {
+        self.snapshot_interval_secs.unwrap_or(300)
+    }
+
+    pub fn sync_journal(&self) -> bool {
+        self.sync_journal.unwrap_or(true)
+    }
+}
+
+impl ConfigRetention {
+    pub fn interval_secs(&self) -> i64 {
+        self.interval_secs.unwrap_or(3600)
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size.unwrap_or(100).max(1)
+    }
+
+    /// get the days to keep the process by model id and process state
+    pub fn keep_days(&self, mid: &str, is_error: bool) -> i64 {
+        let (keep_days, keep_error_days) = match self.models.get(mid) {
+            Some(rule) => (
+                rule.keep_days.or(self.keep_days),
+                rule.keep_error_days.or(self.keep_error_days),
+            ),
+            None => (self.keep_days, self.keep_error_days),
+        };
+
+        let keep_days = keep_days.unwrap_or(30);
+        if is_error {
+            return keep_error_days.unwrap_or(keep_days);
+        }
+
+        keep_days
+    }
+
+    /// get the min days to keep the processes of any model and state
+    pub fn min_keep_days(&self) -> i64 {
+        let keep_days = self.keep_days.unwrap_or(30);
+        let keep_error_days = self.keep_error_days.unwrap_or(keep_days);
+        self.models
+            .keys()
+            .flat_map(|mid| [self.keep_days(mid, false), self.keep_days(mid, true)])
+            .chain([keep_days, keep_error_days])
+            .min()
+            .unwrap_or(keep_days)
+    }
+}
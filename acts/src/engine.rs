@@ -1,5 +1,5 @@
 use crate::{
-    ChannelOptions, Signal,
+    ChannelOptions, Result, Signal,
     config::Config,
     export::{Channel, Executor, Extender},
     package,
@@ -7,7 +7,7 @@ use crate::{
 };
 
 use std::sync::Arc;
-use tracing::info;
+use tracing::{error, info};
 
 /// Workflow Engine
 ///
@@ -127,10 +127,12 @@ impl Engine {
         self.runtime.is_running()
     }
 
-    fn init(&self) {
+    pub(crate) fn init(&self) -> Result<()> {
         info!("init");
-        self.runtime.init(self);
+        self.runtime.init(self)?;
         package::init(self);
+
+        Ok(())
     }
 
     pub(crate) fn new_with_config(config: &Config) -> Self {
@@ -142,7 +144,9 @@ impl Engine {
     }
 
     pub fn start(self) -> Self {
-        self.init();
+        // the store errors are returned by EngineBuilder::build before starting
+        self.init()
+            .unwrap_or_else(|err| error!("engine.start init={}", err));
         self
     }
 }
@@ -20,7 +20,7 @@ pub enum ActError {
     Exception { ecode: String, message: String },
 
     #[error("{0}")]
-    Model(String),
+    Model(ModelError),
 
     #[error("{0}")]
     Runtime(String),
@@ -46,6 +46,75 @@ pub struct Error {
     pub message: String,
 }
 
+/// the model error with the location in the model source
+#[derive(Default, Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ModelError {
+    #[serde(default)]
+    pub message: String,
+
+    /// the yaml path of the error such as `steps[0].acts[1]`, empty for the root
+    #[serde(default)]
+    pub path: String,
+
+    /// the 1-based line in the yaml source
+    #[serde(default)]
+    pub line: Option<usize>,
+
+    /// the 1-based column in the yaml source
+    #[serde(default)]
+    pub column: Option<usize>,
+
+    /// the id of the node which causes the error
+    #[serde(default)]
+    pub node: Option<String>,
+}
+
+impl fmt::Display for ModelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl ModelError {
+    pub fn new(message: &str) -> Self {
+        Self {
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.path = path.to_string();
+        self
+    }
+
+    pub fn with_location(mut self, line: usize, column: usize) -> Self {
+        self.line = Some(line);
+        self.column = Some(column);
+        self
+    }
+
+    pub fn with_node(mut self, id: &str) -> Self {
+        self.node = Some(id.to_string());
+        self
+    }
+}
+
+impl From<String> for ModelError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&str> for ModelError {
+    fn from(message: &str) -> Self {
+        Self::new(message)
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let text = serde_json::to_string(self).unwrap();
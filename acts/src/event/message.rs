@@ -1,4 +1,7 @@
-use crate::{TaskState, Vars, data, utils};
+use crate::{
+    TaskAssignment, TaskState, Vars, data,
+    utils::{self, consts},
+};
 use core::fmt;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -111,6 +114,17 @@ impl Message {
         self.uses == uses
     }
 
+    /// the sla deadline of the task in milliseconds
+    pub fn deadline(&self) -> Option<i64> {
+        self.inputs.get::<i64>(consts::MESSAGE_DEADLINE)
+    }
+
+    /// the assignment of the irq act
+    pub fn assignment(&self) -> Option<TaskAssignment> {
+        self.inputs
+            .get::<TaskAssignment>(consts::MESSAGE_ASSIGNMENT)
+    }
+
     pub fn is_irq(&self) -> bool {
         self.uses == "acts.core.irq"
     }
@@ -1,4 +1,5 @@
 mod act_executor;
+mod backup_executor;
 mod event_executor;
 mod message_executor;
 mod model_executor;
@@ -6,6 +7,7 @@ mod package_executor;
 mod process_executor;
 mod task_executor;
 
+pub use backup_executor::{BUNDLE_VERSION, Bundle};
 use serde::Serialize;
 use serde_json::json;
 
@@ -30,6 +32,7 @@ pub struct Executor {
     task: task_executor::TaskExecutor,
     pack: package_executor::PackageExecutor,
     evt: event_executor::EventExecutor,
+    backup: backup_executor::BackupExecutor,
 }
 
 impl ExecutorQuery {
@@ -88,6 +91,7 @@ impl Executor {
             task: task_executor::TaskExecutor::new(rt),
             pack: package_executor::PackageExecutor::new(rt),
             evt: event_executor::EventExecutor::new(rt),
+            backup: backup_executor::BackupExecutor::new(rt),
         }
     }
 
@@ -126,4 +130,9 @@ impl Executor {
     pub fn evt(&self) -> &event_executor::EventExecutor {
         &self.evt
     }
+
+    /// executor for backup, export and import of the engine state
+    pub fn backup(&self) -> &backup_executor::BackupExecutor {
+        &self.backup
+    }
 }
@@ -1,5 +1,8 @@
 use crate::event::EventAction;
-use crate::{Action, Result, Vars, scheduler::Runtime};
+use crate::{
+    ActError, Action, Result, Vars,
+    scheduler::{Runtime, Task},
+};
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -54,6 +57,29 @@ impl ActExecutor {
         self.do_action(pid, tid, EventAction::SetProcessVars, options)
     }
 
+    /// claim the irq act by the candidate user or the user in the candidate groups
+    pub fn claim(&self, pid: &str, tid: &str, uid: &str, groups: &[String]) -> Result<()> {
+        self.assign(pid, tid, |task| task.claim(uid, groups))
+    }
+
+    /// release the claimed irq act to the candidates
+    pub fn unclaim(&self, pid: &str, tid: &str, uid: &str) -> Result<()> {
+        self.assign(pid, tid, |task| task.unclaim(uid))
+    }
+
+    /// delegate the irq act from the assignee to the user
+    pub fn delegate(&self, pid: &str, tid: &str, uid: &str, to: &str) -> Result<()> {
+        self.assign(pid, tid, |task| task.delegate(uid, to))
+    }
+
+    /// reassign the irq act to the user
+    pub fn reassign(&self, pid: &str, tid: &str, to: &str) -> Result<()> {
+        self.assign(pid, tid, |task| {
+            task.reassign(to);
+            Ok(())
+        })
+    }
+
     pub fn do_action(
         &self,
         pid: &str,
@@ -64,4 +90,25 @@ impl ActExecutor {
         self.runtime
             .do_action(&Action::new(pid, tid, action, options))
     }
+
+    /// change the assignment of the interrupted task and send the task message again
+    fn assign<F: FnOnce(&Task) -> Result<()>>(&self, pid: &str, tid: &str, f: F) -> Result<()> {
+        let task = self
+            .runtime
+            .proc(pid)
+            .and_then(|proc| proc.task(tid))
+            .ok_or(ActError::Runtime(format!(
+                "cannot find task '{tid}' in process '{pid}'"
+            )))?;
+        if !task.state().is_interrupted() {
+            return Err(ActError::Action(format!(
+                "task '{tid}' is not waiting for the user"
+            )));
+        }
+
+        f(&task)?;
+        self.runtime.cache().upsert(&task)?;
+        self.runtime.emitter().emit_message(&task.create_message());
+        Ok(())
+    }
 }
@@ -0,0 +1,269 @@
+use crate::{
+    ActError, DbCollection, Result,
+    data::{self, MessageStatus},
+    scheduler::{Runtime, TaskState},
+    store::query::*,
+    utils,
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::json;
+use std::sync::Arc;
+use tracing::instrument;
+
+/// the current bundle version
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// the page size to read the collections when exporting
+const EXPORT_PAGE_SIZE: usize = 1000;
+
+/// the engine state bundle for backup and migration
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Bundle {
+    pub version: u32,
+    pub create_time: i64,
+
+    #[serde(default)]
+    pub models: Vec<data::Model>,
+    #[serde(default)]
+    pub packages: Vec<data::Package>,
+    #[serde(default)]
+    pub events: Vec<data::Event>,
+    #[serde(default)]
+    pub procs: Vec<data::Proc>,
+    #[serde(default)]
+    pub tasks: Vec<data::Task>,
+    #[serde(default)]
+    pub messages: Vec<data::Message>,
+    #[serde(default)]
+    pub history: Vec<data::History>,
+}
+
+/// the header line of the ndjson bundle
+#[derive(Debug, Serialize, Deserialize)]
+struct BundleHeader {
+    version: u32,
+    create_time: i64,
+}
+
+/// the record line of the ndjson bundle
+#[derive(Debug, Serialize, Deserialize)]
+struct BundleLine {
+    kind: String,
+    data: serde_json::Value,
+}
+
+#[derive(Clone)]
+pub struct BackupExecutor {
+    runtime: Arc<Runtime>,
+}
+
+impl BackupExecutor {
+    pub fn new(rt: &Arc<Runtime>) -> Self {
+        Self {
+            runtime: rt.clone(),
+        }
+    }
+
+    /// export the models, packages, events, running processes with their tasks and the pending messages
+    /// the store writes are blocked while exporting to get a consistent bundle from the running engine
+    #[instrument(skip(self))]
+    pub fn export(&self) -> Result<Bundle> {
+        let store = self.runtime.store();
+        store.read_barrier(|| {
+            let mut states = Cond::or();
+            for state in [
+                TaskState::None,
+                TaskState::Ready,
+                TaskState::Pending,
+                TaskState::Running,
+                TaskState::Interrupt,
+            ] {
+                states = states.push(Expr::eq("state", state.to_string()));
+            }
+            let procs = query_all(&store.procs(), Query::new().push(states))?;
+
+            let mut tasks = Vec::new();
+            let mut messages = Vec::new();
+            let mut history = Vec::new();
+            for proc in procs.iter() {
+                let by_pid = Query::new().push(Cond::and().push(Expr::eq("pid", proc.id.clone())));
+                tasks.extend(query_all(&store.tasks(), by_pid.clone())?);
+                history.extend(query_all(&store.history(), by_pid)?);
+
+                let pending = Query::new().push(
+                    Cond::and()
+                        .push(Expr::eq("pid", proc.id.clone()))
+                        .push(Expr::ne("status", MessageStatus::Completed)),
+                );
+                messages.extend(query_all(&store.messages(), pending)?);
+            }
+
+            Ok(Bundle {
+                version: BUNDLE_VERSION,
+                create_time: utils::time::time_millis(),
+                models: query_all(&store.models(), Query::new())?,
+                packages: query_all(&store.packages(), Query::new())?,
+                events: query_all(&store.events(), Query::new())?,
+                procs,
+                tasks,
+                messages,
+                history,
+            })
+        })
+    }
+
+    /// export the bundle as json text
+    pub fn export_json(&self) -> Result<String> {
+        let bundle = self.export()?;
+        serde_json::to_string(&bundle).map_err(|err| ActError::Convert(err.to_string()))
+    }
+
+    /// export the bundle as ndjson text
+    /// the first line is the header with version, the next lines are the records with kind
+    pub fn export_ndjson(&self) -> Result<String> {
+        let bundle = self.export()?;
+        let mut lines = vec![json!(BundleHeader {
+            version: bundle.version,
+            create_time: bundle.create_time,
+        })];
+
+        push_lines(&mut lines, "models", &bundle.models);
+        push_lines(&mut lines, "packages", &bundle.packages);
+        push_lines(&mut lines, "events", &bundle.events);
+        push_lines(&mut lines, "procs", &bundle.procs);
+        push_lines(&mut lines, "tasks", &bundle.tasks);
+        push_lines(&mut lines, "messages", &bundle.messages);
+        push_lines(&mut lines, "history", &bundle.history);
+
+        let mut text = String::new();
+        for line in lines {
+            text.push_str(&line.to_string());
+            text.push('\n');
+        }
+        Ok(text)
+    }
+
+    /// import the bundle to the current store and reload the running processes to the cache
+    /// the existing records with the same id will be overwritten
+    /// returns the imported record count
+    #[instrument(skip(self, bundle))]
+    pub fn import(&self, bundle: &Bundle) -> Result<usize> {
+        if bundle.version > BUNDLE_VERSION {
+            return Err(ActError::Store(format!(
+                "the bundle version {} is not supported, the max version is {}",
+                bundle.version, BUNDLE_VERSION
+            )));
+        }
+
+        let store = self.runtime.store();
+        let mut count = 0;
+        count += upsert(&store.models(), &bundle.models, |m| &m.id)?;
+        count += upsert(&store.packages(), &bundle.packages, |m| &m.id)?;
+        count += upsert(&store.events(), &bundle.events, |m| &m.id)?;
+        count += upsert(&store.procs(), &bundle.procs, |m| &m.id)?;
+        count += upsert(&store.tasks(), &bundle.tasks, |m| &m.id)?;
+        count += upsert(&store.messages(), &bundle.messages, |m| &m.id)?;
+        count += upsert(&store.history(), &bundle.history, |m| &m.id)?;
+
+        // reload the imported processes to resume them in the engine
+        let cache = self.runtime.cache();
+        for proc in bundle.procs.iter() {
+            cache.uncache(&proc.id);
+        }
+        cache.restore(&self.runtime, |proc| {
+            if proc.state().is_none() {
+                proc.start();
+            }
+        })?;
+
+        Ok(count)
+    }
+
+    /// import the bundle from json text
+    pub fn import_json(&self, text: &str) -> Result<usize> {
+        let bundle: Bundle =
+            serde_json::from_str(text).map_err(|err| ActError::Convert(err.to_string()))?;
+        self.import(&bundle)
+    }
+
+    /// import the bundle from ndjson text
+    pub fn import_ndjson(&self, text: &str) -> Result<usize> {
+        let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+        let header: BundleHeader = match lines.next() {
+            Some(line) => {
+                serde_json::from_str(line).map_err(|err| ActError::Convert(err.to_string()))?
+            }
+            None => return Err(ActError::Convert("the bundle is empty".to_string())),
+        };
+
+        let mut bundle = Bundle {
+            version: header.version,
+            create_time: header.create_time,
+            ..Default::default()
+        };
+        for line in lines {
+            let line: BundleLine =
+                serde_json::from_str(line).map_err(|err| ActError::Convert(err.to_string()))?;
+            match line.kind.as_str() {
+                "models" => bundle.models.push(from_value(line.data)?),
+                "packages" => bundle.packages.push(from_value(line.data)?),
+                "events" => bundle.events.push(from_value(line.data)?),
+                "procs" => bundle.procs.push(from_value(line.data)?),
+                "tasks" => bundle.tasks.push(from_value(line.data)?),
+                "messages" => bundle.messages.push(from_value(line.data)?),
+                "history" => bundle.history.push(from_value(line.data)?),
+                kind => {
+                    return Err(ActError::Convert(format!(
+                        "unknown record kind '{kind}' in bundle"
+                    )));
+                }
+            }
+        }
+
+        self.import(&bundle)
+    }
+}
+
+/// read all of the rows page by page until the page count is reached
+/// the rows are ordered by id to keep the pages stable
+fn query_all<T>(collection: &Arc<dyn DbCollection<Item = T>>, query: Query) -> Result<Vec<T>> {
+    let query = query.set_limit(EXPORT_PAGE_SIZE).push_order("id", false);
+    let mut ret = Vec::new();
+    let mut page = 0;
+    loop {
+        let data = collection.query(&query.clone().set_offset(page * EXPORT_PAGE_SIZE))?;
+        ret.extend(data.rows);
+        page += 1;
+        if page >= data.page_count {
+            break;
+        }
+    }
+    Ok(ret)
+}
+
+fn push_lines<T: Serialize>(lines: &mut Vec<serde_json::Value>, kind: &str, items: &[T]) {
+    for item in items {
+        lines.push(json!(BundleLine {
+            kind: kind.to_string(),
+            data: json!(item),
+        }));
+    }
+}
+
+fn from_value<T: DeserializeOwned>(value: serde_json::Value) -> Result<T> {
+    serde_json::from_value(value).map_err(|err| ActError::Convert(err.to_string()))
+}
+
+fn upsert<T, F>(collection: &Arc<dyn DbCollection<Item = T>>, items: &[T], id: F) -> Result<usize>
+where
+    F: Fn(&T) -> &String,
+{
+    for item in items {
+        if collection.exists(id(item))? {
+            collection.update(item)?;
+        } else {
+            collection.create(item)?;
+        }
+    }
+    Ok(items.len())
+}
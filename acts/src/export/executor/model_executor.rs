@@ -24,10 +24,18 @@ impl ModelExecutor {
 
     #[instrument(skip(self))]
     pub fn deploy(&self, model: &Workflow) -> Result<bool> {
+        let store = self.runtime.cache().store();
+
+        // resolve the templates into the normal steps before deploying
+        let mut model = model.clone();
+        model.resolve_templates(|id| {
+            let m = store.models().find(id)?;
+            Workflow::from_yml(&m.data)
+        })?;
+        model.check_schema_with(&*self.runtime.cache().schema()?)?;
         model.valid()?;
 
-        let store = self.runtime.cache().store();
-        let ret = store.deploy(model)?;
+        let ret = store.deploy(&model)?;
         self.deploy_event(&model.on, &model.id, model.ver)?;
 
         Ok(ret)
@@ -53,9 +61,20 @@ impl ModelExecutor {
         match self.runtime.cache().store().models().find(id) {
             Ok(m) => {
                 let mut model: ModelInfo = m.into();
-                if fmt == "tree" {
-                    let workflow = Workflow::from_yml(&model.data)?;
-                    model.data = workflow.tree_output();
+                match fmt {
+                    "tree" => {
+                        let workflow = Workflow::from_yml(&model.data)?;
+                        model.data = workflow.tree_output();
+                    }
+                    "mermaid" => {
+                        let workflow = Workflow::from_yml(&model.data)?;
+                        model.data = workflow.to_mermaid()?;
+                    }
+                    "dot" => {
+                        let workflow = Workflow::from_yml(&model.data)?;
+                        model.data = workflow.to_dot()?;
+                    }
+                    _ => {}
                 }
                 Ok(model)
             }
@@ -1,7 +1,11 @@
 use super::ExecutorQuery;
 use crate::scheduler::Process;
 use crate::{
-    ModelInfo, ProcInfo, Result, TaskInfo, Vars, scheduler::Runtime, store::PageData, utils::consts,
+    ActError, Branch, HistoryInfo, ModelInfo, ProcInfo, Result, Step, TaskInfo, Vars,
+    query::{Cond, Expr, Query},
+    scheduler::Runtime,
+    store::PageData,
+    utils::consts,
 };
 use std::sync::Arc;
 use tracing::instrument;
@@ -66,8 +70,76 @@ impl ProcessExecutor {
         }
     }
 
+    /// get the history of the process in time order
+    /// including the task state transitions, actions and vars mutations
+    #[instrument(skip(self))]
+    pub fn history(&self, pid: &str) -> Result<Vec<HistoryInfo>> {
+        let query = Query::new()
+            .push(Cond::and().push(Expr::eq("pid", pid)))
+            .push_order("timestamp", false)
+            .push_order("id", false);
+        let items = self.runtime.cache().store().history().query(&query)?;
+        Ok(items.rows.iter().map(HistoryInfo::from).collect())
+    }
+
+    /// render the process diagram with the task states
+    /// the fmt is one of 'mermaid' and 'dot'
+    #[instrument(skip(self))]
+    pub fn diagram(&self, pid: &str, fmt: &str) -> Result<String> {
+        let proc = self
+            .runtime
+            .cache()
+            .proc(pid, &self.runtime)
+            .ok_or(ActError::Runtime(format!("cannot find process '{pid}'")))?;
+        match fmt {
+            "mermaid" => Ok(proc.to_mermaid()),
+            "dot" => Ok(proc.to_dot()),
+            _ => Err(ActError::Action(format!(
+                "the diagram format '{fmt}' is not supported"
+            ))),
+        }
+    }
+
+    /// insert the step after the step of 'after' in the running process
+    #[instrument(skip(self))]
+    pub fn insert_step(&self, pid: &str, after: &str, step: &Step) -> Result<()> {
+        self.patch(pid, |proc| proc.insert_step(after, step))
+    }
+
+    /// append the branch to the step which is not started in the running process
+    #[instrument(skip(self))]
+    pub fn insert_branch(&self, pid: &str, step_id: &str, branch: &Branch) -> Result<()> {
+        self.patch(pid, |proc| proc.insert_branch(step_id, branch))
+    }
+
+    /// remove the step which is not started in the running process
+    #[instrument(skip(self))]
+    pub fn remove_step(&self, pid: &str, id: &str) -> Result<()> {
+        self.patch(pid, |proc| proc.remove_step(id))
+    }
+
+    /// reroute the step to the next step in the running process
+    #[instrument(skip(self))]
+    pub fn set_next(&self, pid: &str, id: &str, next: &str) -> Result<()> {
+        self.patch(pid, |proc| proc.set_next(id, next))
+    }
+
     #[instrument(skip(self))]
     pub fn get_process(&self, pid: &str) -> Option<Arc<Process>> {
         self.runtime.cache().proc(pid, &self.runtime)
     }
+
+    /// patch the process model and save it to the store
+    fn patch<F: FnOnce(&Arc<Process>) -> Result<()>>(&self, pid: &str, f: F) -> Result<()> {
+        let proc = self
+            .runtime
+            .cache()
+            .proc(pid, &self.runtime)
+            .ok_or(ActError::Runtime(format!("cannot find process '{pid}'")))?;
+        if proc.state().is_completed() {
+            return Err(ActError::Action(format!("process '{pid}' is completed")));
+        }
+        f(&proc)?;
+        self.runtime.cache().store().upsert_proc(&proc)
+    }
 }
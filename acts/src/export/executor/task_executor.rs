@@ -1,5 +1,13 @@
 use super::ExecutorQuery;
-use crate::{Result, TaskInfo, scheduler::Runtime, store::PageData, utils::Id};
+use crate::{
+    Result, TaskInfo, TaskState,
+    scheduler::Runtime,
+    store::{
+        PageData, data,
+        query::{Cond, Expr, Query},
+    },
+    utils::Id,
+};
 use std::sync::Arc;
 use tracing::instrument;
 
@@ -38,4 +46,70 @@ impl TaskExecutor {
             Err(err) => Err(err),
         }
     }
+
+    /// the interrupted tasks assigned to the user
+    #[instrument(skip(self))]
+    pub fn inbox(&self, uid: &str, q: &ExecutorQuery) -> Result<PageData<TaskInfo>> {
+        self.query_assigned(q, vec![Cond::and().push(Expr::eq("assignee", uid))])
+    }
+
+    /// the interrupted tasks which are not claimed and can be claimed by the user
+    #[instrument(skip(self))]
+    pub fn group_inbox(
+        &self,
+        uid: &str,
+        groups: &[String],
+        q: &ExecutorQuery,
+    ) -> Result<PageData<TaskInfo>> {
+        let mut candidates = Cond::or().push(Expr::contains(
+            "candidates",
+            &data::Task::candidate_user(uid),
+        ));
+        for group in groups {
+            candidates = candidates.push(Expr::contains(
+                "candidates",
+                &data::Task::candidate_group(group),
+            ));
+        }
+        self.query_assigned(
+            q,
+            vec![Cond::and().push(Expr::eq("assignee", "")), candidates],
+        )
+    }
+
+    /// query the interrupted tasks by the assignment conditions in the store
+    /// the tasks are ordered by the priority when the order is not set,
+    /// and the id is always the last order to keep the pages stable
+    fn query_assigned(&self, q: &ExecutorQuery, conds: Vec<Cond>) -> Result<PageData<TaskInfo>> {
+        let state: String = TaskState::Interrupt.into();
+        let mut order_by = if q.order_by.is_empty() {
+            vec![
+                ("priority".to_string(), true),
+                ("start_time".to_string(), false),
+            ]
+        } else {
+            q.order_by.clone()
+        };
+        if !order_by.iter().any(|(key, _)| key == "id") {
+            order_by.push(("id".to_string(), false));
+        }
+
+        let mut query = Query::new()
+            .push(q.into_cond().push(Expr::eq("state", state)))
+            .set_order(&order_by)
+            .set_offset(q.offset)
+            .set_limit(q.count);
+        for cond in conds {
+            query = query.push(cond);
+        }
+
+        let tasks = self.runtime.cache().store().tasks().query(&query)?;
+        Ok(PageData {
+            count: tasks.count,
+            page_size: tasks.page_size,
+            page_count: tasks.page_count,
+            page_num: tasks.page_num,
+            rows: tasks.rows.iter().map(|m| m.into()).collect(),
+        })
+    }
 }
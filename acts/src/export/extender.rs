@@ -1,6 +1,6 @@
 use crate::{
-    ActError, ActPackageMeta, ActRunAs, DbCollection, Result, env::ActUserVar, scheduler::Runtime,
-    store::DbCollectionIden,
+    ActError, ActPackageMeta, ActRunAs, Calendar, Clock, DbCollection, Result, env::ActUserVar,
+    scheduler::Runtime, store::DbCollectionIden,
 };
 use core::fmt;
 use std::sync::Arc;
@@ -165,4 +165,31 @@ impl Extender {
     {
         self.runtime.store().register(collection);
     }
+
+    /// register the working calendar to calculate the business units such as `3bd`
+    /// the workflow uses the calendar by `calendar` name, or the `default` calendar
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use acts::{Calendar, Engine};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let engine = Engine::new().start();
+    ///     let calendar = Calendar::new()
+    ///         .with_work_hours("09:00", "17:00")
+    ///         .with_holiday("2026-12-25");
+    ///     engine.extender().register_calendar("default", &calendar).unwrap();
+    /// }
+    /// ```
+    pub fn register_calendar(&self, name: &str, calendar: &Calendar) -> Result<()> {
+        calendar.check()?;
+        self.runtime.calendars().register(name, calendar);
+        Ok(())
+    }
+
+    /// set the clock to get the current time when checking the timeouts and escalations
+    pub fn set_clock(&self, clock: Arc<dyn Clock>) {
+        self.runtime.calendars().set_clock(clock);
+    }
 }
@@ -1,9 +1,10 @@
 use crate::{
-    Act, ChannelOptions, Engine, Message, Vars, Workflow,
+    Act, ActError, Calendar, ChannelOptions, Engine, EngineBuilder, Message, Step, TaskAssignment,
+    Vars, Workflow,
     data::{self, Package},
     event::{MessageState, Model},
     export::ExecutorQuery,
-    scheduler::TaskState,
+    scheduler::{Node, NodeContent, TaskState},
     store::query::*,
     utils::{self, consts},
 };
@@ -86,6 +87,132 @@ async fn export_manager_deploy_dup_id_error() {
     assert!(result.is_err());
 }
 
+#[tokio::test]
+async fn export_manager_deploy_schema_error() {
+    let engine = Engine::new().start();
+    let executor = engine.executor();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_step(|step| step.with_id("step1").with_timeout(|t| t.with_on("1x")));
+
+    let result = executor.model().deploy(&model);
+    assert!(
+        matches!(result, Err(ActError::Model(err)) if err.message.contains("/steps/0/timeout/0/on"))
+    );
+}
+
+#[tokio::test]
+async fn export_manager_deploy_schema_with_published_package() {
+    let engine = Engine::new().start();
+    let executor = engine.executor();
+    let model = Workflow::new().with_id(&utils::longid()).with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::new()
+                .with_uses("pack1")
+                .with_params_vars(|vars| vars.with("a", "abc")),
+        )
+    });
+    assert!(executor.model().deploy(&model).is_ok());
+
+    // the cached schema is rebuilt after publishing the package
+    let pack = data::Package {
+        id: "pack1".to_string(),
+        version: "0.1.0".to_string(),
+        schema: json!({
+            "type": "object",
+            "properties": { "a": { "type": "number" } }
+        })
+        .to_string(),
+        run_as: crate::ActRunAs::Func,
+        resources: "[]".to_string(),
+        ..Default::default()
+    };
+    executor.pack().publish(&pack).unwrap();
+    let result = executor.model().deploy(&model);
+    assert!(
+        matches!(result, Err(ActError::Model(err)) if err.message.contains("/steps/0/acts/0/params/a"))
+    );
+}
+
+#[tokio::test]
+async fn export_manager_json_schema_with_package() {
+    let engine = Engine::new().start();
+    let executor = engine.executor();
+    let pack = data::Package {
+        id: "pack1".to_string(),
+        version: "0.1.0".to_string(),
+        schema: json!({
+            "type": "object",
+            "properties": { "a": { "type": "number" } },
+            "required": ["a"]
+        })
+        .to_string(),
+        run_as: crate::ActRunAs::Func,
+        resources: "[]".to_string(),
+        ..Default::default()
+    };
+    executor.pack().publish(&pack).unwrap();
+
+    let schema = Workflow::json_schema(&engine).unwrap();
+    let validator = jsonschema::validator_for(&schema).unwrap();
+    let model = Workflow::new().with_step(|step| {
+        step.with_act(
+            Act::new()
+                .with_uses("pack1")
+                .with_params_vars(|vars| vars.with("a", 1)),
+        )
+    });
+    assert!(validator.is_valid(&serde_json::to_value(&model).unwrap()));
+
+    let model = Workflow::new().with_step(|step| {
+        step.with_act(Act::new().with_uses("pack1").with_params_vars(|vars| vars))
+    });
+    assert!(!validator.is_valid(&serde_json::to_value(&model).unwrap()));
+}
+
+#[tokio::test]
+async fn export_manager_deploy_with_imports() {
+    let engine = Engine::new().start();
+    let executor = engine.executor();
+    let tid = utils::longid();
+    let templates = Workflow::new().with_id(&tid).with_template(|step| {
+        step.with_id("approval").with_act(Act::irq(|act| {
+            act.with_key("approve").with_input("uid", json!("${uid}"))
+        }))
+    });
+    executor.model().deploy(&templates).unwrap();
+
+    let mid = utils::longid();
+    let model = Workflow::new()
+        .with_id(&mid)
+        .with_import(&tid)
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_template(|t| t.with_id("approval").with_param("uid", "u1"))
+        });
+    executor.model().deploy(&model).unwrap();
+
+    let info = executor.model().get(&mid, "text").unwrap();
+    let deployed = Workflow::from_yml(&info.data).unwrap();
+    let step = deployed.step("step1").unwrap();
+    assert!(step.use_template.is_none());
+    assert_eq!(step.acts[0].key, "approve");
+    assert_eq!(step.acts[0].inputs.get::<String>("uid").unwrap(), "u1");
+}
+
+#[tokio::test]
+async fn export_manager_deploy_import_not_found() {
+    let engine = Engine::new().start();
+    let executor = engine.executor();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_import("not_exists")
+        .with_step(|step| step.with_id("step1"));
+
+    let result = executor.model().deploy(&model);
+    assert!(result.is_err());
+}
+
 #[tokio::test]
 async fn engine_executor_start_no_pid() {
     let engine = Engine::new().start();
@@ -101,6 +228,67 @@ async fn engine_executor_start_no_pid() {
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn engine_executor_start_calendar_not_found() {
+    let engine = Engine::new().start();
+    let executor = engine.executor();
+
+    let mid = utils::longid();
+    let workflow = Workflow::new()
+        .with_id(&mid)
+        .with_calendar("not_exists")
+        .with_step(|step| step.with_act(Act::irq(|act| act.with_key("test"))));
+    executor.model().deploy(&workflow).unwrap();
+    let result = executor.proc().start(&mid, &Vars::new());
+    match result {
+        Err(ActError::Runtime(message)) => assert!(message.contains("not_exists")),
+        _ => panic!("the unknown calendar should be an runtime error"),
+    }
+
+    engine
+        .extender()
+        .register_calendar("not_exists", &Calendar::new())
+        .unwrap();
+    assert!(executor.proc().start(&mid, &Vars::new()).is_ok());
+}
+
+#[tokio::test]
+async fn engine_executor_start_input_schema() {
+    let engine = Engine::new().start();
+    let executor = engine.executor();
+
+    let mid = utils::longid();
+    let workflow = Workflow::new()
+        .with_id(&mid)
+        .with_input("currency", json!("usd"))
+        .with_input_schema(json!({
+            "type": "object",
+            "properties": {
+                "amount": { "type": "number" },
+                "currency": { "type": "string" }
+            },
+            "required": ["amount", "currency"]
+        }))
+        .with_step(|step| step.with_act(Act::irq(|act| act.with_key("test"))));
+    executor.model().deploy(&workflow).unwrap();
+
+    let result = executor.proc().start(&mid, &Vars::new());
+    match result {
+        Err(ActError::Model(err)) => assert!(err.message.contains("amount")),
+        _ => panic!("the missing input should be an model error"),
+    }
+
+    let options = Vars::new().with("amount", "100");
+    let result = executor.proc().start(&mid, &options);
+    match result {
+        Err(ActError::Model(err)) => assert!(err.message.contains("/amount")),
+        _ => panic!("the wrong typed input should be an model error"),
+    }
+
+    let options = Vars::new().with("amount", 100);
+    assert!(executor.proc().start(&mid, &options).is_ok());
+}
+
 #[tokio::test]
 async fn engine_executor_start_with_pid() {
     let engine = Engine::new().start();
@@ -275,6 +463,22 @@ async fn export_manager_model_get_tree() {
     assert!(!result.data.is_empty());
 }
 
+#[tokio::test]
+async fn export_manager_model_get_diagram() {
+    let engine = Engine::new().start();
+    let manager = engine.executor();
+    let mut model = Workflow::new().with_step(|step| step.with_id("step1"));
+
+    model.set_id(&utils::longid());
+    manager.model().deploy(&model).unwrap();
+
+    let result = manager.model().get(&model.id, "mermaid").unwrap();
+    assert!(result.data.starts_with("flowchart TD"));
+
+    let result = manager.model().get(&model.id, "dot").unwrap();
+    assert!(result.data.starts_with("digraph"));
+}
+
 #[tokio::test]
 async fn export_manager_model_remove() {
     let engine = Engine::new().start();
@@ -573,6 +777,87 @@ async fn export_manager_proc_get() {
     assert!(!info.tasks.is_empty());
 }
 
+#[tokio::test]
+async fn export_manager_proc_diagram() {
+    let engine = Engine::new().start();
+    let manager = engine.executor();
+    let model = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::irq(|act| act.with_key("act1")))
+    });
+
+    let rt = engine.runtime();
+    let sig = engine.signal(());
+    let s1 = sig.clone();
+    engine.channel().on_message(move |e| {
+        if e.is_key("act1") {
+            s1.close()
+        }
+    });
+    let pid = utils::longid();
+    let proc = rt.create_proc(&pid, &model);
+    rt.launch(&proc);
+    sig.recv().await;
+
+    let text = manager.proc().diagram(&pid, "mermaid").unwrap();
+    assert!(text.contains("classDef running"));
+    assert!(text.contains("classDef interrupted"));
+
+    let text = manager.proc().diagram(&pid, "dot").unwrap();
+    assert!(text.contains("style=\"rounded,filled\""));
+
+    assert!(manager.proc().diagram(&pid, "svg").is_err());
+    assert!(manager.proc().diagram("not_exists", "dot").is_err());
+}
+
+#[tokio::test]
+async fn export_manager_proc_insert_step() {
+    let engine = Engine::new().start();
+    let manager = engine.executor();
+    let model = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::irq(|act| act.with_key("act1")))
+    });
+
+    let rt = engine.runtime();
+    let sig = engine.signal(String::new());
+    let s1 = sig.clone();
+    let done = engine.signal(false);
+    let s2 = done.clone();
+    engine.channel().on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            s1.send(e.tid.clone());
+        }
+        if e.is_key("msg2") {
+            s2.send(true);
+        }
+    });
+    let pid = utils::longid();
+    let proc = rt.create_proc(&pid, &model);
+    rt.launch(&proc);
+    let tid = sig.recv().await;
+
+    let step = Step::new()
+        .with_id("step2")
+        .with_act(Act::msg(|act| act.with_key("msg2")));
+    manager.proc().insert_step(&pid, "step1", &step).unwrap();
+    assert!(manager.proc().remove_step(&pid, "step1").is_err());
+    assert!(
+        manager
+            .proc()
+            .insert_step("not_exists", "step1", &step)
+            .is_err()
+    );
+
+    // reload the process from store
+    rt.cache().uncache(&pid);
+    let proc = manager.proc().get_process(&pid).unwrap();
+    assert!(proc.node("step2").is_some());
+
+    manager.act().complete(&pid, &tid, &Vars::new()).unwrap();
+    assert!(done.recv().await);
+}
+
 #[tokio::test]
 async fn export_manager_tasks_count() {
     let engine = Engine::new().start();
@@ -1257,6 +1542,32 @@ async fn export_manager_packages_query() {
     assert_eq!(rows.first().unwrap().desc, "test-3");
 }
 
+#[tokio::test]
+async fn export_manager_packages_built_in_schema() {
+    let engine = Engine::new().start();
+    let manager = engine.executor();
+    let rows = manager
+        .pack()
+        .list(&ExecutorQuery::new().with_query("built_in", true))
+        .unwrap()
+        .rows;
+    assert!(!rows.is_empty());
+    for pack in rows.iter() {
+        let schema: serde_json::Value = serde_json::from_str(&pack.schema).unwrap();
+        assert_ne!(
+            schema,
+            json!({}),
+            "package '{}' has an empty schema",
+            pack.id
+        );
+        assert!(jsonschema::validator_for(&schema).is_ok());
+    }
+
+    let pack = manager.pack().get("acts.core.parallel").unwrap();
+    let schema: serde_json::Value = serde_json::from_str(&pack.schema).unwrap();
+    assert_eq!(schema["required"], json!(["in", "acts"]));
+}
+
 #[tokio::test]
 async fn export_manager_packages_offset_in_range() {
     let engine = Engine::new().start();
@@ -1448,6 +1759,173 @@ async fn export_executor_complete_no_uid() {
     assert!(ret);
 }
 
+#[tokio::test]
+async fn export_executor_history() {
+    // keep the process to query the history after it is completed
+    let config = crate::config::ConfigData {
+        keep_processes: Some(true),
+        ..Default::default()
+    };
+    let engine = EngineBuilder::new()
+        .set_config(&config)
+        .build()
+        .await
+        .unwrap()
+        .start();
+    let model = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::irq(|act| act.with_key("act1")))
+    });
+
+    let rt = engine.runtime();
+    let sig = engine.signal(String::new());
+    let s1 = sig.clone();
+    let s2 = sig.clone();
+    let executor = engine.executor();
+    engine.channel().on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            let mut vars = Vars::new();
+            vars.insert("uid".to_string(), json!("u1"));
+            vars.insert("approved".to_string(), json!(true));
+            executor.act().complete(&e.pid, &e.tid, &vars).unwrap();
+        }
+    });
+    engine.channel().on_complete(move |e| {
+        s1.send(e.pid.clone());
+    });
+    let mut vars = Vars::new();
+    vars.insert("uid".to_string(), json!("u1"));
+    rt.start(&model, &vars).unwrap();
+    let pid = s2.recv().await;
+
+    let history = engine.executor().proc().history(&pid).unwrap();
+    assert!(
+        history
+            .iter()
+            .any(|h| h.kind == "action" && h.name == "next" && h.actor == "u1")
+    );
+    assert!(
+        history.iter().any(|h| h.kind == "state"
+            && h.name == TaskState::Completed.to_string()
+            && h.tid == "$")
+    );
+    assert!(
+        history
+            .iter()
+            .any(|h| h.kind == "vars" && h.data.get("approved") == Some(&json!(true)))
+    );
+}
+
+#[tokio::test]
+async fn export_executor_vars_snapshot_removed_on_complete() {
+    let config = crate::config::ConfigData {
+        keep_processes: Some(true),
+        ..Default::default()
+    };
+    let engine = EngineBuilder::new()
+        .set_config(&config)
+        .build()
+        .await
+        .unwrap()
+        .start();
+    let model = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::irq(|act| act.with_key("act1")))
+    });
+
+    let rt = engine.runtime();
+    let sig = engine.signal(String::new());
+    let s1 = sig.clone();
+    let s2 = sig.clone();
+    let executor = engine.executor();
+    let store = rt.cache().store();
+    let has_snapshot = Arc::new(Mutex::new(false));
+    let running = has_snapshot.clone();
+    engine.channel().on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            // the snapshot is kept in the step task data while running
+            let step = store
+                .tasks()
+                .query(&Query::new().push(Cond::and().push(Expr::eq("pid", e.pid.clone()))))
+                .unwrap()
+                .rows
+                .into_iter()
+                .find(|t| t.kind == "step")
+                .unwrap();
+            *running.lock().unwrap() = step.data.contains(consts::STEP_VARS_SNAPSHOT);
+            executor
+                .act()
+                .complete(&e.pid, &e.tid, &Vars::new())
+                .unwrap();
+        }
+    });
+    engine.channel().on_complete(move |e| {
+        s1.send(e.pid.clone());
+    });
+    rt.start(&model, &Vars::new()).unwrap();
+    let pid = s2.recv().await;
+    assert!(*has_snapshot.lock().unwrap());
+
+    let tasks = rt
+        .cache()
+        .store()
+        .tasks()
+        .query(&Query::new().push(Cond::and().push(Expr::eq("pid", pid))))
+        .unwrap();
+    assert!(tasks.count > 0);
+    assert!(
+        tasks
+            .rows
+            .iter()
+            .all(|t| !t.data.contains(consts::STEP_VARS_SNAPSHOT))
+    );
+}
+
+#[tokio::test]
+async fn export_executor_history_without_task_history() {
+    let config = crate::config::ConfigData {
+        keep_processes: Some(true),
+        task_history: Some(false),
+        ..Default::default()
+    };
+    let engine = EngineBuilder::new()
+        .set_config(&config)
+        .build()
+        .await
+        .unwrap()
+        .start();
+    let model = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::irq(|act| act.with_key("act1")))
+    });
+
+    let rt = engine.runtime();
+    let sig = engine.signal(String::new());
+    let s1 = sig.clone();
+    let s2 = sig.clone();
+    let executor = engine.executor();
+    engine.channel().on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            let mut vars = Vars::new();
+            vars.insert("uid".to_string(), json!("u1"));
+            executor.act().complete(&e.pid, &e.tid, &vars).unwrap();
+        }
+    });
+    engine.channel().on_complete(move |e| {
+        s1.send(e.pid.clone());
+    });
+    rt.start(&model, &Vars::new()).unwrap();
+    let pid = s2.recv().await;
+
+    let history = engine.executor().proc().history(&pid).unwrap();
+    assert!(history.iter().any(|h| h.kind == "action"));
+    assert!(
+        !history
+            .iter()
+            .any(|h| h.kind == "state" || h.kind == "vars")
+    );
+}
+
 #[tokio::test]
 async fn export_executor_submit() {
     let engine = Engine::new().start();
@@ -1782,6 +2260,230 @@ async fn export_executor_remove() {
     assert!(ret);
 }
 
+#[tokio::test]
+async fn export_executor_claim() {
+    let engine = Engine::new().start();
+    let executor = engine.executor();
+    let model = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::irq(|act| act.with_key("act1")).with_assignment(|a| {
+                a.with_candidate_user("{{ candidate }}")
+                    .with_candidate_group("{{ group }}")
+            }))
+    });
+
+    let rt = engine.runtime();
+    let sig = engine.signal(String::new());
+    let s1 = sig.clone();
+    engine.channel().on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            s1.send(e.tid.clone());
+        }
+    });
+    let pid = utils::longid();
+    let (uid, group) = (utils::longid(), utils::longid());
+    let mut vars = Vars::new();
+    vars.insert("pid".to_string(), json!(pid));
+    vars.insert("candidate".to_string(), json!(uid));
+    vars.insert("group".to_string(), json!(group));
+    rt.start(&model, &vars).unwrap();
+    let tid = sig.recv().await;
+
+    let q = ExecutorQuery::new().with_query("pid", &pid);
+    let act = executor.act();
+    let task = executor.task();
+    assert_eq!(task.group_inbox(&uid, &[], &q).unwrap().count, 1);
+    assert_eq!(task.inbox(&uid, &q).unwrap().count, 0);
+
+    // the user in the candidate groups can claim the task
+    assert!(act.claim(&pid, &tid, "u1", &[]).is_err());
+    act.claim(&pid, &tid, "u1", std::slice::from_ref(&group))
+        .unwrap();
+    act.unclaim(&pid, &tid, "u1").unwrap();
+
+    act.claim(&pid, &tid, &uid, &[]).unwrap();
+    assert!(act.claim(&pid, &tid, "u1", &[group]).is_err());
+    assert_eq!(task.inbox(&uid, &q).unwrap().count, 1);
+    assert_eq!(task.group_inbox(&uid, &[], &q).unwrap().count, 0);
+
+    act.delegate(&pid, &tid, &uid, "u2").unwrap();
+    assert!(act.delegate(&pid, &tid, &uid, "u3").is_err());
+    let rows = task.inbox("u2", &q).unwrap().rows;
+    let assignment = rows[0].assignment.as_ref().unwrap();
+    assert_eq!(assignment.owner.as_deref(), Some(uid.as_str()));
+
+    assert!(act.unclaim(&pid, &tid, &uid).is_err());
+    act.unclaim(&pid, &tid, "u2").unwrap();
+    assert_eq!(task.group_inbox(&uid, &[], &q).unwrap().count, 1);
+
+    act.reassign(&pid, &tid, "u3").unwrap();
+    assert_eq!(task.inbox("u3", &q).unwrap().count, 1);
+
+    act.complete(&pid, &tid, &Vars::new()).unwrap();
+    assert!(act.reassign(&pid, &tid, "u4").is_err());
+    assert_eq!(task.inbox("u3", &q).unwrap().count, 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn export_executor_claim_concurrent() {
+    let engine = Engine::new().start();
+    let executor = engine.executor();
+    let model = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::irq(|act| act.with_key("act1"))
+                .with_assignment(|a| a.with_candidate_group("{{ group }}")),
+        )
+    });
+
+    let rt = engine.runtime();
+    let sig = engine.signal(String::new());
+    let s1 = sig.clone();
+    engine.channel().on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            s1.send(e.tid.clone());
+        }
+    });
+    let pid = utils::longid();
+    let group = utils::longid();
+    let mut vars = Vars::new();
+    vars.insert("pid".to_string(), json!(pid));
+    vars.insert("group".to_string(), json!(group));
+    rt.start(&model, &vars).unwrap();
+    let tid = sig.recv().await;
+
+    // only one of the users can claim the task at the same time
+    let handles = (0..8)
+        .map(|i| {
+            let act = executor.act().clone();
+            let (pid, tid, groups) = (pid.clone(), tid.clone(), [group.clone()]);
+            tokio::task::spawn_blocking(move || act.claim(&pid, &tid, &format!("u{i}"), &groups))
+        })
+        .collect::<Vec<_>>();
+    let mut oks = 0;
+    for handle in handles {
+        if handle.await.unwrap().is_ok() {
+            oks += 1;
+        }
+    }
+    assert_eq!(oks, 1);
+}
+
+#[tokio::test]
+async fn export_executor_inbox_pages() {
+    let engine = Engine::new().start();
+    let executor = engine.executor();
+    let store = engine.runtime().cache().store();
+    let pid = utils::longid();
+    let node = Node::new(
+        "act1",
+        NodeContent::Act(Act::irq(|act| act.with_key("act1"))),
+        2,
+    )
+    .to_string();
+    for i in 0..650 {
+        let uid = if i % 2 == 0 { "u1" } else { "u2" };
+        let assignment = TaskAssignment {
+            assignee: Some(uid.to_string()),
+            ..Default::default()
+        };
+        let tid = format!("t{i}");
+        store
+            .tasks()
+            .create(&data::Task {
+                id: utils::Id::new(&pid, &tid).id(),
+                pid: pid.clone(),
+                tid,
+                node_data: node.clone(),
+                kind: "act".to_string(),
+                prev: None,
+                name: String::new(),
+                state: TaskState::Interrupt.into(),
+                data: Vars::new()
+                    .with(consts::TASK_ASSIGNMENT, assignment)
+                    .to_string(),
+                err: None,
+                start_time: i,
+                end_time: 0,
+                hooks: "{}".to_string(),
+                timestamp: i,
+                assignee: uid.to_string(),
+                candidates: String::new(),
+                priority: 0,
+            })
+            .unwrap();
+    }
+
+    // the tasks are filtered by the assignee and paged in the store
+    let q = ExecutorQuery::new().with_query("pid", &pid).with_count(100);
+    let page = executor.task().inbox("u1", &q).unwrap();
+    assert_eq!(page.count, 325);
+    assert_eq!(page.page_count, 4);
+    assert_eq!(page.rows.len(), 100);
+    assert_eq!(page.rows[0].id, "t0");
+
+    // the pages are stable and cover all of the tasks without duplicates
+    let mut ids = std::collections::HashSet::new();
+    for offset in (0..325).step_by(100) {
+        let q = ExecutorQuery::new()
+            .with_query("pid", &pid)
+            .with_offset(offset)
+            .with_count(100);
+        for row in executor.task().inbox("u1", &q).unwrap().rows {
+            assert!(ids.insert(row.id));
+        }
+    }
+    assert_eq!(ids.len(), 325);
+}
+
+#[tokio::test]
+async fn export_executor_inbox_order() {
+    let engine = Engine::new().start();
+    let executor = engine.executor();
+    let low = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::irq(|act| act.with_key("low"))
+                .with_assignment(|a| a.with_assignee("{{ assignee }}").with_priority(1)),
+        )
+    });
+    let high = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::irq(|act| act.with_key("high"))
+                .with_assignment(|a| a.with_assignee("{{ assignee }}").with_priority(5)),
+        )
+    });
+
+    let rt = engine.runtime();
+    let sig = engine.signal(0);
+    let s1 = sig.clone();
+    engine.channel().on_message(move |e| {
+        if e.is_irq() && e.is_state(MessageState::Created) {
+            s1.update(|count| *count += 1);
+            if s1.data() == 2 {
+                s1.close();
+            }
+        }
+    });
+    let uid = utils::longid();
+    let mut vars = Vars::new();
+    vars.insert("assignee".to_string(), json!(uid));
+    rt.start(&low, &vars).unwrap();
+    rt.start(&high, &vars).unwrap();
+    sig.recv().await;
+
+    let inbox = executor.task().inbox(&uid, &ExecutorQuery::new()).unwrap();
+    assert_eq!(inbox.count, 2);
+    assert_eq!(inbox.rows[0].key, "high");
+    assert_eq!(inbox.rows[1].key, "low");
+
+    let inbox = executor
+        .task()
+        .inbox(&uid, &ExecutorQuery::new().with_offset(1).with_count(1))
+        .unwrap();
+    assert_eq!(inbox.page_count, 2);
+    assert_eq!(inbox.page_num, 2);
+    assert_eq!(inbox.rows[0].key, "low");
+}
+
 #[tokio::test]
 async fn export_extender_register_module() {
     let engine = Engine::new().start();
@@ -1794,6 +2496,21 @@ async fn export_extender_register_module() {
     assert_eq!(count, before_count + 1);
 }
 
+#[tokio::test]
+async fn export_extender_register_calendar() {
+    let engine = Engine::new().start();
+    let extender = engine.extender();
+
+    let calendar = Calendar::new().with_holiday("2026-12-25");
+    extender.register_calendar("cn", &calendar).unwrap();
+    assert_eq!(engine.runtime().calendars().get("cn"), Some(calendar));
+
+    let calendar = Calendar::new().with_work_hours("18:00", "09:00");
+    let ret = extender.register_calendar("error", &calendar);
+    assert!(matches!(ret, Err(ActError::Config(_))));
+    assert!(engine.runtime().calendars().get("error").is_none());
+}
+
 #[tokio::test]
 async fn export_emitter_default() {
     let engine = Engine::new().start();
@@ -2297,3 +3014,125 @@ mod test_module {
         }
     }
 }
+
+#[tokio::test]
+async fn export_executor_backup_export_import() {
+    let engine = Engine::new().start();
+    let model = Workflow::new().with_id(&utils::longid()).with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::irq(|act| act.with_key("act1")))
+    });
+    engine.executor().model().deploy(&model).unwrap();
+
+    let sig = engine.signal(String::new());
+    let s1 = sig.clone();
+    engine.channel().on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            s1.send(e.pid.clone());
+        }
+    });
+    engine.runtime().start(&model, &Vars::new()).unwrap();
+    let pid = sig.recv().await;
+
+    let bundle = engine.executor().backup().export().unwrap();
+    assert_eq!(bundle.version, crate::BUNDLE_VERSION);
+    assert!(bundle.models.iter().any(|m| m.id == model.id));
+    assert!(bundle.procs.iter().any(|p| p.id == pid));
+    assert!(bundle.tasks.iter().any(|t| t.pid == pid));
+
+    let target = Engine::new().start();
+    let count = target.executor().backup().import(&bundle).unwrap();
+    assert!(count > 0);
+    assert!(target.executor().model().get(&model.id, "text").is_ok());
+    assert!(target.runtime().store().procs().exists(&pid).unwrap());
+
+    // import again will overwrite the existing records
+    let count2 = target.executor().backup().import(&bundle).unwrap();
+    assert_eq!(count, count2);
+}
+
+#[tokio::test]
+async fn export_executor_backup_export_pages() {
+    let engine = Engine::new().start();
+    let store = engine.runtime().store();
+    for _ in 0..1001 {
+        store
+            .events()
+            .create(&data::Event {
+                id: utils::longid(),
+                ..Default::default()
+            })
+            .unwrap();
+    }
+
+    // the collections are read page by page
+    let bundle = engine.executor().backup().export().unwrap();
+    assert_eq!(bundle.events.len(), 1001);
+}
+
+#[tokio::test]
+async fn export_executor_backup_import_resume() {
+    let engine = Engine::new().start();
+    let model = Workflow::new().with_id(&utils::longid()).with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::irq(|act| act.with_key("act1")))
+    });
+    engine.executor().model().deploy(&model).unwrap();
+
+    let sig = engine.signal((String::new(), String::new()));
+    let s1 = sig.clone();
+    engine.channel().on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            s1.send((e.pid.clone(), e.tid.clone()));
+        }
+    });
+    engine.runtime().start(&model, &Vars::new()).unwrap();
+    let (pid, tid) = sig.recv().await;
+    let bundle = engine.executor().backup().export().unwrap();
+
+    // the imported process is reloaded and can be continued in the target engine
+    let target = Engine::new().start();
+    let sig = target.signal(String::new());
+    let s1 = sig.clone();
+    target
+        .channel()
+        .on_complete(move |e| s1.send(e.pid.clone()));
+    target.executor().backup().import(&bundle).unwrap();
+    target
+        .executor()
+        .act()
+        .complete(&pid, &tid, &Vars::new())
+        .unwrap();
+    assert_eq!(sig.recv().await, pid);
+}
+
+#[tokio::test]
+async fn export_executor_backup_ndjson() {
+    let engine = Engine::new().start();
+    let model = Workflow::new().with_id(&utils::longid());
+    engine.executor().model().deploy(&model).unwrap();
+
+    let text = engine.executor().backup().export_ndjson().unwrap();
+    let header: serde_json::Value = serde_json::from_str(text.lines().next().unwrap()).unwrap();
+    assert_eq!(header["version"], json!(crate::BUNDLE_VERSION));
+
+    let target = Engine::new().start();
+    target.executor().backup().import_ndjson(&text).unwrap();
+    assert!(target.executor().model().get(&model.id, "text").is_ok());
+
+    let json = engine.executor().backup().export_json().unwrap();
+    let target = Engine::new().start();
+    target.executor().backup().import_json(&json).unwrap();
+    assert!(target.executor().model().get(&model.id, "text").is_ok());
+}
+
+#[tokio::test]
+async fn export_executor_backup_version_error() {
+    let engine = Engine::new().start();
+    let bundle = crate::Bundle {
+        version: crate::BUNDLE_VERSION + 1,
+        ..Default::default()
+    };
+    let ret = engine.executor().backup().import(&bundle);
+    assert!(ret.is_err());
+}
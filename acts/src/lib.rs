@@ -4,6 +4,7 @@
 
 mod builder;
 mod cache;
+mod calendar;
 mod config;
 mod engine;
 mod env;
@@ -25,12 +26,15 @@ use std::sync::Arc;
 use std::sync::RwLock;
 
 pub use builder::EngineBuilder;
+pub use calendar::{Calendar, Clock, SystemClock};
 pub use config::Config;
 pub use engine::Engine;
 pub use env::ActUserVar;
-pub use error::{ActError, Error};
+pub use error::{ActError, Error, ModelError};
 pub use event::{Action, Event, Message, MessageState};
-pub use export::{Channel, ChannelOptions, Executor, ExecutorQuery, Extender};
+pub use export::{
+    BUNDLE_VERSION, Bundle, Channel, ChannelOptions, Executor, ExecutorQuery, Extender,
+};
 pub use model::*;
 pub use package::{
     ActOperation, ActPackage, ActPackageCatalog, ActPackageMeta, ActResource, ActRunAs,
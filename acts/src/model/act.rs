@@ -1,9 +1,13 @@
+mod assignment;
 mod catch;
 mod retry;
+mod sla;
 mod timeout;
 
+pub use assignment::Assignment;
 pub use catch::Catch;
 pub use retry::Retry;
+pub use sla::{Escalation, Sla};
 
 use crate::{ModelBase, StmtBuild, Vars};
 use serde::{Deserialize, Serialize};
@@ -67,6 +71,18 @@ pub struct Act {
 
     #[serde(default)]
     pub timeout: Vec<Timeout>,
+
+    /// the deadline and the escalations when the act is not completed in time
+    #[serde(default)]
+    pub sla: Option<Sla>,
+
+    /// the assignee, candidates, due time and priority of the irq act
+    #[serde(default)]
+    pub assignment: Option<Assignment>,
+
+    /// the acts to undo the completed act when the process is aborted or error
+    #[serde(default)]
+    pub compensate: Vec<Act>,
 }
 
 impl ModelBase for Act {
@@ -162,6 +178,12 @@ impl Act {
         self
     }
 
+    pub fn with_compensate(mut self, build: fn(Vec<Act>) -> Vec<Act>) -> Self {
+        let stmts = Vec::new();
+        self.compensate = build(stmts);
+        self
+    }
+
     pub fn with_on(mut self, event: ActEvent) -> Self {
         self.on = Some(event);
         self
@@ -179,6 +201,16 @@ impl Act {
         self
     }
 
+    pub fn with_sla(mut self, build: fn(Sla) -> Sla) -> Self {
+        self.sla = Some(build(Sla::default()));
+        self
+    }
+
+    pub fn with_assignment(mut self, build: fn(Assignment) -> Assignment) -> Self {
+        self.assignment = Some(build(Assignment::default()));
+        self
+    }
+
     pub fn with_if(mut self, v: &str) -> Self {
         self.r#if = Some(v.to_string());
         self
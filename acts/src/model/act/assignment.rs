@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+/// the human task assignment of the irq act
+/// the values can be the expressions, such as '{{ initiator }}'
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Assignment {
+    /// the user who does the task
+    #[serde(default)]
+    pub assignee: Option<String>,
+
+    /// the users who can claim the task
+    #[serde(default)]
+    pub candidate_users: Vec<String>,
+
+    /// the groups whose users can claim the task
+    #[serde(default)]
+    pub candidate_groups: Vec<String>,
+
+    /// the due time since the task is created, such as '3bd'
+    /// or the rfc3339 date time, such as '2026-10-01T18:00:00+08:00'
+    #[serde(default)]
+    pub due: Option<String>,
+
+    /// the bigger is the more important
+    #[serde(default)]
+    pub priority: i32,
+}
+
+impl Assignment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_assignee(mut self, uid: &str) -> Self {
+        self.assignee = Some(uid.to_string());
+        self
+    }
+
+    pub fn with_candidate_user(mut self, uid: &str) -> Self {
+        self.candidate_users.push(uid.to_string());
+        self
+    }
+
+    pub fn with_candidate_group(mut self, group: &str) -> Self {
+        self.candidate_groups.push(group.to_string());
+        self
+    }
+
+    pub fn with_due(mut self, v: &str) -> Self {
+        self.due = Some(v.to_string());
+        self
+    }
+
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+}
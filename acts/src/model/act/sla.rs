@@ -0,0 +1,85 @@
+use crate::{Vars, event::EventAction};
+use serde::{Deserialize, Serialize};
+
+/// the service level of the task with the deadline and the escalations
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Sla {
+    /// the deadline since the task is created, such as '5d'
+    #[serde(default)]
+    pub deadline: Option<String>,
+
+    /// the escalations in time order
+    #[serde(default)]
+    pub escalations: Vec<Escalation>,
+}
+
+/// the escalation runs once when the task is not completed in time
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Escalation {
+    /// the elapsed time since the task is created, such as '1d'
+    #[serde(default)]
+    pub on: String,
+
+    /// send a message with the key
+    #[serde(default)]
+    pub msg: Option<String>,
+
+    /// reassign the task to the user and send the task message again
+    #[serde(default)]
+    pub reassign: Option<String>,
+
+    /// do the action to the task, such as 'next'
+    #[serde(default)]
+    pub action: Option<EventAction>,
+
+    /// the action options
+    #[serde(default)]
+    pub options: Vars,
+}
+
+impl Sla {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_deadline(mut self, v: &str) -> Self {
+        self.deadline = Some(v.to_string());
+        self
+    }
+
+    pub fn with_escalation(mut self, build: fn(Escalation) -> Escalation) -> Self {
+        self.escalations.push(build(Escalation::default()));
+        self
+    }
+}
+
+impl Escalation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_on(mut self, v: &str) -> Self {
+        self.on = v.to_string();
+        self
+    }
+
+    pub fn with_msg(mut self, key: &str) -> Self {
+        self.msg = Some(key.to_string());
+        self
+    }
+
+    pub fn with_reassign(mut self, uid: &str) -> Self {
+        self.reassign = Some(uid.to_string());
+        self
+    }
+
+    pub fn with_action(mut self, action: EventAction) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    pub fn with_options(mut self, options: Vars) -> Self {
+        self.options = options;
+        self
+    }
+}
@@ -18,6 +18,10 @@ pub enum TimeoutUnit {
     Minute,
     Hour,
     Day,
+    /// the working hours by the calendar
+    BusinessHour,
+    /// the working days by the calendar
+    BusinessDay,
 }
 
 #[derive(Debug, Default, PartialEq, Clone)]
@@ -44,6 +48,8 @@ impl std::fmt::Display for TimeoutLimit {
                 TimeoutUnit::Minute => "m",
                 TimeoutUnit::Hour => "h",
                 TimeoutUnit::Day => "d",
+                TimeoutUnit::BusinessHour => "bh",
+                TimeoutUnit::BusinessDay => "bd",
             }
         ))
     }
@@ -83,34 +89,43 @@ impl Serialize for TimeoutLimit {
 
 impl TimeoutLimit {
     pub fn parse(expr: &str) -> Result<Self> {
-        let re = Regex::new(r"^(.*)(s|m|h|d)$").unwrap();
+        let re = Regex::new(r"^(.*?)(bd|bh|s|m|h|d)$").unwrap();
         let caps = re.captures(expr);
 
         if let Some(caps) = caps {
             let value = caps.get(1).map_or("0", |m| m.as_str());
             let unit = caps.get(2).map_or("s", |m| m.as_str());
 
-            let value = value
-                .parse::<i64>()
-                .map_err(|err| ActError::Model(format!("timeout parse error with '{err}'")))?;
+            let value = value.parse::<i64>().map_err(|err| {
+                ActError::Model(format!("timeout parse error with '{err}'").into())
+            })?;
             let unit = TimeoutUnit::parse(unit)?;
 
             return Ok(Self { value, unit });
         }
 
-        Err(ActError::Model(format!(
-            "timeout parse error with '{expr}'"
-        )))
+        Err(ActError::Model(
+            format!("timeout parse error with '{expr}'").into(),
+        ))
     }
 
+    /// the wall-clock seconds, the business units are taken as hours and days
     pub fn as_secs(&self) -> i64 {
         match self.unit {
             TimeoutUnit::Second => self.value,
             TimeoutUnit::Minute => self.value * 60,
-            TimeoutUnit::Hour => self.value * 60 * 60,
-            TimeoutUnit::Day => self.value * 60 * 60 * 24,
+            TimeoutUnit::Hour | TimeoutUnit::BusinessHour => self.value * 60 * 60,
+            TimeoutUnit::Day | TimeoutUnit::BusinessDay => self.value * 60 * 60 * 24,
         }
     }
+
+    /// check if the limit needs the calendar to calculate
+    pub fn is_business(&self) -> bool {
+        matches!(
+            self.unit,
+            TimeoutUnit::BusinessHour | TimeoutUnit::BusinessDay
+        )
+    }
 }
 
 impl TimeoutUnit {
@@ -120,9 +135,11 @@ impl TimeoutUnit {
             "m" => Ok(TimeoutUnit::Minute),
             "h" => Ok(TimeoutUnit::Hour),
             "d" => Ok(TimeoutUnit::Day),
-            _ => Err(ActError::Model(format!(
-                "timeout parse error with '{expr}'"
-            ))),
+            "bh" => Ok(TimeoutUnit::BusinessHour),
+            "bd" => Ok(TimeoutUnit::BusinessDay),
+            _ => Err(ActError::Model(
+                format!("timeout parse error with '{expr}'").into(),
+            )),
         }
     }
 }
@@ -0,0 +1,534 @@
+use crate::{Act, ActError, Branch, Catch, Result, Step, Timeout, Vars, Workflow};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// the options to convert the bpmn 2.0 process definition
+#[derive(Debug, Default, Clone)]
+pub struct BpmnOptions {
+    services: HashMap<String, String>,
+    default_service: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ElementKind {
+    Start,
+    End,
+    UserTask,
+    ServiceTask,
+    ExclusiveGateway,
+    ParallelGateway,
+}
+
+#[derive(Debug, Clone)]
+struct Element {
+    id: String,
+    name: String,
+    kind: ElementKind,
+    implementation: Option<String>,
+    default_flow: Option<String>,
+    error_code: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Flow {
+    id: String,
+    name: String,
+    source: String,
+    target: String,
+    condition: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+enum BoundaryKind {
+    Timer(String),
+    Error(Option<String>),
+}
+
+#[derive(Debug, Clone)]
+struct Boundary {
+    id: String,
+    attached_to: String,
+    kind: BoundaryKind,
+}
+
+#[derive(Default)]
+struct Converter {
+    elements: HashMap<String, Element>,
+    order: Vec<String>,
+    flows: Vec<Flow>,
+    boundaries: Vec<Boundary>,
+    visits: HashSet<String>,
+    unsupported: Vec<String>,
+}
+
+impl BpmnOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// map the service task to the package by its 'implementation' or id
+    pub fn with_service(mut self, name: &str, uses: &str) -> Self {
+        self.services.insert(name.to_string(), uses.to_string());
+        self
+    }
+
+    /// the package for the service tasks not mapped by 'with_service'
+    pub fn with_default_service(mut self, uses: &str) -> Self {
+        self.default_service = Some(uses.to_string());
+        self
+    }
+}
+
+impl Workflow {
+    /// convert the bpmn 2.0 xml to workflow
+    pub fn from_bpmn(xml: &str) -> Result<Self> {
+        Self::from_bpmn_with(xml, &BpmnOptions::default())
+    }
+
+    /// convert the bpmn 2.0 xml to workflow with the service task mapping
+    pub fn from_bpmn_with(xml: &str, options: &BpmnOptions) -> Result<Self> {
+        let doc = roxmltree::Document::parse(xml)
+            .map_err(|err| ActError::Model(format!("invalid bpmn: {err}").into()))?;
+        let root = doc.root_element();
+        let errors = root
+            .children()
+            .filter(|n| n.tag_name().name() == "error")
+            .filter_map(|n| {
+                let id = n.attribute("id")?;
+                Some((
+                    id.to_string(),
+                    n.attribute("errorCode").unwrap_or(id).to_string(),
+                ))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let processes = root
+            .children()
+            .filter(|n| n.tag_name().name() == "process")
+            .collect::<Vec<_>>();
+        if processes.len() != 1 {
+            return Err(ActError::Model(
+                format!("expect one bpmn process, but found {}", processes.len()).into(),
+            ));
+        }
+        let process = processes[0];
+
+        let mut converter = Converter::default();
+        converter.parse(&process, &errors);
+        if !converter.unsupported.is_empty() {
+            return Err(ActError::Model(
+                format!(
+                    "unsupported bpmn elements: {}",
+                    converter.unsupported.join(", ")
+                )
+                .into(),
+            ));
+        }
+
+        let mut workflow = Workflow::new()
+            .with_id(process.attribute("id").unwrap_or_default())
+            .with_name(
+                process
+                    .attribute("name")
+                    .or(root.attribute("name"))
+                    .unwrap_or_default(),
+            );
+        workflow.steps = converter.convert(options)?;
+
+        Ok(workflow)
+    }
+}
+
+impl Converter {
+    fn parse(&mut self, process: &roxmltree::Node, errors: &HashMap<String, String>) {
+        for node in process.children().filter(|n| n.is_element()) {
+            let tag = node.tag_name().name();
+            let id = node.attribute("id").unwrap_or_default().to_string();
+            let name = node.attribute("name").unwrap_or_default().to_string();
+            let definitions = node
+                .children()
+                .filter(|n| n.is_element() && n.tag_name().name().ends_with("EventDefinition"))
+                .collect::<Vec<_>>();
+
+            let kind = match tag {
+                "startEvent" if definitions.is_empty() => ElementKind::Start,
+                "endEvent" => ElementKind::End,
+                "userTask" => ElementKind::UserTask,
+                "serviceTask" => ElementKind::ServiceTask,
+                "exclusiveGateway" => ElementKind::ExclusiveGateway,
+                "parallelGateway" => ElementKind::ParallelGateway,
+                "sequenceFlow" => {
+                    let condition = node
+                        .children()
+                        .find(|n| n.tag_name().name() == "conditionExpression")
+                        .and_then(|n| n.text())
+                        .map(expression);
+                    self.flows.push(Flow {
+                        id,
+                        name,
+                        source: node.attribute("sourceRef").unwrap_or_default().to_string(),
+                        target: node.attribute("targetRef").unwrap_or_default().to_string(),
+                        condition,
+                    });
+                    continue;
+                }
+                "boundaryEvent" => {
+                    let kind = match definitions.first() {
+                        Some(def) if definitions.len() == 1 => match def.tag_name().name() {
+                            "timerEventDefinition" => def
+                                .children()
+                                .find(|n| n.tag_name().name() == "timeDuration")
+                                .and_then(|n| n.text())
+                                .map(|text| BoundaryKind::Timer(text.trim().to_string())),
+                            "errorEventDefinition" => Some(BoundaryKind::Error(
+                                def.attribute("errorRef")
+                                    .map(|r| errors.get(r).cloned().unwrap_or(r.to_string())),
+                            )),
+                            _ => None,
+                        },
+                        _ => None,
+                    };
+                    match kind {
+                        Some(kind) => self.boundaries.push(Boundary {
+                            id: id.clone(),
+                            attached_to: node
+                                .attribute("attachedToRef")
+                                .unwrap_or_default()
+                                .to_string(),
+                            kind,
+                        }),
+                        None => self.unsupported.push(format!("{tag}({id})")),
+                    }
+                    self.order.push(id);
+                    continue;
+                }
+                "laneSet" | "documentation" | "extensionElements" | "textAnnotation"
+                | "association" => continue,
+                _ => {
+                    self.unsupported.push(format!("{tag}({id})"));
+                    continue;
+                }
+            };
+
+            let mut error_code = None;
+            if kind == ElementKind::End {
+                match definitions.first().map(|n| n.tag_name().name()) {
+                    None | Some("terminateEventDefinition") => {}
+                    Some("errorEventDefinition") => {
+                        let def = definitions[0];
+                        error_code = Some(
+                            def.attribute("errorRef")
+                                .map(|r| errors.get(r).cloned().unwrap_or(r.to_string()))
+                                .unwrap_or_default(),
+                        );
+                    }
+                    Some(_) => {
+                        self.unsupported.push(format!("{tag}({id})"));
+                        continue;
+                    }
+                }
+            }
+
+            self.order.push(id.clone());
+            self.elements.insert(
+                id.clone(),
+                Element {
+                    id,
+                    name,
+                    kind,
+                    implementation: node.attribute("implementation").map(|v| v.to_string()),
+                    default_flow: node.attribute("default").map(|v| v.to_string()),
+                    error_code,
+                },
+            );
+        }
+    }
+
+    fn convert(&mut self, options: &BpmnOptions) -> Result<Vec<Step>> {
+        let starts = self
+            .order
+            .iter()
+            .filter(|id| {
+                self.elements
+                    .get(*id)
+                    .is_some_and(|e| e.kind == ElementKind::Start)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        if starts.len() != 1 {
+            return Err(ActError::Model(
+                format!("expect one bpmn start event, but found {}", starts.len()).into(),
+            ));
+        }
+
+        let start = &starts[0];
+        self.visits.insert(start.clone());
+        let mut steps = match self.outgoing(start).first() {
+            Some(flow) => self.walk(&flow.target.clone(), None, options)?,
+            None => Vec::new(),
+        };
+
+        // the boundary events are converted after the main flow
+        for boundary in self.boundaries.clone() {
+            self.visits.insert(boundary.id.clone());
+            let boundary_steps = match self.outgoing(&boundary.id).first() {
+                Some(flow) => self.walk(&flow.target.clone(), None, options)?,
+                None => Vec::new(),
+            };
+            let step = find_step(&mut steps, &boundary.attached_to).ok_or(ActError::Model(
+                format!(
+                    "cannot find the task '{}' for boundary event '{}'",
+                    boundary.attached_to, boundary.id
+                )
+                .into(),
+            ))?;
+            match boundary.kind {
+                BoundaryKind::Timer(duration) => step.timeout.push(Timeout {
+                    on: duration_limit(&duration)?,
+                    steps: boundary_steps,
+                }),
+                BoundaryKind::Error(on) => step.catches.push(Catch {
+                    on,
+                    steps: boundary_steps,
+                }),
+            }
+        }
+
+        let unreachable = self
+            .order
+            .iter()
+            .filter(|id| !self.visits.contains(*id))
+            .cloned()
+            .collect::<Vec<_>>();
+        if !unreachable.is_empty() {
+            return Err(ActError::Model(
+                format!(
+                    "bpmn elements are not reachable from the start event: {}",
+                    unreachable.join(", ")
+                )
+                .into(),
+            ));
+        }
+
+        Ok(steps)
+    }
+
+    /// convert the elements from id until the stop element
+    fn walk(&mut self, id: &str, stop: Option<&str>, options: &BpmnOptions) -> Result<Vec<Step>> {
+        let mut steps: Vec<Step> = Vec::new();
+        let mut current = Some(id.to_string());
+        while let Some(id) = current.take() {
+            if stop == Some(id.as_str()) {
+                break;
+            }
+
+            // go back to the converted element
+            if self.visits.contains(&id) {
+                match steps.last_mut() {
+                    Some(step) => step.next = Some(id),
+                    None => {
+                        return Err(ActError::Model(
+                            format!("unsupported bpmn loop to '{id}' without a task before it")
+                                .into(),
+                        ));
+                    }
+                }
+                break;
+            }
+            self.visits.insert(id.clone());
+
+            let element = self.elements.get(&id).cloned().ok_or(ActError::Model(
+                format!("cannot find the bpmn element '{id}'").into(),
+            ))?;
+            match element.kind {
+                ElementKind::Start | ElementKind::End => {
+                    if let Some(ecode) = &element.error_code {
+                        steps.push(
+                            Step::new()
+                                .with_id(&element.id)
+                                .with_name(&element.name)
+                                .with_act(Act::action(
+                                    Vars::new()
+                                        .with("action", "error")
+                                        .with("options", Vars::new().with("ecode", ecode)),
+                                )),
+                        );
+                    }
+                }
+                ElementKind::UserTask | ElementKind::ServiceTask => {
+                    steps.push(task_step(&element, options)?);
+                    current = self.outgoing(&id).first().map(|f| f.target.clone());
+                }
+                ElementKind::ExclusiveGateway | ElementKind::ParallelGateway => {
+                    let outgoing = self.outgoing(&id);
+                    if outgoing.len() <= 1 {
+                        // the joining gateway
+                        current = outgoing.first().map(|f| f.target.clone());
+                        continue;
+                    }
+
+                    let join = self.find_join(&id);
+                    let mut step = Step::new().with_id(&element.id).with_name(&element.name);
+                    for flow in outgoing {
+                        let mut branch = Branch::new().with_id(&flow.id).with_name(&flow.name);
+                        if element.kind == ElementKind::ParallelGateway {
+                            branch.r#if = Some("true".to_string());
+                        } else if element.default_flow.as_deref() == Some(flow.id.as_str()) {
+                            branch.r#else = true;
+                        } else {
+                            branch.r#if = Some(flow.condition.clone().ok_or(ActError::Model(
+                                format!("the sequence flow '{}' has no condition", flow.id).into(),
+                            ))?);
+                        }
+                        branch.steps = self.walk(&flow.target, join.as_deref(), options)?;
+                        step.branches.push(branch);
+                    }
+                    steps.push(step);
+                    current = join;
+                }
+            }
+        }
+
+        Ok(steps)
+    }
+
+    fn outgoing(&self, id: &str) -> Vec<Flow> {
+        self.flows
+            .iter()
+            .filter(|f| f.source == id)
+            .cloned()
+            .collect()
+    }
+
+    /// find the nearest gateway reachable from all the outgoing flows
+    fn find_join(&self, id: &str) -> Option<String> {
+        let reaches = self
+            .outgoing(id)
+            .iter()
+            .map(|flow| self.reachable(&flow.target, id))
+            .collect::<Vec<_>>();
+        let first = reaches.first()?;
+        first
+            .iter()
+            .find(|node| {
+                self.elements.get(*node).is_some_and(|e| {
+                    matches!(
+                        e.kind,
+                        ElementKind::ExclusiveGateway | ElementKind::ParallelGateway
+                    )
+                }) && reaches.iter().all(|r| r.contains(node))
+            })
+            .cloned()
+    }
+
+    /// the element ids reachable from id in bfs order
+    fn reachable(&self, id: &str, exclude: &str) -> Vec<String> {
+        let mut ret = Vec::new();
+        let mut queue = VecDeque::from([id.to_string()]);
+        while let Some(id) = queue.pop_front() {
+            if id == exclude || ret.contains(&id) {
+                continue;
+            }
+            for flow in self.outgoing(&id) {
+                queue.push_back(flow.target);
+            }
+            ret.push(id);
+        }
+        ret
+    }
+}
+
+fn task_step(element: &Element, options: &BpmnOptions) -> Result<Step> {
+    let act = match element.kind {
+        ElementKind::ServiceTask => {
+            let uses = element
+                .implementation
+                .as_ref()
+                .and_then(|name| options.services.get(name))
+                .or(options.services.get(&element.id))
+                .or(options.default_service.as_ref())
+                .ok_or(ActError::Model(
+                    format!("cannot find the package for service task '{}'", element.id).into(),
+                ))?;
+            Act::new().with_uses(uses)
+        }
+        _ => Act::irq(|act| act),
+    };
+
+    Ok(Step::new()
+        .with_id(&element.id)
+        .with_name(&element.name)
+        .with_act(act.with_key(&element.id).with_name(&element.name)))
+}
+
+fn find_step<'a>(steps: &'a mut [Step], id: &str) -> Option<&'a mut Step> {
+    for step in steps.iter_mut() {
+        if step.id == id {
+            return Some(step);
+        }
+        for branch in step.branches.iter_mut() {
+            if let Some(step) = find_step(&mut branch.steps, id) {
+                return Some(step);
+            }
+        }
+        for catch in step.catches.iter_mut() {
+            if let Some(step) = find_step(&mut catch.steps, id) {
+                return Some(step);
+            }
+        }
+        for timeout in step.timeout.iter_mut() {
+            if let Some(step) = find_step(&mut timeout.steps, id) {
+                return Some(step);
+            }
+        }
+    }
+    None
+}
+
+/// get the expression from '${ expr }' or '#{ expr }'
+fn expression(text: &str) -> String {
+    let text = text.trim();
+    for prefix in ["${", "#{"] {
+        if let Some(expr) = text.strip_prefix(prefix).and_then(|t| t.strip_suffix('}')) {
+            return expr.trim().to_string();
+        }
+    }
+    text.to_string()
+}
+
+/// convert the iso 8601 duration (such as 'PT5M') to the timeout limit
+fn duration_limit(duration: &str) -> Result<String> {
+    let err = || ActError::Model(format!("unsupported bpmn timer duration '{duration}'").into());
+    let text = duration.strip_prefix('P').ok_or_else(err)?;
+    let mut seconds = 0;
+    let mut is_time = false;
+    let mut value = String::new();
+    for c in text.chars() {
+        match c {
+            'T' => is_time = true,
+            '0'..='9' => value.push(c),
+            _ => {
+                let n = value.parse::<i64>().map_err(|_| err())?;
+                value.clear();
+                seconds += n * match (is_time, c) {
+                    (false, 'W') => 7 * 86400,
+                    (false, 'D') => 86400,
+                    (true, 'H') => 3600,
+                    (true, 'M') => 60,
+                    (true, 'S') => 1,
+                    _ => return Err(err()),
+                };
+            }
+        }
+    }
+    if !value.is_empty() || seconds == 0 {
+        return Err(err());
+    }
+
+    let limit = match seconds {
+        s if s % 86400 == 0 => format!("{}d", s / 86400),
+        s if s % 3600 == 0 => format!("{}h", s / 3600),
+        s if s % 60 == 0 => format!("{}m", s / 60),
+        s => format!("{s}s"),
+    };
+    Ok(limit)
+}
@@ -1,6 +1,7 @@
 use crate::{ModelBase, Vars, model::Step};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
+use serde_json::json;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Branch {
@@ -38,6 +39,14 @@ pub struct Branch {
 
     #[serde(default)]
     pub needs: Vec<String>,
+
+    /// the case value to match the step switch value
+    #[serde(default)]
+    pub case: Option<JsonValue>,
+
+    /// run the branch when no case matches the step switch value
+    #[serde(default)]
+    pub default: bool,
 }
 
 impl ModelBase for Branch {
@@ -105,4 +114,24 @@ impl Branch {
         self.needs.push(need.to_string());
         self
     }
+
+    pub fn with_case<T: Serialize>(mut self, value: T) -> Self {
+        self.case = Some(json!(value));
+        self
+    }
+
+    pub fn with_default(mut self, default: bool) -> Self {
+        self.default = default;
+        self
+    }
+
+    /// check if the case matches the switch value
+    /// the numbers are compared by value, such as 1 and 1.0
+    pub fn is_case(&self, value: &JsonValue) -> bool {
+        match (&self.case, value) {
+            (Some(JsonValue::Number(a)), JsonValue::Number(b)) => a.as_f64() == b.as_f64(),
+            (Some(case), value) => case == value,
+            (None, _) => false,
+        }
+    }
 }
@@ -1,8 +1,9 @@
 use crate::{
-    ActError, ActRunAs, MessageState, Result, Workflow,
+    ActError, ActRunAs, MessageState, Result, Vars, Workflow,
     package::ActPackageCatalog,
     scheduler::{self, NodeData},
     store::data,
+    utils::consts,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -52,6 +53,33 @@ pub struct TaskInfo {
     pub start_time: i64,
     pub end_time: i64,
     pub timestamp: i64,
+
+    /// the iteration index when the task is created by a loop step
+    pub index: Option<u32>,
+
+    /// the sla deadline in milliseconds
+    pub deadline: Option<i64>,
+
+    /// the assignment of the irq act
+    pub assignment: Option<TaskAssignment>,
+}
+
+/// the assignment of the task which is saved in the task data
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TaskAssignment {
+    /// the user who does the task
+    pub assignee: Option<String>,
+
+    /// the user who delegates the task to the assignee
+    pub owner: Option<String>,
+
+    pub candidate_users: Vec<String>,
+    pub candidate_groups: Vec<String>,
+
+    /// the due time in milliseconds
+    pub due: Option<i64>,
+
+    pub priority: i32,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -100,6 +128,20 @@ pub struct EventInfo {
     pub timestamp: i64,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct HistoryInfo {
+    pub id: String,
+    pub pid: String,
+    pub tid: String,
+    pub kind: String,
+    pub name: String,
+    pub actor: String,
+    pub data: serde_json::Value,
+
+    pub create_time: i64,
+    pub timestamp: i64,
+}
+
 impl From<&data::Package> for PackageInfo {
     fn from(m: &data::Package) -> Self {
         Self {
@@ -171,6 +213,14 @@ impl From<&data::Proc> for ProcInfo {
 impl From<data::Task> for TaskInfo {
     fn from(t: data::Task) -> Self {
         let node_data: NodeData = serde_json::from_str(&t.node_data).unwrap();
+        let data = serde_json::from_str::<Vars>(&t.data).ok();
+        let index = data
+            .as_ref()
+            .and_then(|data| data.get::<u32>(consts::ACT_INDEX));
+        let deadline = data
+            .as_ref()
+            .and_then(|data| data.get::<i64>(consts::TASK_DEADLINE));
+        let assignment = data.and_then(|data| data.get::<TaskAssignment>(consts::TASK_ASSIGNMENT));
         Self {
             id: t.tid,
             prev: t.prev,
@@ -185,6 +235,9 @@ impl From<data::Task> for TaskInfo {
             timestamp: t.timestamp,
             key: node_data.content.key(),
             tag: node_data.content.tag(),
+            index,
+            deadline,
+            assignment,
         }
     }
 }
@@ -211,10 +264,21 @@ impl From<&Arc<scheduler::Task>> for TaskInfo {
             timestamp: t.timestamp,
             tag: t.node().tag(),
             key: t.node().key(),
+            index: t.with_data(|data| data.get::<u32>(consts::ACT_INDEX)),
+            deadline: t.deadline(),
+            assignment: t.assignment(),
         }
     }
 }
 
+impl TaskAssignment {
+    /// check if the user can claim the task by the candidate users or groups
+    pub fn is_candidate(&self, uid: &str, groups: &[String]) -> bool {
+        self.candidate_users.iter().any(|u| u == uid)
+            || self.candidate_groups.iter().any(|g| groups.contains(g))
+    }
+}
+
 impl From<&data::Message> for MessageInfo {
     fn from(m: &data::Message) -> Self {
         Self {
@@ -290,3 +354,25 @@ impl From<EventInfo> for serde_json::Value {
         serde_json::to_value(val).unwrap()
     }
 }
+
+impl From<&data::History> for HistoryInfo {
+    fn from(h: &data::History) -> Self {
+        Self {
+            id: h.id.clone(),
+            pid: h.pid.clone(),
+            tid: h.tid.clone(),
+            kind: h.kind.clone(),
+            name: h.name.clone(),
+            actor: h.actor.clone(),
+            data: serde_json::from_str(&h.data).unwrap_or(serde_json::Value::Null),
+            create_time: h.create_time,
+            timestamp: h.timestamp,
+        }
+    }
+}
+
+impl From<HistoryInfo> for serde_json::Value {
+    fn from(val: HistoryInfo) -> Self {
+        serde_json::to_value(val).unwrap()
+    }
+}
@@ -0,0 +1,246 @@
+use crate::{Act, ActError, Branch, Step, Workflow};
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use std::fmt;
+
+/// the marker message to stop the yaml deserializing at the located value
+const LOCATED: &str = "@located";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// parse the path such as `steps[0].acts[1].uses` to the segments
+fn segments(path: &str) -> Vec<Segment> {
+    let mut ret = Vec::new();
+    for part in path.split('.').filter(|p| !p.is_empty()) {
+        let (key, indexes) = part.split_once('[').unwrap_or((part, ""));
+        if !key.is_empty() {
+            ret.push(Segment::Key(key.to_string()));
+        }
+        for index in indexes.split('[') {
+            if let Ok(index) = index.trim_end_matches(']').parse::<usize>() {
+                ret.push(Segment::Index(index));
+            }
+        }
+    }
+    ret
+}
+
+/// walk the yaml value by the path segments and raise the located error
+/// when reaching the target, the error is marked with the position by serde_yaml
+struct Locate<'a> {
+    path: &'a [Segment],
+}
+
+impl Locate<'_> {
+    fn located<T, E: de::Error>(&self) -> Result<T, E> {
+        if self.path.is_empty() {
+            return Err(E::custom(LOCATED));
+        }
+        Err(E::custom("not found"))
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for Locate<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Locate<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("yaml value")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let Some((Segment::Key(name), rest)) = self.path.split_first() else {
+            return self.located();
+        };
+        while let Some(key) = map.next_key::<String>()? {
+            if &key == name {
+                return map.next_value_seed(Locate { path: rest });
+            }
+            map.next_value::<IgnoredAny>()?;
+        }
+        Err(de::Error::custom("not found"))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let Some((Segment::Index(index), rest)) = self.path.split_first() else {
+            return self.located();
+        };
+        for _ in 0..*index {
+            if seq.next_element::<IgnoredAny>()?.is_none() {
+                return Err(de::Error::custom("not found"));
+            }
+        }
+        match seq.next_element_seed(Locate { path: rest })? {
+            Some(_) => Ok(()),
+            None => Err(de::Error::custom("not found")),
+        }
+    }
+
+    fn visit_bool<E: de::Error>(self, _: bool) -> Result<(), E> {
+        self.located()
+    }
+
+    fn visit_i64<E: de::Error>(self, _: i64) -> Result<(), E> {
+        self.located()
+    }
+
+    fn visit_u64<E: de::Error>(self, _: u64) -> Result<(), E> {
+        self.located()
+    }
+
+    fn visit_f64<E: de::Error>(self, _: f64) -> Result<(), E> {
+        self.located()
+    }
+
+    fn visit_str<E: de::Error>(self, _: &str) -> Result<(), E> {
+        self.located()
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+        self.located()
+    }
+}
+
+/// find the 1-based line and column of the path in the yaml source
+pub(crate) fn locate_yml(source: &str, path: &str) -> Option<(usize, usize)> {
+    let segments = segments(path);
+    let err = Locate { path: &segments }
+        .deserialize(serde_yaml::Deserializer::from_str(source))
+        .err()?;
+    if !err.to_string().contains(LOCATED) {
+        return None;
+    }
+    err.location().map(|loc| (loc.line(), loc.column()))
+}
+
+/// find the path of the node by id, the last one is returned for the duplicated ids
+pub(crate) fn node_path(model: &Workflow, id: &str) -> Option<String> {
+    let mut ret = None;
+    if model.id == id {
+        ret = Some(String::new());
+    }
+    for (index, act) in model.on.iter().enumerate() {
+        if act.id == id {
+            ret = Some(format!("on[{index}]"));
+        }
+    }
+    for (index, step) in model.steps.iter().enumerate() {
+        step_path(step, &format!("steps[{index}]"), id, &mut ret);
+    }
+    for (index, catch) in model.catches.iter().enumerate() {
+        for (i, step) in catch.steps.iter().enumerate() {
+            step_path(step, &format!("catches[{index}].steps[{i}]"), id, &mut ret);
+        }
+    }
+    for (index, timeout) in model.timeout.iter().enumerate() {
+        for (i, step) in timeout.steps.iter().enumerate() {
+            step_path(step, &format!("timeout[{index}].steps[{i}]"), id, &mut ret);
+        }
+    }
+    ret
+}
+
+fn step_path(step: &Step, path: &str, id: &str, ret: &mut Option<String>) {
+    if step.id == id {
+        *ret = Some(path.to_string());
+    }
+    for (index, branch) in step.branches.iter().enumerate() {
+        branch_path(branch, &format!("{path}.branches[{index}]"), id, ret);
+    }
+    for (index, act) in step.acts.iter().enumerate() {
+        act_path(act, &format!("{path}.acts[{index}]"), id, ret);
+    }
+    for (index, catch) in step.catches.iter().enumerate() {
+        for (i, step) in catch.steps.iter().enumerate() {
+            step_path(
+                step,
+                &format!("{path}.catches[{index}].steps[{i}]"),
+                id,
+                ret,
+            );
+        }
+    }
+    for (index, timeout) in step.timeout.iter().enumerate() {
+        for (i, step) in timeout.steps.iter().enumerate() {
+            step_path(
+                step,
+                &format!("{path}.timeout[{index}].steps[{i}]"),
+                id,
+                ret,
+            );
+        }
+    }
+}
+
+fn branch_path(branch: &Branch, path: &str, id: &str, ret: &mut Option<String>) {
+    if branch.id == id {
+        *ret = Some(path.to_string());
+    }
+    for (index, step) in branch.steps.iter().enumerate() {
+        step_path(step, &format!("{path}.steps[{index}]"), id, ret);
+    }
+}
+
+fn act_path(act: &Act, path: &str, id: &str, ret: &mut Option<String>) {
+    if act.id == id {
+        *ret = Some(path.to_string());
+    }
+    for (index, catch) in act.catches.iter().enumerate() {
+        for (i, step) in catch.steps.iter().enumerate() {
+            step_path(
+                step,
+                &format!("{path}.catches[{index}].steps[{i}]"),
+                id,
+                ret,
+            );
+        }
+    }
+    for (index, timeout) in act.timeout.iter().enumerate() {
+        for (i, step) in timeout.steps.iter().enumerate() {
+            step_path(
+                step,
+                &format!("{path}.timeout[{index}].steps[{i}]"),
+                id,
+                ret,
+            );
+        }
+    }
+}
+
+impl Workflow {
+    /// fill the model error with the node path and the line and column in the yaml source
+    pub(crate) fn locate(&self, err: ActError) -> ActError {
+        let ActError::Model(mut err) = err else {
+            return err;
+        };
+        let node = err.node.as_ref().and_then(|id| node_path(self, id));
+        if let Some(path) = &node {
+            err.path = match (path.is_empty(), err.path.is_empty()) {
+                (_, true) => path.clone(),
+                (true, false) => err.path,
+                (false, false) => format!("{path}.{}", err.path),
+            };
+        }
+        if err.line.is_none()
+            && let Some(source) = &self.source
+        {
+            // fallback to the node when the field is not in the source
+            let location = locate_yml(source, &err.path)
+                .or_else(|| node.and_then(|path| locate_yml(source, &path)));
+            if let Some((line, column)) = location {
+                err = err.with_location(line, column);
+            }
+        }
+        ActError::Model(err)
+    }
+}
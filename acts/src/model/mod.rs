@@ -1,19 +1,28 @@
 mod act;
+mod bpmn;
 mod branch;
 mod info;
+mod location;
 mod output;
+mod schema;
 mod step;
+mod template;
 mod vars;
 mod workflow;
 
 #[cfg(test)]
 mod tests;
 
-pub use act::{Act, Catch, Retry, Timeout, TimeoutLimit};
+pub use act::{Act, Assignment, Catch, Escalation, Retry, Sla, Timeout, TimeoutLimit, TimeoutUnit};
+pub use bpmn::BpmnOptions;
 pub use branch::Branch;
-pub use info::{EventInfo, MessageInfo, ModelInfo, PackageInfo, ProcInfo, TaskInfo};
+pub use info::{
+    EventInfo, HistoryInfo, MessageInfo, ModelInfo, PackageInfo, ProcInfo, TaskAssignment, TaskInfo,
+};
 pub use output::{Output, OutputType, Outputs};
-pub use step::Step;
+pub(crate) use schema::validator;
+pub use step::{LOOP_MAX_DEFAULT, Loop, Step};
+pub use template::UseTemplate;
 pub use vars::Vars;
 pub use workflow::Workflow;
 
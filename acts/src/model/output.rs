@@ -4,13 +4,24 @@ use serde::{Deserialize, Serialize, de};
 use serde_json::Value;
 use std::collections::HashMap;
 
+/// the keys to declare the output in the model outputs
+const OUTPUT_DECL_KEYS: [&str; 3] = ["$type", "$required", "$default"];
+
+/// the legacy keys to declare the output, the `type` key is required to tell it from the literals
+const OUTPUT_LEGACY_KEYS: [&str; 3] = ["type", "required", "default"];
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize, strum::AsRefStr)]
 pub enum OutputType {
     #[default]
+    #[serde(alias = "string")]
     String,
+    #[serde(alias = "bool", alias = "boolean")]
     Bool,
+    #[serde(alias = "number")]
     Number,
+    #[serde(alias = "array")]
     Array,
+    #[serde(alias = "object")]
     Object,
 }
 
@@ -59,6 +70,45 @@ impl From<Value> for Output {
     }
 }
 
+impl Output {
+    /// parse the output declaration from the value
+    /// the value is a declaration only when it is an object with the explicit
+    /// '$type', '$required' or '$default' keys and no other keys, or the legacy
+    /// 'type', 'required' and 'default' keys with a valid 'type', the other values
+    /// such as `{ "required": true }` are kept as the literals
+    pub fn parse(value: &Value) -> Option<Self> {
+        let map = value.as_object()?;
+        let is_decl =
+            |keys: &[&str]| !map.is_empty() && map.keys().all(|k| keys.contains(&k.as_str()));
+        let is_legacy = is_decl(&OUTPUT_LEGACY_KEYS) && map.contains_key("type");
+        if !is_decl(&OUTPUT_DECL_KEYS) && !is_legacy {
+            return None;
+        }
+        let decl = map
+            .iter()
+            .map(|(k, v)| (k.trim_start_matches('$').to_string(), v.clone()))
+            .collect::<serde_json::Map<String, Value>>();
+        if decl
+            .get("type")
+            .is_some_and(|v| serde_json::from_value::<OutputType>(v.clone()).is_err())
+        {
+            return None;
+        }
+
+        Some(Value::Object(decl).into())
+    }
+
+    pub fn is_match(&self, value: &Value) -> bool {
+        match self.r#type {
+            OutputType::String => value.is_string(),
+            OutputType::Bool => value.is_boolean(),
+            OutputType::Number => value.is_number(),
+            OutputType::Array => value.is_array(),
+            OutputType::Object => value.is_object(),
+        }
+    }
+}
+
 impl Serialize for Outputs {
     fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
@@ -122,28 +172,51 @@ impl Outputs {
         self.inner.insert(name.to_string(), output.clone());
     }
 
+    /// collect the output declarations from the model outputs
+    /// the plain output values are ignored
+    pub fn from_vars(vars: &Vars) -> Self {
+        let mut outputs = Self::default();
+        for (ref k, ref v) in vars {
+            if let Some(output) = Output::parse(v) {
+                outputs.push(k, &output);
+            }
+        }
+        outputs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.inner.contains_key(name)
+    }
+
+    /// set the default values for the missing keys and check the vars
+    pub fn apply(&self, vars: &mut Vars) -> crate::Result<()> {
+        for (k, output) in &self.inner {
+            let is_missing = vars.get_value(k).is_none_or(|v| v.is_null());
+            if is_missing && !output.default.is_null() {
+                vars.set(k, output.default.clone());
+            }
+        }
+        self.check(vars)
+    }
+
     pub fn check(&self, vars: &Vars) -> crate::Result<()> {
         for (k, output) in &self.inner {
-            let v = vars.get::<Value>(k);
+            let v = vars.get::<Value>(k).filter(|v| !v.is_null());
             if v.is_none() && output.required {
                 return Err(ActError::Runtime(format!("the key '{k}' is required",)));
             }
 
-            if let Some(v) = &v {
-                let is_type_match = match output.r#type {
-                    OutputType::String => v.is_string(),
-                    OutputType::Bool => v.is_boolean(),
-                    OutputType::Number => v.is_number(),
-                    OutputType::Array => v.is_array(),
-                    OutputType::Object => v.is_object(),
-                };
-
-                if !is_type_match {
-                    return Err(ActError::Runtime(format!(
-                        "the value {k}({v}) is not matched the type '{}'",
-                        output.r#type
-                    )));
-                }
+            if let Some(v) = &v
+                && !output.is_match(v)
+            {
+                return Err(ActError::Runtime(format!(
+                    "the value {k}({v}) is not matched the type '{}'",
+                    output.r#type
+                )));
             }
         }
         Ok(())
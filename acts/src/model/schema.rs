@@ -0,0 +1,292 @@
+use crate::{ActError, Engine, Result, Workflow, query::Query, store::Store, utils};
+use serde_json::{Value as JsonValue, json};
+
+/// the json schema draft of the workflow dsl
+const SCHEMA_DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// the pattern of the time such as `1d`
+const TIME_PATTERN: &str = "^[0-9]+(s|m|h|d|bh|bd)$";
+
+/// the pattern of the expression such as `{{ items }}`
+const EXPR_PATTERN: &str = r"^\s*\{\{.*\}\}\s*$";
+
+fn nullable(typ: &str) -> JsonValue {
+    json!({ "type": [typ, "null"] })
+}
+
+fn list(def: &str) -> JsonValue {
+    json!({ "type": "array", "items": { "$ref": format!("#/$defs/{def}") } })
+}
+
+/// the model node which rejects the unknown keys
+/// the yaml merge key `<<` and the vendor extension keys `x-*` are allowed
+fn object(properties: JsonValue) -> JsonValue {
+    json!({
+        "type": "object",
+        "properties": properties,
+        "patternProperties": {
+            "^x-": {},
+            "^<<$": { "type": ["object", "array"] }
+        },
+        "additionalProperties": false
+    })
+}
+
+/// allow the expression for the params values at any level since they are filled when running
+fn with_exprs(schema: &JsonValue) -> JsonValue {
+    let mut schema = schema.clone();
+    if let Some(obj) = schema.as_object_mut() {
+        for key in ["properties", "patternProperties"] {
+            if let Some(properties) = obj.get_mut(key).and_then(|p| p.as_object_mut()) {
+                for value in properties.values_mut() {
+                    *value = or_expr(value);
+                }
+            }
+        }
+        for key in ["items", "additionalProperties"] {
+            if let Some(value) = obj.get_mut(key).filter(|v| v.is_object()) {
+                *value = or_expr(value);
+            }
+        }
+    }
+    schema
+}
+
+fn or_expr(schema: &JsonValue) -> JsonValue {
+    json!({
+        "anyOf": [with_exprs(schema), { "type": "string", "pattern": EXPR_PATTERN }]
+    })
+}
+
+/// build the workflow dsl schema with the package params schemas
+/// the packages are the pairs of the package name and its params schema
+fn build(packages: &[(String, JsonValue)]) -> JsonValue {
+    let vars = json!({ "type": "object" });
+    let input_schema = json!({ "type": ["object", "boolean", "null"] });
+
+    let workflow = object(json!({
+        "id": { "type": "string" },
+        "name": { "type": "string" },
+        "desc": { "type": "string" },
+        "tag": { "type": "string" },
+        "steps": list("step"),
+        "env": vars,
+        "inputs": vars,
+        "outputs": vars,
+        "input_schema": input_schema,
+        "setup": list("act"),
+        "on": list("act"),
+        "catches": list("catch"),
+        "timeout": list("timeout"),
+        "imports": { "type": "array", "items": { "type": "string" } },
+        "templates": list("step"),
+        "calendar": nullable("string"),
+        "ver": { "type": "integer" },
+    }));
+
+    let step = object(json!({
+        "id": { "type": "string" },
+        "name": { "type": "string" },
+        "desc": { "type": "string" },
+        "tag": { "type": "string" },
+        "inputs": vars,
+        "outputs": vars,
+        "input_schema": input_schema,
+        "if": nullable("string"),
+        "branches": list("branch"),
+        "next": nullable("string"),
+        "acts": list("act"),
+        "catches": list("catch"),
+        "timeout": list("timeout"),
+        "sla": { "oneOf": [{ "$ref": "#/$defs/sla" }, { "type": "null" }] },
+        "setup": list("act"),
+        "compensate": list("act"),
+        "loop": { "oneOf": [{ "$ref": "#/$defs/loop" }, { "type": "null" }] },
+        "for_each": nullable("string"),
+        "switch": nullable("string"),
+        "use_template": { "oneOf": [{ "$ref": "#/$defs/use_template" }, { "type": "null" }] },
+    }));
+
+    let branch = object(json!({
+        "id": { "type": "string" },
+        "name": { "type": "string" },
+        "desc": { "type": "string" },
+        "tag": { "type": "string" },
+        "inputs": vars,
+        "outputs": vars,
+        "run": nullable("string"),
+        "if": nullable("string"),
+        "steps": list("step"),
+        "next": nullable("string"),
+        "else": { "type": "boolean" },
+        "needs": { "type": "array", "items": { "type": "string" } },
+        "case": {},
+        "default": { "type": "boolean" },
+    }));
+
+    let mut act = object(json!({
+        "id": { "type": "string" },
+        "name": { "type": "string" },
+        "desc": { "type": "string" },
+        "uses": {
+            "type": "string",
+            "examples": packages.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        },
+        "params": {},
+        "options": vars,
+        "if": nullable("string"),
+        "key": { "type": "string" },
+        "tag": { "type": "string" },
+        "on": {
+            "enum": ["created", "completed", "before_update", "updated", "step", null]
+        },
+        "inputs": vars,
+        "outputs": vars,
+        "setup": list("act"),
+        "catches": list("catch"),
+        "timeout": list("timeout"),
+        "sla": { "oneOf": [{ "$ref": "#/$defs/sla" }, { "type": "null" }] },
+        "assignment": {
+            "oneOf": [{ "$ref": "#/$defs/assignment" }, { "type": "null" }]
+        },
+        "compensate": list("act"),
+    }));
+
+    // check the params by the package schema
+    let rules = packages
+        .iter()
+        .filter(|(_, schema)| schema.as_object().is_some_and(|s| !s.is_empty()))
+        .map(|(name, schema)| {
+            json!({
+                "if": {
+                    "properties": { "uses": { "const": name } },
+                    "required": ["uses"]
+                },
+                "then": { "properties": { "params": with_exprs(schema) } }
+            })
+        })
+        .collect::<Vec<_>>();
+    if !rules.is_empty() {
+        act["allOf"] = json!(rules);
+    }
+
+    let catch = object(json!({
+        "on": nullable("string"),
+        "steps": list("step"),
+    }));
+
+    let timeout = object(json!({
+        "on": { "type": "string", "pattern": TIME_PATTERN },
+        "steps": list("step"),
+    }));
+
+    let sla = object(json!({
+        "deadline": { "type": ["string", "null"], "pattern": TIME_PATTERN },
+        "escalations": list("escalation"),
+    }));
+
+    let mut escalation = object(json!({
+        "on": { "type": "string", "pattern": TIME_PATTERN },
+        "msg": nullable("string"),
+        "reassign": nullable("string"),
+        "action": {
+            "enum": [
+                "next", "submit", "back", "cancel", "abort", "skip",
+                "error", "push", "remove", "set_process_vars", null
+            ]
+        },
+        "options": vars,
+    }));
+    escalation["required"] = json!(["on"]);
+
+    let assignment = object(json!({
+        "assignee": nullable("string"),
+        "candidate_users": { "type": "array", "items": { "type": "string" } },
+        "candidate_groups": { "type": "array", "items": { "type": "string" } },
+        "due": nullable("string"),
+        "priority": { "type": "integer" },
+    }));
+
+    let r#loop = object(json!({
+        "while": nullable("string"),
+        "until": nullable("string"),
+        "max": { "type": ["integer", "null"], "minimum": 0 },
+    }));
+
+    let mut use_template = object(json!({
+        "id": { "type": "string" },
+        "params": vars,
+    }));
+    use_template["required"] = json!(["id"]);
+
+    json!({
+        "$schema": SCHEMA_DRAFT,
+        "title": "acts workflow",
+        "$ref": "#/$defs/workflow",
+        "$defs": {
+            "workflow": workflow,
+            "step": step,
+            "branch": branch,
+            "act": act,
+            "catch": catch,
+            "timeout": timeout,
+            "sla": sla,
+            "escalation": escalation,
+            "assignment": assignment,
+            "loop": r#loop,
+            "use_template": use_template,
+        }
+    })
+}
+
+/// the installed packages with their params schemas
+fn package_schemas(store: &Store) -> Result<Vec<(String, JsonValue)>> {
+    let packages = store
+        .packages()
+        .query(&Query::new())?
+        .rows
+        .iter()
+        .map(|p| {
+            let schema = serde_json::from_str::<JsonValue>(&p.schema).unwrap_or(json!({}));
+            (p.id.clone(), schema)
+        })
+        .collect();
+    Ok(packages)
+}
+
+/// build the validator of the workflow dsl schema with the installed packages
+pub(crate) fn validator(store: &Store) -> Result<jsonschema::Validator> {
+    let schema = build(&package_schemas(store)?);
+    Ok(jsonschema::validator_for(&schema)?)
+}
+
+impl Workflow {
+    /// generate the json schema of the workflow dsl
+    /// the act params are checked by the schemas of the installed packages
+    pub fn json_schema(engine: &Engine) -> Result<JsonValue> {
+        let packages = package_schemas(&engine.runtime().cache().store())?;
+        Ok(build(&packages))
+    }
+
+    /// check the workflow by the dsl schema with the params schemas of the installed packages
+    pub fn check_schema(&self, engine: &Engine) -> Result<()> {
+        self.check_schema_with(&*engine.runtime().cache().schema()?)
+    }
+
+    /// check the yaml or json source of the workflow by the dsl schema
+    /// the source is checked before deserializing to report the unknown or misspelled keys,
+    /// the serialized workflow is checked when it is not parsed from a source
+    pub(crate) fn check_schema_with(&self, validator: &jsonschema::Validator) -> Result<()> {
+        let value = match &self.source {
+            Some(source) => serde_yaml::from_str::<JsonValue>(source)
+                .map_err(|err| ActError::Model(err.to_string().into()))?,
+            None => serde_json::to_value(self)?,
+        };
+        utils::check_validator(validator, &value).map_err(|err| match err {
+            ActError::Model(err) => {
+                ActError::Model(format!("invalid model '{}': {err}", self.id).into())
+            }
+            err => err,
+        })
+    }
+}
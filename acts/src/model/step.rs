@@ -1,8 +1,30 @@
 #[allow(unused_imports)]
-use crate::{Act, Catch, ModelBase, Timeout, Vars, model::Branch};
+use crate::{Act, Catch, ModelBase, Sla, Timeout, UseTemplate, Vars, model::Branch};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
+/// the default max iterations of the step loop
+/// it is used to guard the loop against the infinite cycles
+pub const LOOP_MAX_DEFAULT: u32 = 1000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Loop {
+    /// run the next iteration while the expression is true
+    /// it is checked before each iteration
+    #[serde(default)]
+    pub r#while: Option<String>,
+
+    /// stop the loop when the expression is true
+    /// it is checked after each iteration
+    #[serde(default)]
+    pub until: Option<String>,
+
+    /// the max iterations, default to 1000
+    /// the step fails if the default is reached without an explicit max
+    #[serde(default)]
+    pub max: Option<u32>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Step {
     #[serde(default)]
@@ -20,6 +42,10 @@ pub struct Step {
     #[serde(default)]
     pub outputs: Vars,
 
+    /// the json schema to validate the step inputs when the step is created
+    #[serde(default)]
+    pub input_schema: Option<JsonValue>,
+
     #[serde(default)]
     pub tag: String,
 
@@ -41,8 +67,30 @@ pub struct Step {
     #[serde(default)]
     pub timeout: Vec<Timeout>,
 
+    /// the deadline and the escalations when the step is not completed in time
+    #[serde(default)]
+    pub sla: Option<Sla>,
+
     #[serde(default)]
     pub setup: Vec<Act>,
+
+    /// the acts to undo the completed step when the process is aborted or error
+    #[serde(default)]
+    pub compensate: Vec<Act>,
+
+    #[serde(default)]
+    pub r#loop: Option<Loop>,
+
+    #[serde(default)]
+    pub for_each: Option<String>,
+
+    /// the expression is evaluated once to route to the branch with the matched case
+    #[serde(default)]
+    pub switch: Option<String>,
+
+    /// the step is replaced by the template when deploying the model
+    #[serde(default)]
+    pub use_template: Option<UseTemplate>,
 }
 
 impl ModelBase for Step {
@@ -101,6 +149,11 @@ impl Step {
         self
     }
 
+    pub fn with_input_schema(mut self, schema: JsonValue) -> Self {
+        self.input_schema = Some(schema);
+        self
+    }
+
     pub fn with_catch(mut self, build: fn(Catch) -> Catch) -> Self {
         let catch = Catch::default();
         self.catches.push(build(catch));
@@ -113,9 +166,69 @@ impl Step {
         self
     }
 
+    pub fn with_sla(mut self, build: fn(Sla) -> Sla) -> Self {
+        self.sla = Some(build(Sla::default()));
+        self
+    }
+
     pub fn with_setup(mut self, build: fn(Vec<Act>) -> Vec<Act>) -> Self {
         let stmts = Vec::new();
         self.setup = build(stmts);
         self
     }
+
+    pub fn with_compensate(mut self, build: fn(Vec<Act>) -> Vec<Act>) -> Self {
+        let stmts = Vec::new();
+        self.compensate = build(stmts);
+        self
+    }
+
+    pub fn with_loop(mut self, build: fn(Loop) -> Loop) -> Self {
+        self.r#loop = Some(build(Loop::default()));
+        self
+    }
+
+    pub fn with_for_each(mut self, expr: &str) -> Self {
+        self.for_each = Some(expr.to_string());
+        self
+    }
+
+    pub fn with_switch(mut self, expr: &str) -> Self {
+        self.switch = Some(expr.to_string());
+        self
+    }
+
+    pub fn with_template(mut self, build: fn(UseTemplate) -> UseTemplate) -> Self {
+        self.use_template = Some(build(UseTemplate::default()));
+        self
+    }
+
+    pub fn is_loop(&self) -> bool {
+        self.r#loop.is_some() || self.for_each.is_some()
+    }
+}
+
+impl Loop {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_while(mut self, expr: &str) -> Self {
+        self.r#while = Some(expr.to_string());
+        self
+    }
+
+    pub fn with_until(mut self, expr: &str) -> Self {
+        self.until = Some(expr.to_string());
+        self
+    }
+
+    pub fn with_max(mut self, max: u32) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    pub fn max(&self) -> u32 {
+        self.max.unwrap_or(LOOP_MAX_DEFAULT)
+    }
 }
@@ -0,0 +1,301 @@
+use crate::{ActError, ModelError, Result, Step, Vars, Workflow, utils};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+
+/// the max nested levels of the templates
+/// it is used to guard the circular templates
+pub const TEMPLATE_DEPTH_MAX: usize = 16;
+
+const CODE_PACKAGE: &str = "acts.transform.code";
+
+/// use a step template in the workflow `templates` or the imported models
+///
+/// the `${name}` placeholders in the template are replaced by the params
+/// the nested node ids are prefixed by the step id, such as `step1_act1`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UseTemplate {
+    /// the template step id
+    pub id: String,
+
+    /// the params to replace the placeholders
+    #[serde(default)]
+    pub params: Vars,
+}
+
+impl UseTemplate {
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    pub fn with_param<T>(mut self, name: &str, value: T) -> Self
+    where
+        T: Serialize + Clone,
+    {
+        self.params.set(name, value);
+        self
+    }
+}
+
+impl Workflow {
+    /// resolve the `use_template` steps into the normal steps
+    /// the `load` function is to load the imported models by id
+    pub fn resolve_templates<F: Fn(&str) -> Result<Workflow>>(&mut self, load: F) -> Result<()> {
+        let mut templates = self.templates.clone();
+        for id in self.imports.iter() {
+            let model = load(id).map_err(|err| {
+                ActError::Model(format!("cannot import model '{id}': {err}").into())
+            })?;
+            templates.extend(model.templates);
+            templates.extend(model.steps);
+        }
+
+        for step in self.templates.iter_mut() {
+            resolve_step(step, &templates, 0)?;
+        }
+        for step in self.steps.iter_mut() {
+            resolve_step(step, &templates, 0)?;
+        }
+        for catch in self.catches.iter_mut() {
+            for step in catch.steps.iter_mut() {
+                resolve_step(step, &templates, 0)?;
+            }
+        }
+        for timeout in self.timeout.iter_mut() {
+            for step in timeout.steps.iter_mut() {
+                resolve_step(step, &templates, 0)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn resolve_step(step: &mut Step, templates: &[Step], depth: usize) -> Result<()> {
+    let mut depth = depth;
+    let mut is_template = false;
+    while let Some(t) = step.use_template.take() {
+        if depth >= TEMPLATE_DEPTH_MAX {
+            return Err(ActError::Model(
+                format!("template '{}' is nested too deep or circular", t.id).into(),
+            ));
+        }
+        check_own_fields(step, &t)?;
+        let template = templates
+            .iter()
+            .find(|s| s.id == t.id)
+            .ok_or(ActError::Model(
+                format!("cannot find template '{}'", t.id).into(),
+            ))?;
+
+        let mut value = serde_json::to_value(template)
+            .map_err(|err| ActError::Model(err.to_string().into()))?;
+        fill_params(&mut value, &t.params);
+        let mut resolved: Step =
+            serde_json::from_value(value).map_err(|err| ActError::Model(err.to_string().into()))?;
+
+        // keep the step own identity
+        resolved.id = step.id.clone();
+        if !step.name.is_empty() {
+            resolved.name = step.name.clone();
+        }
+        if !step.tag.is_empty() {
+            resolved.tag = step.tag.clone();
+        }
+        if step.r#if.is_some() {
+            resolved.r#if = step.r#if.clone();
+        }
+        if step.next.is_some() {
+            resolved.next = step.next.clone();
+        }
+        *step = resolved;
+
+        // the template can also use the other templates
+        depth += 1;
+        is_template = true;
+    }
+
+    for branch in step.branches.iter_mut() {
+        for step in branch.steps.iter_mut() {
+            resolve_step(step, templates, depth)?;
+        }
+    }
+    for catch in step.catches.iter_mut() {
+        for step in catch.steps.iter_mut() {
+            resolve_step(step, templates, depth)?;
+        }
+    }
+    for timeout in step.timeout.iter_mut() {
+        for step in timeout.steps.iter_mut() {
+            resolve_step(step, templates, depth)?;
+        }
+    }
+
+    if is_template {
+        prefix_ids(step);
+    }
+
+    Ok(())
+}
+
+/// the `use_template` step can only set its own identity fields,
+/// the other fields are rejected instead of being replaced by the template silently
+fn check_own_fields(step: &Step, t: &UseTemplate) -> Result<()> {
+    let own = Step {
+        id: String::new(),
+        name: String::new(),
+        tag: String::new(),
+        r#if: None,
+        next: None,
+        ..step.clone()
+    };
+    let value =
+        serde_json::to_value(&own).map_err(|err| ActError::Model(err.to_string().into()))?;
+    let default = serde_json::to_value(Step::default())
+        .map_err(|err| ActError::Model(err.to_string().into()))?;
+    let keys = match (&value, &default) {
+        (JsonValue::Object(value), JsonValue::Object(default)) => value
+            .iter()
+            .filter(|(k, v)| default.get(k.as_str()) != Some(v))
+            .map(|(k, _)| format!("'{k}'"))
+            .collect::<Vec<_>>(),
+        _ => Vec::new(),
+    };
+    if !keys.is_empty() {
+        return Err(ActError::Model(
+            ModelError::new(&format!(
+                "step '{}' uses template '{}' and cannot set {}, only 'id', 'name', 'tag', 'if' and 'next' are kept",
+                step.id,
+                t.id,
+                keys.join(", ")
+            ))
+            .with_node(&step.id),
+        ));
+    }
+
+    Ok(())
+}
+
+/// prefix the nested node ids by the step id
+/// to keep the ids unique when the template is used more than once
+fn prefix_ids(step: &mut Step) {
+    if step.id.is_empty() {
+        step.id = utils::shortid();
+    }
+    let prefix = step.id.clone();
+    let mut ids = HashMap::new();
+    rename_nodes(step, &prefix, &mut ids);
+    relink_nodes(step, &ids);
+}
+
+fn rename_id(id: &mut String, prefix: &str, ids: &mut HashMap<String, String>) {
+    if id.is_empty() {
+        return;
+    }
+    let new_id = format!("{prefix}_{id}");
+    ids.insert(id.clone(), new_id.clone());
+    *id = new_id;
+}
+
+fn rename_steps(steps: &mut [Step], prefix: &str, ids: &mut HashMap<String, String>) {
+    for step in steps.iter_mut() {
+        rename_id(&mut step.id, prefix, ids);
+        rename_nodes(step, prefix, ids);
+    }
+}
+
+fn rename_nodes(step: &mut Step, prefix: &str, ids: &mut HashMap<String, String>) {
+    for act in step.acts.iter_mut() {
+        rename_id(&mut act.id, prefix, ids);
+    }
+    for branch in step.branches.iter_mut() {
+        rename_id(&mut branch.id, prefix, ids);
+        rename_steps(&mut branch.steps, prefix, ids);
+    }
+    for catch in step.catches.iter_mut() {
+        rename_steps(&mut catch.steps, prefix, ids);
+    }
+    for timeout in step.timeout.iter_mut() {
+        rename_steps(&mut timeout.steps, prefix, ids);
+    }
+}
+
+fn relink_id(id: &mut String, ids: &HashMap<String, String>) {
+    if let Some(new_id) = ids.get(id) {
+        *id = new_id.clone();
+    }
+}
+
+fn relink_steps(steps: &mut [Step], ids: &HashMap<String, String>) {
+    for step in steps.iter_mut() {
+        if let Some(next) = step.next.as_mut() {
+            relink_id(next, ids);
+        }
+        relink_nodes(step, ids);
+    }
+}
+
+fn relink_nodes(step: &mut Step, ids: &HashMap<String, String>) {
+    for branch in step.branches.iter_mut() {
+        if let Some(next) = branch.next.as_mut() {
+            relink_id(next, ids);
+        }
+        for need in branch.needs.iter_mut() {
+            relink_id(need, ids);
+        }
+        relink_steps(&mut branch.steps, ids);
+    }
+    for catch in step.catches.iter_mut() {
+        relink_steps(&mut catch.steps, ids);
+    }
+    for timeout in step.timeout.iter_mut() {
+        relink_steps(&mut timeout.steps, ids);
+    }
+}
+
+/// replace the `${name}` placeholders in the strings
+/// the whole placeholder string is replaced by the param value with its type
+/// the code of `acts.transform.code` is kept for the javascript template literals
+fn fill_params(value: &mut JsonValue, params: &Vars) {
+    match value {
+        JsonValue::String(s) => {
+            for (name, param) in params.iter() {
+                let key = format!("${{{name}}}");
+                if *s == key {
+                    *value = param.clone();
+                    return;
+                }
+                if s.contains(&key) {
+                    let text = match param {
+                        JsonValue::String(v) => v.clone(),
+                        v => v.to_string(),
+                    };
+                    *s = s.replace(&key, &text);
+                }
+            }
+        }
+        JsonValue::Array(items) => {
+            for item in items.iter_mut() {
+                fill_params(item, params);
+            }
+        }
+        JsonValue::Object(map) => {
+            let is_code = map.get("uses").and_then(|v| v.as_str()) == Some(CODE_PACKAGE);
+            for (key, item) in map.iter_mut() {
+                if is_code && key == "params" {
+                    continue;
+                }
+                fill_params(item, params);
+            }
+        }
+        _ => {}
+    }
+}
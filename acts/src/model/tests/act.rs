@@ -1,6 +1,8 @@
+mod assignment;
 mod catch;
 mod hooks;
 mod setup;
+mod sla;
 mod timeout;
 
 use crate::Act;
@@ -0,0 +1,69 @@
+use crate::{Act, ActError, Engine, Workflow, scheduler::NodeTree};
+
+#[test]
+fn model_act_assignment() {
+    let act = Act::irq(|act| act.with_key("act1")).with_assignment(|a| {
+        a.with_assignee("u1")
+            .with_candidate_user("u2")
+            .with_candidate_group("managers")
+            .with_due("3bd")
+            .with_priority(10)
+    });
+
+    let assignment = act.assignment.unwrap();
+    assert_eq!(assignment.assignee.as_deref(), Some("u1"));
+    assert_eq!(assignment.candidate_users, ["u2"]);
+    assert_eq!(assignment.candidate_groups, ["managers"]);
+    assert_eq!(assignment.due.as_deref(), Some("3bd"));
+    assert_eq!(assignment.priority, 10);
+}
+
+#[tokio::test]
+async fn model_act_yml_assignment() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          acts:
+            - uses: acts.core.irq
+              key: act1
+              assignment:
+                assignee: "{{ initiator }}"
+                candidate_groups: [managers]
+                due: "2026-10-01T18:00:00+08:00"
+                priority: 5
+    "#;
+    let workflow = Workflow::from_yml(text).unwrap();
+    let engine = Engine::new().start();
+    workflow.check_schema(&engine).unwrap();
+
+    let step = workflow.step("step1").unwrap();
+    let assignment = step.acts[0].assignment.as_ref().unwrap();
+    assert_eq!(assignment.assignee.as_deref(), Some("{{ initiator }}"));
+    assert_eq!(assignment.candidate_groups, ["managers"]);
+    assert!(assignment.candidate_users.is_empty());
+    assert_eq!(assignment.priority, 5);
+}
+
+#[test]
+fn model_act_assignment_due_error() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::irq(|act| act.with_id("act1").with_key("act1"))
+                .with_assignment(|a| a.with_due("tomorrow")),
+        )
+    });
+    let Err(ActError::Model(err)) = NodeTree::build(&mut workflow) else {
+        panic!("expect model error");
+    };
+    assert_eq!(err.path, "steps[0].acts[0].assignment.due");
+
+    // the expression is checked when running
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::irq(|act| act.with_id("act1").with_key("act1"))
+                .with_assignment(|a| a.with_due("{{ due }}")),
+        )
+    });
+    assert!(NodeTree::build(&mut workflow).is_ok());
+}
@@ -0,0 +1,122 @@
+use crate::{Act, ActError, Engine, Step, Workflow, event::EventAction, scheduler::NodeTree};
+
+#[test]
+fn model_act_sla() {
+    let act = Act::irq(|act| act.with_key("act1")).with_sla(|sla| {
+        sla.with_deadline("5d")
+            .with_escalation(|e| e.with_on("1d").with_msg("remind"))
+            .with_escalation(|e| e.with_on("3d").with_reassign("manager"))
+            .with_escalation(|e| e.with_on("5d").with_action(EventAction::Next))
+    });
+
+    let sla = act.sla.unwrap();
+    assert_eq!(sla.deadline.as_deref(), Some("5d"));
+    assert_eq!(sla.escalations.len(), 3);
+    assert_eq!(sla.escalations[0].msg.as_deref(), Some("remind"));
+    assert_eq!(sla.escalations[1].reassign.as_deref(), Some("manager"));
+    assert_eq!(sla.escalations[2].action, Some(EventAction::Next));
+}
+
+#[tokio::test]
+async fn model_act_yml_sla() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          sla:
+            deadline: 2d
+          acts:
+            - uses: acts.core.irq
+              key: act1
+              sla:
+                deadline: 5d
+                escalations:
+                    - on: 1d
+                      msg: remind
+                    - on: 5d
+                      action: next
+                      options:
+                        approved: true
+    "#;
+    let workflow = Workflow::from_yml(text).unwrap();
+    let engine = Engine::new().start();
+    workflow.check_schema(&engine).unwrap();
+
+    let step = workflow.step("step1").unwrap();
+    assert_eq!(step.sla.as_ref().unwrap().deadline.as_deref(), Some("2d"));
+
+    let sla = step.acts[0].sla.as_ref().unwrap();
+    assert_eq!(sla.escalations.len(), 2);
+    assert_eq!(sla.escalations[1].action, Some(EventAction::Next));
+    assert_eq!(
+        sla.escalations[1].options.get::<bool>("approved"),
+        Some(true)
+    );
+}
+
+#[test]
+fn model_act_sla_order_error() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::irq(|act| act.with_id("act1").with_key("act1")).with_sla(|sla| {
+                sla.with_escalation(|e| e.with_on("2d").with_msg("remind"))
+                    .with_escalation(|e| e.with_on("1d").with_msg("remind"))
+            }),
+        )
+    });
+    let Err(ActError::Model(err)) = NodeTree::build(&mut workflow) else {
+        panic!("expect model error");
+    };
+    assert!(err.message.contains("time order"));
+    assert_eq!(err.path, "steps[0].acts[0].sla.escalations[1].on");
+}
+
+#[test]
+fn model_act_sla_mixed_units_error() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::irq(|act| act.with_id("act1").with_key("act1")).with_sla(|sla| {
+                sla.with_escalation(|e| e.with_on("9bh").with_msg("remind"))
+                    .with_escalation(|e| e.with_on("1bd").with_msg("remind"))
+            }),
+        )
+    });
+    let Err(ActError::Model(err)) = NodeTree::build(&mut workflow) else {
+        panic!("expect model error");
+    };
+    assert!(err.message.contains("business unit"));
+    assert_eq!(err.path, "steps[0].acts[0].sla.escalations[1].on");
+
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_sla(|sla| {
+            sla.with_escalation(|e| e.with_on("2h").with_msg("remind"))
+                .with_escalation(|e| e.with_on("1bd").with_msg("remind"))
+        })
+    });
+    assert!(NodeTree::build(&mut workflow).is_err());
+
+    // the same business unit is in time order
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_sla(|sla| {
+            sla.with_escalation(|e| e.with_on("1bd").with_msg("remind"))
+                .with_escalation(|e| e.with_on("2bd").with_msg("remind"))
+        })
+    });
+    assert!(NodeTree::build(&mut workflow).is_ok());
+}
+
+#[test]
+fn model_act_sla_escalation_error() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_sla(|sla| sla.with_escalation(|e| e.with_on("1d")))
+    });
+    assert!(NodeTree::build(&mut workflow).is_err());
+
+    let mut workflow = Workflow::new().with_step(|_| {
+        Step::new()
+            .with_id("step1")
+            .with_sla(|sla| sla.with_deadline("abc"))
+    });
+    assert!(NodeTree::build(&mut workflow).is_err());
+}
@@ -0,0 +1,233 @@
+use crate::{BpmnOptions, Workflow};
+
+fn definitions(body: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<bpmn:definitions xmlns:bpmn="http://www.omg.org/spec/BPMN/20100524/MODEL" id="defs1">
+  <bpmn:error id="Error_1" errorCode="err1" />
+  <bpmn:process id="p1" name="process1" isExecutable="true">
+    {body}
+  </bpmn:process>
+</bpmn:definitions>"#
+    )
+}
+
+#[test]
+fn model_bpmn_sequence() {
+    let xml = definitions(
+        r#"
+    <bpmn:startEvent id="start" />
+    <bpmn:sequenceFlow id="f1" sourceRef="start" targetRef="task1" />
+    <bpmn:userTask id="task1" name="approve" />
+    <bpmn:sequenceFlow id="f2" sourceRef="task1" targetRef="task2" />
+    <bpmn:serviceTask id="task2" name="notify" implementation="mail" />
+    <bpmn:sequenceFlow id="f3" sourceRef="task2" targetRef="end" />
+    <bpmn:endEvent id="end" />
+    "#,
+    );
+    let options = BpmnOptions::new().with_service("mail", "acts.app.msg");
+    let workflow = Workflow::from_bpmn_with(&xml, &options).unwrap();
+    assert_eq!(workflow.id, "p1");
+    assert_eq!(workflow.name, "process1");
+    assert_eq!(workflow.steps.len(), 2);
+
+    let step = workflow.step("task1").unwrap();
+    assert_eq!(step.name, "approve");
+    assert_eq!(step.acts[0].uses, "acts.core.irq");
+    assert_eq!(step.acts[0].key, "task1");
+
+    let step = workflow.step("task2").unwrap();
+    assert_eq!(step.acts[0].uses, "acts.app.msg");
+    assert_eq!(step.acts[0].key, "task2");
+}
+
+#[test]
+fn model_bpmn_service_default() {
+    let xml = definitions(
+        r#"
+    <bpmn:startEvent id="start" />
+    <bpmn:sequenceFlow id="f1" sourceRef="start" targetRef="task1" />
+    <bpmn:serviceTask id="task1" />
+    "#,
+    );
+    assert!(Workflow::from_bpmn(&xml).is_err());
+
+    let options = BpmnOptions::new().with_default_service("acts.core.msg");
+    let workflow = Workflow::from_bpmn_with(&xml, &options).unwrap();
+    assert_eq!(workflow.steps[0].acts[0].uses, "acts.core.msg");
+}
+
+#[test]
+fn model_bpmn_exclusive_gateway() {
+    let xml = definitions(
+        r#"
+    <bpmn:startEvent id="start" />
+    <bpmn:sequenceFlow id="f1" sourceRef="start" targetRef="gw1" />
+    <bpmn:exclusiveGateway id="gw1" default="f3" />
+    <bpmn:sequenceFlow id="f2" sourceRef="gw1" targetRef="task1">
+      <bpmn:conditionExpression>${amount &gt; 100}</bpmn:conditionExpression>
+    </bpmn:sequenceFlow>
+    <bpmn:sequenceFlow id="f3" sourceRef="gw1" targetRef="task2" />
+    <bpmn:userTask id="task1" />
+    <bpmn:userTask id="task2" />
+    <bpmn:sequenceFlow id="f4" sourceRef="task1" targetRef="gw2" />
+    <bpmn:sequenceFlow id="f5" sourceRef="task2" targetRef="gw2" />
+    <bpmn:exclusiveGateway id="gw2" />
+    <bpmn:sequenceFlow id="f6" sourceRef="gw2" targetRef="task3" />
+    <bpmn:userTask id="task3" />
+    <bpmn:sequenceFlow id="f7" sourceRef="task3" targetRef="end" />
+    <bpmn:endEvent id="end" />
+    "#,
+    );
+    let workflow = Workflow::from_bpmn(&xml).unwrap();
+    assert_eq!(workflow.steps.len(), 2);
+
+    let step = workflow.step("gw1").unwrap();
+    assert_eq!(step.branches.len(), 2);
+    assert_eq!(step.branches[0].id, "f2");
+    assert_eq!(step.branches[0].r#if.as_deref(), Some("amount > 100"));
+    assert_eq!(step.branches[0].steps[0].id, "task1");
+    assert!(step.branches[1].r#else);
+    assert_eq!(step.branches[1].steps[0].id, "task2");
+    assert_eq!(workflow.steps[1].id, "task3");
+}
+
+#[test]
+fn model_bpmn_parallel_gateway() {
+    let xml = definitions(
+        r#"
+    <bpmn:startEvent id="start" />
+    <bpmn:sequenceFlow id="f1" sourceRef="start" targetRef="gw1" />
+    <bpmn:parallelGateway id="gw1" />
+    <bpmn:sequenceFlow id="f2" sourceRef="gw1" targetRef="task1" />
+    <bpmn:sequenceFlow id="f3" sourceRef="gw1" targetRef="task2" />
+    <bpmn:userTask id="task1" />
+    <bpmn:userTask id="task2" />
+    <bpmn:sequenceFlow id="f4" sourceRef="task1" targetRef="gw2" />
+    <bpmn:sequenceFlow id="f5" sourceRef="task2" targetRef="gw2" />
+    <bpmn:parallelGateway id="gw2" />
+    <bpmn:sequenceFlow id="f6" sourceRef="gw2" targetRef="end" />
+    <bpmn:endEvent id="end" />
+    "#,
+    );
+    let workflow = Workflow::from_bpmn(&xml).unwrap();
+    assert_eq!(workflow.steps.len(), 1);
+    let step = workflow.step("gw1").unwrap();
+    assert_eq!(step.branches.len(), 2);
+    assert!(
+        step.branches
+            .iter()
+            .all(|b| b.r#if.as_deref() == Some("true"))
+    );
+}
+
+#[test]
+fn model_bpmn_boundary_events() {
+    let xml = definitions(
+        r#"
+    <bpmn:startEvent id="start" />
+    <bpmn:sequenceFlow id="f1" sourceRef="start" targetRef="task1" />
+    <bpmn:userTask id="task1" />
+    <bpmn:sequenceFlow id="f2" sourceRef="task1" targetRef="end" />
+    <bpmn:endEvent id="end" />
+    <bpmn:boundaryEvent id="timer1" attachedToRef="task1">
+      <bpmn:timerEventDefinition>
+        <bpmn:timeDuration>PT1H30M</bpmn:timeDuration>
+      </bpmn:timerEventDefinition>
+    </bpmn:boundaryEvent>
+    <bpmn:sequenceFlow id="f3" sourceRef="timer1" targetRef="task2" />
+    <bpmn:userTask id="task2" />
+    <bpmn:boundaryEvent id="error1" attachedToRef="task1">
+      <bpmn:errorEventDefinition errorRef="Error_1" />
+    </bpmn:boundaryEvent>
+    <bpmn:sequenceFlow id="f4" sourceRef="error1" targetRef="task3" />
+    <bpmn:serviceTask id="task3" />
+    "#,
+    );
+    let options = BpmnOptions::new().with_service("task3", "acts.core.msg");
+    let workflow = Workflow::from_bpmn_with(&xml, &options).unwrap();
+    let step = workflow.step("task1").unwrap();
+    assert_eq!(step.timeout.len(), 1);
+    assert_eq!(step.timeout[0].on, "90m");
+    assert_eq!(step.timeout[0].steps[0].id, "task2");
+    assert_eq!(step.catches.len(), 1);
+    assert_eq!(step.catches[0].on.as_deref(), Some("err1"));
+    assert_eq!(step.catches[0].steps[0].id, "task3");
+
+    // the converted workflow should be valid
+    assert!(workflow.valid().is_ok());
+}
+
+#[test]
+fn model_bpmn_loop() {
+    let xml = definitions(
+        r#"
+    <bpmn:startEvent id="start" />
+    <bpmn:sequenceFlow id="f1" sourceRef="start" targetRef="task1" />
+    <bpmn:userTask id="task1" />
+    <bpmn:sequenceFlow id="f2" sourceRef="task1" targetRef="task2" />
+    <bpmn:userTask id="task2" />
+    <bpmn:sequenceFlow id="f3" sourceRef="task2" targetRef="task1" />
+    "#,
+    );
+    let workflow = Workflow::from_bpmn(&xml).unwrap();
+    assert_eq!(
+        workflow.step("task2").unwrap().next.as_deref(),
+        Some("task1")
+    );
+}
+
+#[test]
+fn model_bpmn_unsupported_elements() {
+    let xml = definitions(
+        r#"
+    <bpmn:startEvent id="start" />
+    <bpmn:sequenceFlow id="f1" sourceRef="start" targetRef="script1" />
+    <bpmn:scriptTask id="script1" />
+    <bpmn:inclusiveGateway id="gw1" />
+    "#,
+    );
+    let err = Workflow::from_bpmn(&xml).unwrap_err().to_string();
+    assert!(err.contains("scriptTask(script1)"));
+    assert!(err.contains("inclusiveGateway(gw1)"));
+}
+
+#[test]
+fn model_bpmn_unreachable_elements() {
+    let xml = definitions(
+        r#"
+    <bpmn:startEvent id="start" />
+    <bpmn:sequenceFlow id="f1" sourceRef="start" targetRef="task1" />
+    <bpmn:userTask id="task1" />
+    <bpmn:userTask id="task2" />
+    "#,
+    );
+    let err = Workflow::from_bpmn(&xml).unwrap_err().to_string();
+    assert!(err.contains("not reachable"));
+    assert!(err.contains("task2"));
+}
+
+#[test]
+fn model_bpmn_timer_duration_error() {
+    let xml = definitions(
+        r#"
+    <bpmn:startEvent id="start" />
+    <bpmn:sequenceFlow id="f1" sourceRef="start" targetRef="task1" />
+    <bpmn:userTask id="task1" />
+    <bpmn:boundaryEvent id="timer1" attachedToRef="task1">
+      <bpmn:timerEventDefinition>
+        <bpmn:timeDuration>P1M</bpmn:timeDuration>
+      </bpmn:timerEventDefinition>
+    </bpmn:boundaryEvent>
+    "#,
+    );
+    let err = Workflow::from_bpmn(&xml).unwrap_err().to_string();
+    assert!(err.contains("unsupported bpmn timer duration 'P1M'"));
+}
+
+#[test]
+fn model_bpmn_invalid_xml() {
+    assert!(Workflow::from_bpmn("<bpmn:definitions").is_err());
+    let xml = r#"<definitions xmlns="http://www.omg.org/spec/BPMN/20100524/MODEL" />"#;
+    assert!(Workflow::from_bpmn(xml).is_err());
+}
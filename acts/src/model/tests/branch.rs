@@ -76,3 +76,21 @@ fn model_branch_steps() {
         .with_step(|step| step.with_id("step2"));
     assert_eq!(b.steps.len(), 2);
 }
+
+#[test]
+fn model_branch_case() {
+    let b = Branch::new().with_case("a");
+    assert_eq!(b.case, Some(json!("a")));
+    assert!(b.is_case(&json!("a")));
+    assert!(!b.is_case(&json!("b")));
+
+    let b = Branch::new().with_case(1);
+    assert!(b.is_case(&json!(1.0)));
+}
+
+#[test]
+fn model_branch_default() {
+    let b = Branch::new().with_default(true);
+    assert!(b.default);
+    assert!(!b.is_case(&json!(null)));
+}
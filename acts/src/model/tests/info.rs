@@ -83,6 +83,9 @@ fn model_info_task() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
     let info: TaskInfo = task.clone().into();
     assert_eq!(info.id, task.tid);
@@ -239,6 +242,9 @@ fn model_info_task_arr_to_value() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
     let info: TaskInfo = task.clone().into();
 
@@ -1,7 +1,9 @@
 mod act;
+mod bpmn;
 mod branch;
 mod info;
 mod output;
+mod schema;
 mod step;
 mod valid;
 mod vars;
@@ -1,4 +1,4 @@
-use crate::{Output, OutputType, Outputs};
+use crate::{Output, OutputType, Outputs, Vars};
 use serde_json::json;
 
 #[test]
@@ -176,3 +176,77 @@ fn model_outputs_many() {
     assert_eq!(output.get("required").unwrap(), true);
     assert_eq!(output.get("type").unwrap(), "Object");
 }
+
+#[test]
+fn model_output_parse() {
+    let output = Output::parse(&json!({ "$type": "number", "$required": true })).unwrap();
+    assert!(output.required);
+    assert!(matches!(output.r#type, OutputType::Number));
+
+    let output = Output::parse(&json!({ "$type": "String", "$default": "abc" })).unwrap();
+    assert!(!output.required);
+    assert_eq!(output.default, json!("abc"));
+
+    // the plain values are not declarations
+    assert!(Output::parse(&json!(null)).is_none());
+    assert!(Output::parse(&json!("{{ a }}")).is_none());
+    assert!(Output::parse(&json!({ "a": 5 })).is_none());
+    assert!(Output::parse(&json!({})).is_none());
+    assert!(Output::parse(&json!({ "$type": "number", "a": 5 })).is_none());
+    assert!(Output::parse(&json!({ "$type": "unknown" })).is_none());
+
+    // the literals with the same keys are not declarations
+    assert!(Output::parse(&json!({ "required": true })).is_none());
+    assert!(Output::parse(&json!({ "type": "x" })).is_none());
+    assert!(Output::parse(&json!({ "type": "number", "a": 5 })).is_none());
+    assert!(Output::parse(&json!({ "type": "number", "$default": 5 })).is_none());
+}
+
+#[test]
+fn model_output_parse_legacy() {
+    let output = Output::parse(&json!({ "type": "number", "default": 5 })).unwrap();
+    assert!(!output.required);
+    assert!(matches!(output.r#type, OutputType::Number));
+    assert_eq!(output.default, json!(5));
+
+    let output = Output::parse(&json!({ "type": "string", "required": true })).unwrap();
+    assert!(output.required);
+    assert!(matches!(output.r#type, OutputType::String));
+}
+
+#[test]
+fn model_outputs_apply_legacy() {
+    let decls = Vars::new()
+        .with("a", json!({ "type": "string", "required": true }))
+        .with("b", json!({ "type": "number", "default": 5 }));
+    let outputs = Outputs::from_vars(&decls);
+
+    let mut vars = Vars::new().with("a", "abc");
+    outputs.apply(&mut vars).unwrap();
+    assert_eq!(vars.get::<i32>("b").unwrap(), 5);
+
+    let mut vars = Vars::new().with("a", 10);
+    assert!(outputs.apply(&mut vars).is_err());
+}
+
+#[test]
+fn model_outputs_apply() {
+    let decls = Vars::new()
+        .with("a", json!({ "$type": "string", "$required": true }))
+        .with("b", json!({ "$type": "number", "$default": 5 }))
+        .with("c", json!(null));
+    let outputs = Outputs::from_vars(&decls);
+    assert!(outputs.contains_key("a"));
+    assert!(outputs.contains_key("b"));
+    assert!(!outputs.contains_key("c"));
+
+    let mut vars = Vars::new().with("a", "abc");
+    outputs.apply(&mut vars).unwrap();
+    assert_eq!(vars.get::<i32>("b").unwrap(), 5);
+
+    let mut vars = Vars::new();
+    assert!(outputs.apply(&mut vars).is_err());
+
+    let mut vars = Vars::new().with("a", 10);
+    assert!(outputs.apply(&mut vars).is_err());
+}
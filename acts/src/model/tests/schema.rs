@@ -0,0 +1,261 @@
+use crate::{
+    Act, ActError, Assignment, Branch, Catch, Engine, Escalation, Loop, Sla, Step, Timeout,
+    UseTemplate, Workflow, data,
+};
+use serde_json::json;
+
+#[tokio::test]
+async fn model_schema_defs() {
+    let engine = Engine::new().start();
+    let schema = Workflow::json_schema(&engine).unwrap();
+    assert_eq!(schema["$ref"], "#/$defs/workflow");
+    for def in ["workflow", "step", "branch", "act", "catch", "timeout"] {
+        assert!(schema["$defs"][def].is_object());
+    }
+    assert!(
+        schema["$defs"]["act"]["properties"]["uses"]["examples"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("acts.core.irq"))
+    );
+}
+
+#[tokio::test]
+async fn model_schema_package_params() {
+    let engine = Engine::new().start();
+    let schema = Workflow::json_schema(&engine).unwrap();
+    let validator = jsonschema::validator_for(&schema).unwrap();
+
+    let model = Workflow::new().with_step(|step| {
+        step.with_act(
+            Act::new()
+                .with_uses("acts.core.subflow")
+                .with_params_vars(|vars| vars.with("to", "sub1")),
+        )
+    });
+    assert!(validator.is_valid(&serde_json::to_value(&model).unwrap()));
+
+    let model = Workflow::new().with_step(|step| {
+        step.with_act(
+            Act::new()
+                .with_uses("acts.core.subflow")
+                .with_params_vars(|vars| vars.with("to", 10)),
+        )
+    });
+    assert!(!validator.is_valid(&serde_json::to_value(&model).unwrap()));
+}
+
+#[tokio::test]
+async fn model_schema_package_params_expr() {
+    let engine = Engine::new().start();
+    let schema = Workflow::json_schema(&engine).unwrap();
+    let validator = jsonschema::validator_for(&schema).unwrap();
+
+    let model = Workflow::new().with_step(|step| {
+        step.with_act(Act::parallel(json!({
+            "in": "{{ users }}",
+            "acts": [{ "uses": "acts.core.irq", "key": "act1" }]
+        })))
+    });
+    assert!(validator.is_valid(&serde_json::to_value(&model).unwrap()));
+
+    let model = Workflow::new().with_step(|step| {
+        step.with_act(Act::parallel(json!({
+            "acts": [{ "uses": "acts.core.irq", "key": "act1" }]
+        })))
+    });
+    assert!(!validator.is_valid(&serde_json::to_value(&model).unwrap()));
+}
+
+#[tokio::test]
+async fn model_schema_package_params_nested_expr() {
+    let engine = Engine::new().start();
+    let pack = data::Package {
+        id: "pack1".to_string(),
+        version: "0.1.0".to_string(),
+        schema: json!({
+            "type": "object",
+            "properties": {
+                "user": {
+                    "type": "object",
+                    "properties": { "age": { "type": "number" } },
+                    "additionalProperties": false
+                },
+                "tags": { "type": "array", "items": { "type": "number" } }
+            }
+        })
+        .to_string(),
+        run_as: crate::ActRunAs::Func,
+        resources: "[]".to_string(),
+        ..Default::default()
+    };
+    engine.executor().pack().publish(&pack).unwrap();
+
+    let schema = Workflow::json_schema(&engine).unwrap();
+    let validator = jsonschema::validator_for(&schema).unwrap();
+    let model = Workflow::new().with_step(|step| {
+        step.with_act(Act::new().with_uses("pack1").with_params_vars(|vars| {
+            vars.with("user", json!({ "age": "{{ age }}" }))
+                .with("tags", json!([1, "{{ tag }}"]))
+        }))
+    });
+    assert!(validator.is_valid(&serde_json::to_value(&model).unwrap()));
+
+    let model = Workflow::new().with_step(|step| {
+        step.with_act(
+            Act::new()
+                .with_uses("pack1")
+                .with_params_vars(|vars| vars.with("user", json!({ "age": "abc" }))),
+        )
+    });
+    assert!(!validator.is_valid(&serde_json::to_value(&model).unwrap()));
+}
+
+#[tokio::test]
+async fn model_schema_yaml_unknown_property() {
+    let engine = Engine::new().start();
+    let schema = Workflow::json_schema(&engine).unwrap();
+    let validator = jsonschema::validator_for(&schema).unwrap();
+
+    let text = r#"
+    id: m1
+    steps:
+      - name: step1
+        actz:
+          - uses: acts.core.irq
+    "#;
+    let value: serde_json::Value = serde_yaml::from_str(text).unwrap();
+    assert!(!validator.is_valid(&value));
+}
+
+#[tokio::test]
+async fn model_schema_check_ok() {
+    let engine = Engine::new().start();
+    let model = Workflow::new()
+        .with_id("m1")
+        .with_step(|step| step.with_act(Act::irq(|act| act.with_key("act1"))));
+    assert!(model.check_schema(&engine).is_ok());
+}
+
+#[tokio::test]
+async fn model_schema_check_error() {
+    let engine = Engine::new().start();
+    let mut model = Workflow::new().with_id("m1");
+    model.steps.push(
+        Step::new()
+            .with_id("step1")
+            .with_timeout(|t| t.with_on("abc")),
+    );
+    let result = model.check_schema(&engine);
+    let Err(ActError::Model(err)) = result else {
+        panic!("expect model error");
+    };
+    assert!(err.message.contains("/steps/0/timeout/0/on"));
+}
+
+#[tokio::test]
+async fn model_schema_check_yml_unknown_property() {
+    let engine = Engine::new().start();
+    let text = r#"
+    id: m1
+    steps:
+      - id: step1
+        actz:
+          - uses: acts.core.irq
+    "#;
+    let model = Workflow::from_yml(text).unwrap();
+    let Err(ActError::Model(err)) = model.check_schema(&engine) else {
+        panic!("expect model error");
+    };
+    assert!(err.message.contains("actz"));
+    assert!(err.message.contains("/steps/0"));
+}
+
+#[tokio::test]
+async fn model_schema_check_yml_extension_keys() {
+    let engine = Engine::new().start();
+    let text = r#"
+    id: m1
+    x-defaults: &defaults
+      name: step
+      tag: tag1
+    steps:
+      - id: step1
+        <<: *defaults
+        x-owner: team1
+        acts:
+          - uses: acts.core.irq
+            key: act1
+            params:
+              x-any: 1
+    "#;
+    let model = Workflow::from_yml(text).unwrap();
+    assert!(model.check_schema(&engine).is_ok());
+}
+
+#[tokio::test]
+async fn model_schema_check_package_params() {
+    let engine = Engine::new().start();
+    let text = r#"
+    id: m1
+    steps:
+      - id: step1
+        acts:
+          - uses: acts.core.subflow
+            params:
+              to: 10
+    "#;
+    let model = Workflow::from_yml(text).unwrap();
+    let Err(ActError::Model(err)) = model.check_schema(&engine) else {
+        panic!("expect model error");
+    };
+    assert!(err.message.contains("/steps/0/acts/0/params/to"));
+}
+
+#[tokio::test]
+async fn model_schema_defs_fields() {
+    let engine = Engine::new().start();
+    let schema = Workflow::json_schema(&engine).unwrap();
+
+    // every serialized field of the model structs is declared in the schema
+    let defs = [
+        (
+            "workflow",
+            serde_json::to_value(Workflow::default()).unwrap(),
+        ),
+        ("step", serde_json::to_value(Step::default()).unwrap()),
+        ("branch", serde_json::to_value(Branch::default()).unwrap()),
+        ("act", serde_json::to_value(Act::default()).unwrap()),
+        ("catch", serde_json::to_value(Catch::default()).unwrap()),
+        ("timeout", serde_json::to_value(Timeout::default()).unwrap()),
+        ("sla", serde_json::to_value(Sla::default()).unwrap()),
+        (
+            "escalation",
+            serde_json::to_value(Escalation::default()).unwrap(),
+        ),
+        (
+            "assignment",
+            serde_json::to_value(Assignment::default()).unwrap(),
+        ),
+        ("loop", serde_json::to_value(Loop::default()).unwrap()),
+        (
+            "use_template",
+            serde_json::to_value(UseTemplate::default()).unwrap(),
+        ),
+    ];
+    for (def, value) in defs {
+        let properties = schema["$defs"][def]["properties"].as_object().unwrap();
+        for key in value.as_object().unwrap().keys() {
+            assert!(
+                properties.contains_key(key),
+                "the field '{key}' of '{def}' is not in the schema"
+            );
+        }
+        for key in properties.keys() {
+            assert!(
+                value.get(key).is_some(),
+                "the schema property '{key}' of '{def}' is not in the model"
+            );
+        }
+    }
+}
@@ -120,8 +120,68 @@ fn model_step_acts() {
     assert_eq!(step.acts.len(), 2);
 }
 
+#[test]
+fn model_step_yml_loop() {
+    let text = r#"
+    name: workflow
+    id: m1
+    steps:
+        - id: step1
+          loop:
+            while: $index < 3
+            max: 10
+        - id: step2
+          for_each: '["a", "b"]'
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    let step1 = m.steps.first().unwrap();
+    let r#loop = step1.r#loop.as_ref().unwrap();
+    assert_eq!(r#loop.r#while.as_deref(), Some("$index < 3"));
+    assert_eq!(r#loop.max(), 10);
+    assert!(step1.is_loop());
+
+    let step2 = m.steps.get(1).unwrap();
+    assert_eq!(step2.for_each.as_deref(), Some(r#"["a", "b"]"#));
+    assert!(step2.is_loop());
+}
+
+#[test]
+fn model_step_loop() {
+    let step = Step::new().with_loop(|l| l.with_until("done").with_max(3));
+    let r#loop = step.r#loop.unwrap();
+    assert_eq!(r#loop.until.as_deref(), Some("done"));
+    assert_eq!(r#loop.max(), 3);
+}
+
 // #[test]
 // fn model_step_uses() {
 //     let step = Step::new().with_uses("p1");
 //     assert_eq!(step.uses.unwrap(), "p1");
 // }
+
+#[test]
+fn model_step_yml_compensate() {
+    let text = r#"
+    name: workflow
+    id: m1
+    steps:
+        - id: step1
+          compensate:
+            - uses: acts.core.msg
+              key: undo_step1
+          acts:
+            - uses: acts.core.irq
+              key: act1
+              compensate:
+                - uses: acts.core.irq
+                  key: undo_act1
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    let step1 = m.steps.first().unwrap();
+    assert_eq!(step1.compensate.len(), 1);
+    assert_eq!(step1.compensate[0].key, "undo_step1");
+
+    let act1 = step1.acts.first().unwrap();
+    assert_eq!(act1.compensate.len(), 1);
+    assert_eq!(act1.compensate[0].key, "undo_act1");
+}
@@ -63,6 +63,26 @@ fn model_timeout_parse_days() {
     assert_eq!(timeout.as_secs(), 100 * 60 * 60 * 24);
 }
 
+#[test]
+fn model_timeout_parse_business_units() {
+    let timeout = TimeoutLimit::parse("3bd").unwrap();
+
+    assert_eq!(timeout.value, 3);
+    assert_eq!(timeout.unit, TimeoutUnit::BusinessDay);
+    assert!(timeout.is_business());
+    assert_eq!(timeout.to_string(), "3bd");
+
+    let timeout = TimeoutLimit::parse("4bh").unwrap();
+
+    assert_eq!(timeout.value, 4);
+    assert_eq!(timeout.unit, TimeoutUnit::BusinessHour);
+    assert_eq!(timeout.as_secs(), 4 * 60 * 60);
+    assert_eq!(timeout.to_string(), "4bh");
+
+    let timeout = TimeoutLimit::parse("4h").unwrap();
+    assert!(!timeout.is_business());
+}
+
 #[test]
 fn model_timeout_parse_error() {
     let timeout = TimeoutLimit::parse("");
@@ -77,6 +97,9 @@ fn model_timeout_parse_error() {
 
     let timeout = TimeoutLimit::parse("100");
     assert!(timeout.is_err());
+
+    let timeout = TimeoutLimit::parse("1b");
+    assert!(timeout.is_err());
 }
 
 #[test]
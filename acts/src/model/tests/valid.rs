@@ -1,4 +1,5 @@
 use crate::{Act, Workflow};
+use serde_json::json;
 
 #[test]
 fn model_valid_step_id() {
@@ -42,3 +43,76 @@ fn model_valid_same_tag() {
 //     });
 //     assert_eq!(m.valid().is_err(), true);
 // }
+
+#[test]
+fn model_valid_switch_ok() {
+    let m = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_switch("v")
+            .with_branch(|b| b.with_id("b1").with_case("a"))
+            .with_branch(|b| b.with_id("b2").with_case("b"))
+            .with_branch(|b| b.with_id("b3").with_default(true))
+    });
+    assert!(m.valid().is_ok());
+}
+
+#[test]
+fn model_valid_switch_dup_case() {
+    let m = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_switch("v")
+            .with_branch(|b| b.with_id("b1").with_case(1))
+            .with_branch(|b| b.with_id("b2").with_case(1))
+    });
+    assert!(m.valid().is_err());
+}
+
+#[test]
+fn model_valid_switch_multi_default() {
+    let m = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_switch("v")
+            .with_branch(|b| b.with_id("b1").with_default(true))
+            .with_branch(|b| b.with_id("b2").with_default(true))
+    });
+    assert!(m.valid().is_err());
+}
+
+#[test]
+fn model_valid_switch_with_if() {
+    let m = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_switch("v")
+            .with_branch(|b| b.with_id("b1").with_case(1).with_if("v > 0"))
+    });
+    assert!(m.valid().is_err());
+}
+
+#[test]
+fn model_valid_case_no_switch() {
+    let m = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_branch(|b| b.with_id("b1").with_case(1))
+    });
+    assert!(m.valid().is_err());
+}
+
+#[test]
+fn model_valid_input_schema() {
+    let m = Workflow::new()
+        .with_input_schema(json!({ "type": 5 }))
+        .with_step(|step| step.with_id("step1"));
+    assert!(m.valid().is_err());
+
+    let m = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_input_schema(json!({ "type": "object" }))
+    });
+    assert!(m.valid().is_ok());
+
+    let m = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_input_schema(json!({ "required": 1 }))
+    });
+    assert!(m.valid().is_err());
+}
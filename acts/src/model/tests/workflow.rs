@@ -1,4 +1,4 @@
-use crate::{Act, StmtBuild, Vars, Workflow};
+use crate::{Act, ActError, StmtBuild, Vars, Workflow};
 use serde_json::json;
 
 #[test]
@@ -25,6 +25,125 @@ fn model_workflow_from_json_str() {
     assert_eq!(m.name, "workflow");
 }
 
+#[test]
+fn model_workflow_from_yml_error_location() {
+    let text = r#"
+name: workflow
+id: m1
+steps:
+  - id: step1
+  - id: step2
+    acts: 10
+"#;
+    let Err(ActError::Model(err)) = Workflow::from_yml(text) else {
+        panic!("expect model error");
+    };
+    assert_eq!(err.path, "steps[1].acts");
+    assert_eq!(err.line, Some(7));
+    assert_eq!(err.column, Some(11));
+}
+
+#[test]
+fn model_workflow_from_json_error_location() {
+    let text = r#"{
+    "id": "m1",
+    "steps": [{ "id": 1 }]
+}"#;
+    let Err(ActError::Model(err)) = Workflow::from_json(text) else {
+        panic!("expect model error");
+    };
+    assert_eq!(err.path, "steps[0].id");
+    assert_eq!(err.line, Some(3));
+}
+
+#[test]
+fn model_workflow_dup_id_error_location() {
+    let text = r#"
+id: m1
+steps:
+  - id: step1
+  - id: step2
+    branches:
+      - id: b1
+        steps:
+          - id: step1
+"#;
+    let model = Workflow::from_yml(text).unwrap();
+    let Err(ActError::Model(err)) = model.valid() else {
+        panic!("expect model error");
+    };
+    assert_eq!(err.node.as_deref(), Some("step1"));
+    assert_eq!(err.path, "steps[1].branches[0].steps[0]");
+    assert_eq!(err.line, Some(9));
+    assert_eq!(err.column, Some(13));
+}
+
+#[test]
+fn model_workflow_switch_error_location() {
+    let text = r#"
+id: m1
+steps:
+  - id: step1
+    switch: "{{ a }}"
+    branches:
+      - id: b1
+        case: 1
+      - id: b2
+        case: 1
+"#;
+    let model = Workflow::from_yml(text).unwrap();
+    let Err(ActError::Model(err)) = model.valid() else {
+        panic!("expect model error");
+    };
+    assert_eq!(err.node.as_deref(), Some("step1"));
+    assert_eq!(err.path, "steps[0].branches[1].case");
+    assert_eq!(err.line, Some(10));
+    assert_eq!(err.column, Some(15));
+}
+
+#[test]
+fn model_workflow_sibling_keys_error_location() {
+    let text = r#"
+id: m1
+steps:
+  - id: step1
+    switch: "{{ a }}"
+    branches:
+      - id: b1
+        case: 1
+      - id: b2
+        case: 2
+  - id: step2
+    switch: "{{ a }}"
+    branches:
+      - id: b3
+        case: 1
+      - id: b4
+        case: 1
+"#;
+    let model = Workflow::from_yml(text).unwrap();
+    let Err(ActError::Model(err)) = model.valid() else {
+        panic!("expect model error");
+    };
+    assert_eq!(err.node.as_deref(), Some("step2"));
+    assert_eq!(err.path, "steps[1].branches[1].case");
+    assert_eq!(err.line, Some(17));
+    assert_eq!(err.column, Some(15));
+}
+
+#[test]
+fn model_workflow_error_without_source() {
+    let model = Workflow::new()
+        .with_id("m1")
+        .with_step(|step| step.with_id("step1"))
+        .with_step(|step| step.with_id("step1"));
+    let Err(ActError::Model(err)) = model.valid() else {
+        panic!("expect model error");
+    };
+    assert_eq!(err.path, "steps[1]");
+    assert_eq!(err.line, None);
+}
+
 #[test]
 fn model_workflow_to_yml_str() {
     let model = Workflow::new().with_step(|step| step.with_id("step1"));
@@ -160,3 +279,219 @@ fn model_workflow_on_event() {
         .with_step(|step| step.with_id("step1"));
     assert_eq!(workflow.on.len(), 2);
 }
+
+#[test]
+fn model_workflow_catches_timeout_parse() {
+    let text = r#"
+    name: workflow
+    id: m1
+    steps:
+      - id: step1
+    catches:
+      - on: err1
+        steps:
+          - id: catch1
+    timeout:
+      - on: 5d
+        steps:
+          - id: timeout1
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert_eq!(m.catches.len(), 1);
+    assert_eq!(m.catches[0].on.as_deref(), Some("err1"));
+    assert_eq!(m.timeout.len(), 1);
+    assert_eq!(m.timeout[0].on, "5d");
+    assert_eq!(m.timeout[0].steps[0].id, "timeout1");
+}
+
+#[test]
+fn model_workflow_templates_resolve() {
+    let text = r#"
+    name: workflow
+    id: m1
+    templates:
+      - id: approval
+        name: approval
+        acts:
+          - uses: acts.core.irq
+            key: approve
+            inputs:
+              uid: ${uid}
+              level: ${level}
+              title: level ${level} approval
+    steps:
+      - id: step1
+        name: first approval
+        use_template:
+          id: approval
+          params:
+            uid: u1
+            level: 1
+      - id: step2
+        use_template:
+          id: approval
+          params:
+            uid: u2
+            level: 2
+    "#;
+    let mut m = Workflow::from_yml(text).unwrap();
+    m.resolve_templates(|_| Ok(Workflow::new())).unwrap();
+
+    let step1 = m.step("step1").unwrap();
+    assert!(step1.use_template.is_none());
+    assert_eq!(step1.name, "first approval");
+    let inputs = &step1.acts[0].inputs;
+    assert_eq!(inputs.get::<String>("uid").unwrap(), "u1");
+    assert_eq!(inputs.get::<i32>("level").unwrap(), 1);
+    assert_eq!(inputs.get::<String>("title").unwrap(), "level 1 approval");
+
+    let step2 = m.step("step2").unwrap();
+    assert_eq!(step2.name, "approval");
+    assert_eq!(step2.acts[0].inputs.get::<String>("uid").unwrap(), "u2");
+    assert!(m.valid().is_ok());
+}
+
+#[test]
+fn model_workflow_templates_import() {
+    let mut m = Workflow::new()
+        .with_import("shared")
+        .with_step(|step| step.with_id("step1").with_template(|t| t.with_id("notify")));
+    m.resolve_templates(|id| {
+        assert_eq!(id, "shared");
+        Ok(Workflow::new().with_step(|step| {
+            step.with_id("notify")
+                .with_act(Act::msg(|act| act.with_key("msg1")))
+        }))
+    })
+    .unwrap();
+
+    let step1 = m.step("step1").unwrap();
+    assert_eq!(step1.acts[0].key, "msg1");
+}
+
+#[test]
+fn model_workflow_templates_not_found() {
+    let mut m = Workflow::new()
+        .with_step(|step| step.with_id("step1").with_template(|t| t.with_id("notify")));
+    let ret = m.resolve_templates(|_| Ok(Workflow::new()));
+    assert!(ret.is_err());
+}
+
+#[test]
+fn model_workflow_templates_own_fields_error() {
+    let text = r#"
+    name: workflow
+    id: m1
+    templates:
+      - id: approval
+        acts:
+          - uses: acts.core.irq
+            key: approve
+    steps:
+      - id: step1
+        name: first approval
+        inputs:
+          uid: u1
+        acts:
+          - uses: acts.core.msg
+            key: msg1
+        use_template:
+          id: approval
+    "#;
+    let mut m = Workflow::from_yml(text).unwrap();
+    let ret = m.resolve_templates(|_| Ok(Workflow::new()));
+    let ActError::Model(err) = ret.unwrap_err() else {
+        panic!("expect model error");
+    };
+    assert_eq!(err.node, Some("step1".to_string()));
+    assert!(err.message.contains("step 'step1'"));
+    let (fields, _) = err.message.split_once(", only").unwrap();
+    assert!(fields.contains("'acts'"));
+    assert!(fields.contains("'inputs'"));
+    assert!(!fields.contains("'name'"));
+}
+
+#[test]
+fn model_workflow_templates_circular() {
+    let mut m = Workflow::new()
+        .with_template(|step| {
+            step.with_id("t1")
+                .with_branch(|b| b.with_step(|step| step.with_template(|t| t.with_id("t1"))))
+        })
+        .with_step(|step| step.with_id("step1").with_template(|t| t.with_id("t1")));
+    let ret = m.resolve_templates(|_| Ok(Workflow::new()));
+    assert!(ret.is_err());
+}
+
+#[test]
+fn model_workflow_templates_reuse_nested_ids() {
+    let text = r#"
+    name: workflow
+    id: m1
+    templates:
+      - id: review
+        branches:
+          - id: b1
+            steps:
+              - id: check
+                next: notify
+              - id: skip
+              - id: notify
+          - id: b2
+            needs: [b1]
+            steps:
+              - id: done
+                acts:
+                  - uses: acts.core.irq
+                    id: act1
+                    key: done
+    steps:
+      - id: step1
+        use_template:
+          id: review
+      - id: step2
+        use_template:
+          id: review
+    "#;
+    let mut m = Workflow::from_yml(text).unwrap();
+    m.resolve_templates(|_| Ok(Workflow::new())).unwrap();
+
+    let step1 = m.step("step1").unwrap();
+    assert_eq!(step1.branches[0].id, "step1_b1");
+    assert_eq!(step1.branches[0].steps[0].id, "step1_check");
+    assert_eq!(
+        step1.branches[0].steps[0].next.as_deref(),
+        Some("step1_notify")
+    );
+    assert_eq!(step1.branches[1].needs, vec!["step1_b1"]);
+    assert_eq!(step1.branches[1].steps[0].acts[0].id, "step1_act1");
+
+    let step2 = m.step("step2").unwrap();
+    assert_eq!(step2.branches[0].id, "step2_b1");
+    assert_eq!(step2.branches[1].steps[0].acts[0].id, "step2_act1");
+    assert!(m.valid().is_ok());
+}
+
+#[test]
+fn model_workflow_templates_keep_code() {
+    let mut m = Workflow::new()
+        .with_template(|step| {
+            step.with_id("t1")
+                .with_act(Act::code("let a = `${uid}`; return { a };"))
+                .with_act(Act::msg(|act| {
+                    act.with_key("msg1").with_input("uid", "${uid}")
+                }))
+        })
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_template(|t| t.with_id("t1").with_param("uid", "u1"))
+        });
+    m.resolve_templates(|_| Ok(Workflow::new())).unwrap();
+
+    let step1 = m.step("step1").unwrap();
+    assert_eq!(
+        step1.acts[0].params,
+        json!("let a = `${uid}`; return { a };")
+    );
+    assert_eq!(step1.acts[1].inputs.get::<String>("uid").unwrap(), "u1");
+}
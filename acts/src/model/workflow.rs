@@ -1,6 +1,11 @@
-use crate::{Act, ActError, ModelBase, Result, Step, Vars, scheduler::NodeTree};
+use crate::{
+    Act, ActError, Catch, ModelBase, ModelError, Result, Step, Timeout, Vars,
+    scheduler::{Diagram, NodeTree},
+    utils,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Workflow {
@@ -28,30 +33,82 @@ pub struct Workflow {
     #[serde(default)]
     pub outputs: Vars,
 
+    /// the json schema to validate the inputs when starting the process
+    #[serde(default)]
+    pub input_schema: Option<JsonValue>,
+
     #[serde(default)]
     pub setup: Vec<Act>,
 
     #[serde(default)]
     pub on: Vec<Act>,
 
+    #[serde(default)]
+    pub catches: Vec<Catch>,
+
+    #[serde(default)]
+    pub timeout: Vec<Timeout>,
+
+    /// the imported model ids to use their templates and steps
+    #[serde(default)]
+    pub imports: Vec<String>,
+
+    /// the step templates to use by `use_template`
+    #[serde(default)]
+    pub templates: Vec<Step>,
+
+    /// the calendar name to calculate the business units such as `3bd`
+    #[serde(default)]
+    pub calendar: Option<String>,
+
     #[serde(default)]
     pub ver: i32,
+
+    /// the yaml or json source to locate the model errors
+    #[serde(skip)]
+    pub(crate) source: Option<Arc<str>>,
 }
 
 impl Workflow {
     pub fn from_yml(s: &str) -> Result<Self> {
-        let workflow = serde_yaml::from_str::<Workflow>(s);
+        let workflow =
+            serde_path_to_error::deserialize::<_, Workflow>(serde_yaml::Deserializer::from_str(s));
         match workflow {
-            Ok(v) => Ok(v),
-            Err(e) => Err(ActError::Model(format!("{}", e))),
+            Ok(mut v) => {
+                v.source = Some(Arc::from(s));
+                Ok(v)
+            }
+            Err(e) => {
+                let path = e.path().to_string();
+                let e = e.into_inner();
+                let mut err =
+                    ModelError::new(&e.to_string()).with_path(path.trim_start_matches('.'));
+                if let Some(loc) = e.location() {
+                    err = err.with_location(loc.line(), loc.column());
+                }
+                Err(ActError::Model(err))
+            }
         }
     }
 
     pub fn from_json(s: &str) -> Result<Self> {
-        let workflow = serde_json::from_str::<Workflow>(s);
+        let mut de = serde_json::Deserializer::from_str(s);
+        let workflow = serde_path_to_error::deserialize::<_, Workflow>(&mut de);
         match workflow {
-            Ok(v) => Ok(v),
-            Err(e) => Err(ActError::Model(format!("{}", e))),
+            Ok(mut v) => {
+                // the json is also the yaml to locate the errors
+                v.source = Some(Arc::from(s));
+                Ok(v)
+            }
+            Err(e) => {
+                let path = e.path().to_string();
+                let e = e.into_inner();
+                Err(ActError::Model(
+                    ModelError::new(&e.to_string())
+                        .with_path(path.trim_start_matches('.'))
+                        .with_location(e.line(), e.column()),
+                ))
+            }
         }
     }
 
@@ -85,6 +142,25 @@ impl Workflow {
         root.tree_output()
     }
 
+    /// render the workflow as a mermaid flowchart
+    pub fn to_mermaid(&self) -> Result<String> {
+        Ok(self.diagram()?.to_mermaid())
+    }
+
+    /// render the workflow as a graphviz dot digraph
+    pub fn to_dot(&self) -> Result<String> {
+        Ok(self.diagram()?.to_dot())
+    }
+
+    fn diagram(&self) -> Result<Diagram> {
+        let mut tree = NodeTree::new();
+        tree.load(self)?;
+        if let Some(err) = tree.error {
+            return Err(err);
+        }
+        Ok(Diagram::new(&tree))
+    }
+
     pub fn step(&self, id: &str) -> Option<&Step> {
         match self.steps.iter().find(|s| s.id == id) {
             Some(s) => Some(s),
@@ -102,22 +178,42 @@ impl Workflow {
     pub fn to_yml(&self) -> Result<String> {
         match serde_yaml::to_string(self) {
             Ok(s) => Ok(s),
-            Err(e) => Err(ActError::Model(e.to_string())),
+            Err(e) => Err(ActError::Model(e.to_string().into())),
         }
     }
 
     pub fn to_json(&self) -> Result<String> {
         match serde_json::to_string(self) {
             Ok(s) => Ok(s),
-            Err(e) => Err(ActError::Model(e.to_string())),
+            Err(e) => Err(ActError::Model(e.to_string().into())),
         }
     }
 
     pub fn valid(&self) -> Result<()> {
+        if let Some(schema) = &self.input_schema {
+            jsonschema::validator_for(schema).map_err(|err| {
+                ActError::Model(
+                    format!("invalid input_schema of workflow '{}': {err}", self.id).into(),
+                )
+            })?;
+        }
         let mut root = NodeTree::new();
         root.load(self)?;
+        if let Some(err) = root.error {
+            return Err(err);
+        }
         Ok(())
     }
+
+    /// validate the inputs by the input_schema
+    pub fn check_inputs(&self) -> Result<()> {
+        match &self.input_schema {
+            Some(schema) => {
+                utils::check_inputs(&format!("workflow '{}'", self.id), schema, &self.inputs)
+            }
+            None => Ok(()),
+        }
+    }
 }
 
 impl ModelBase for Workflow {
@@ -157,6 +253,16 @@ impl Workflow {
         self
     }
 
+    pub fn with_calendar(mut self, name: &str) -> Self {
+        self.calendar = Some(name.to_string());
+        self
+    }
+
+    pub fn with_input_schema(mut self, schema: JsonValue) -> Self {
+        self.input_schema = Some(schema);
+        self
+    }
+
     pub fn with_output(mut self, name: &str, value: JsonValue) -> Self {
         self.outputs.insert(name.to_string(), value);
         self
@@ -179,4 +285,27 @@ impl Workflow {
         self.on.push(act);
         self
     }
+
+    pub fn with_catch(mut self, build: fn(Catch) -> Catch) -> Self {
+        let catch = Catch::default();
+        self.catches.push(build(catch));
+        self
+    }
+
+    pub fn with_timeout(mut self, build: fn(Timeout) -> Timeout) -> Self {
+        let timeout = Timeout::default();
+        self.timeout.push(build(timeout));
+        self
+    }
+
+    pub fn with_import(mut self, id: &str) -> Self {
+        self.imports.push(id.to_string());
+        self
+    }
+
+    pub fn with_template(mut self, build: fn(Step) -> Step) -> Self {
+        let step = Step::default();
+        self.templates.push(build(step));
+        self
+    }
 }
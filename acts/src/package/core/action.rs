@@ -24,7 +24,24 @@ impl ActPackage for ActionPackage {
             version: "0.1.0",
             icon: "icon-action",
             doc: "",
-            schema: json!({}),
+            schema: json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "description": "the action to do with the parent step",
+                        "enum": [
+                            "next", "submit", "back", "cancel", "abort", "skip",
+                            "error", "push", "remove", "set_process_vars"
+                        ]
+                    },
+                    "options": {
+                        "type": "object",
+                        "description": "the action options such as ecode and message for error"
+                    }
+                },
+                "required": ["action"]
+            }),
             run_as: ActRunAs::Func,
             resources: vec![],
             catalog: ActPackageCatalog::Core,
@@ -54,7 +71,7 @@ inventory::submit!(ActPackageRegister::new::<ActionPackage>());
 
 #[cfg(test)]
 mod tests {
-    use crate::ActPackage;
+    use crate::{ActError, ActPackage, package::ActPackageRegister};
 
     #[test]
     fn pack_action_parse() {
@@ -67,6 +84,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn pack_action_invalid_params() {
+        let register = ActPackageRegister::new::<super::ActionPackage>();
+        let params = serde_json::json!({ "action": "finish" });
+        let Err(ActError::Package(message)) = (register.create)(params) else {
+            panic!("expect package error");
+        };
+        assert!(message.contains("'acts.core.action'"));
+        assert!(message.contains("at '/action'"));
+    }
+
     #[cfg(test)]
     fn pack_action(action: &str) {
         let params = format!(
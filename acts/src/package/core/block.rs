@@ -40,7 +40,22 @@ impl ActPackage for BlockPackage {
             version: "0.1.0",
             icon: "icon-block",
             doc: "",
-            schema: json!({}),
+            schema: json!({
+                "type": "object",
+                "properties": {
+                    "mode": {
+                        "type": "string",
+                        "description": "run the acts one by one or at the same time",
+                        "enum": ["sequence", "parallel"]
+                    },
+                    "acts": {
+                        "type": "array",
+                        "description": "the acts to run",
+                        "items": { "type": "object" }
+                    }
+                },
+                "required": ["mode", "acts"]
+            }),
             run_as: ActRunAs::Func,
             resources: vec![],
             catalog: ActPackageCatalog::Core,
@@ -65,7 +80,7 @@ inventory::submit!(ActPackageRegister::new::<BlockPackage>());
 
 #[cfg(test)]
 mod tests {
-    use crate::ActPackage;
+    use crate::{ActError, ActPackage, package::ActPackageRegister};
 
     #[test]
     fn pack_block_sequence_parse() {
@@ -96,4 +111,15 @@ mod tests {
         serde_json::from_value::<super::BlockPackage>(value.clone()).unwrap();
         jsonschema::validate(&meta.schema, &value).unwrap()
     }
+
+    #[test]
+    fn pack_block_invalid_params() {
+        let register = ActPackageRegister::new::<super::BlockPackage>();
+        let params = serde_json::json!({ "mode": "all", "acts": [] });
+        let Err(ActError::Package(message)) = (register.create)(params) else {
+            panic!("expect package error");
+        };
+        assert!(message.contains("'acts.core.block'"));
+        assert!(message.contains("at '/mode'"));
+    }
 }
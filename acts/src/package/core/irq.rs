@@ -15,7 +15,9 @@ impl ActPackage for IrqPackage {
             version: "0.1.0",
             icon: "icon-irq",
             doc: "",
-            schema: json!({}),
+            schema: json!({
+                "description": "the params are sent to the client with the irq message"
+            }),
             run_as: ActRunAs::Irq,
             resources: vec![],
             catalog: ActPackageCatalog::Core,
@@ -15,7 +15,9 @@ impl ActPackage for MsgPackage {
             version: "0.1.0",
             icon: "icon-msg",
             doc: "",
-            schema: json!({}),
+            schema: json!({
+                "description": "the params are sent to the client with the msg message"
+            }),
             run_as: ActRunAs::Msg,
             resources: vec![],
             catalog: ActPackageCatalog::Core,
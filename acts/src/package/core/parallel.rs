@@ -1,4 +1,5 @@
 use super::super::core::{BlockPackage, RunningMode};
+use crate::scheduler::Task;
 use crate::{
     Act, Context, Result, Vars,
     package::{
@@ -8,11 +9,50 @@ use crate::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::{Value as JsonValue, json};
+use std::sync::Arc;
+
+/// the rule to complete the parallel act
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompleteWhen {
+    /// all of the items are completed
+    #[default]
+    All,
+    /// any of the items is completed
+    Any,
+    /// the completed items reach the count
+    Count(usize),
+    /// the completed items reach the ratio of all items
+    Ratio(f64),
+    /// the expression over the collected results is true
+    Expr(String),
+}
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ParallelPackage {
     r#in: Vec<JsonValue>,
     acts: Vec<Act>,
+
+    /// the var names to collect from each item
+    #[serde(default)]
+    collect: Vec<String>,
+
+    /// the expression to reduce the collected results
+    #[serde(default)]
+    reduce: Option<String>,
+
+    /// the var name to expose the collected results and the reduced value to the step
+    /// they are only kept in the act when it is not set
+    #[serde(default)]
+    output: Option<String>,
+
+    /// the rule to complete the act, the remaining items will be cancelled
+    #[serde(default)]
+    complete_when: CompleteWhen,
+
+    /// the max running items, the others are pending until a running one is completed
+    #[serde(default)]
+    max_concurrency: Option<usize>,
 }
 
 impl ActPackage for ParallelPackage {
@@ -23,7 +63,65 @@ impl ActPackage for ParallelPackage {
             version: "0.1.0",
             icon: "icon-parallel",
             doc: "",
-            schema: json!({}),
+            schema: json!({
+                "type": "object",
+                "properties": {
+                    "in": {
+                        "type": "array",
+                        "description": "the items to run the acts for each one at the same time"
+                    },
+                    "acts": {
+                        "type": "array",
+                        "description": "the acts to run for each item",
+                        "items": { "type": "object" }
+                    },
+                    "collect": {
+                        "type": "array",
+                        "description": "the var names to collect from each item",
+                        "items": { "type": "string" }
+                    },
+                    "reduce": {
+                        "type": ["string", "null"],
+                        "description": "the expression to reduce the collected results"
+                    },
+                    "output": {
+                        "type": ["string", "null"],
+                        "description": "the var name to expose the collected results and the reduced value"
+                    },
+                    "complete_when": {
+                        "description": "the rule to complete the act",
+                        "oneOf": [
+                            { "type": "string", "enum": ["all", "any"] },
+                            {
+                                "type": "object",
+                                "properties": { "count": { "type": "integer", "minimum": 1 } },
+                                "required": ["count"],
+                                "additionalProperties": false
+                            },
+                            {
+                                "type": "object",
+                                "properties": {
+                                    "ratio": { "type": "number", "minimum": 0, "maximum": 1 }
+                                },
+                                "required": ["ratio"],
+                                "additionalProperties": false
+                            },
+                            {
+                                "type": "object",
+                                "properties": { "expr": { "type": "string" } },
+                                "required": ["expr"],
+                                "additionalProperties": false
+                            }
+                        ]
+                    },
+                    "max_concurrency": {
+                        "type": ["integer", "null"],
+                        "description": "the max running items",
+                        "minimum": 1
+                    }
+                },
+                "required": ["in", "acts"]
+            }),
             run_as: ActRunAs::Func,
             resources: vec![],
             catalog: ActPackageCatalog::Core,
@@ -49,16 +147,93 @@ impl ActPackageFn for ParallelPackage {
                 ..Default::default()
             });
         }
+        if let Some(max) = self.max_concurrency {
+            ctx.task()
+                .set_data(&Vars::new().with(consts::ACT_MAX_CONCURRENCY, max));
+        }
         ctx.build_acts(&acts, false)?;
         Ok(None)
     }
+
+    fn can_complete(&self, ctx: &Context) -> Result<bool> {
+        let blocks = ctx.task().children();
+        let total = blocks.len();
+        let count = blocks.iter().filter(|t| t.state().is_success()).count();
+        let ret = match &self.complete_when {
+            CompleteWhen::All => count == total,
+            CompleteWhen::Any => count > 0,
+            CompleteWhen::Count(n) => count >= *n,
+            CompleteWhen::Ratio(ratio) => total > 0 && count as f64 / total as f64 >= *ratio,
+            CompleteWhen::Expr(expr) => {
+                let vars = Vars::new().with(consts::ACT_COLLECT_RESULTS, self.results(ctx));
+                ctx.task().set_data(&vars);
+                ctx.eval::<bool>(expr)?
+            }
+        };
+
+        Ok(ret)
+    }
+
+    fn complete(&self, ctx: &Context) -> Result<Option<Vars>> {
+        if self.collect.is_empty() && self.reduce.is_none() {
+            return Ok(None);
+        }
+
+        let task = ctx.task();
+        let results = self.results(ctx);
+        let mut vars = Vars::new().with(consts::ACT_COLLECT_RESULTS, &results);
+        if let Some(expr) = &self.reduce {
+            // set the results to make it available in the reduce expression
+            task.set_data(&vars);
+            let value = ctx.eval::<JsonValue>(expr)?;
+            vars.set(consts::ACT_REDUCE_RESULT, value);
+        }
+
+        // keep the results in the act and only expose the output name to avoid overwriting the step vars
+        task.set_data(&vars);
+        Ok(self
+            .output
+            .as_ref()
+            .map(|name| Vars::new().with(name, vars)))
+    }
+}
+
+impl ParallelPackage {
+    /// collect the vars of each item ordered by the item index
+    fn results(&self, ctx: &Context) -> Vec<JsonValue> {
+        let mut blocks = ctx.task().children();
+        blocks.sort_by_key(|t| t.options().get::<usize>(consts::ACT_INDEX));
+
+        blocks
+            .iter()
+            .map(|block| {
+                let data = collect_data(block);
+                let mut item = Vars::new();
+                for key in self.collect.iter() {
+                    item.set(key, data.get_value(key).cloned().unwrap_or(JsonValue::Null));
+                }
+                JsonValue::from(item)
+            })
+            .collect::<Vec<_>>()
+    }
+}
+
+/// merge the data of the task and its descendant tasks in running order
+fn collect_data(task: &Arc<Task>) -> Vars {
+    let mut data = task.data();
+    for child in task.children() {
+        for (ref key, value) in &collect_data(&child) {
+            data.set(key, value.clone());
+        }
+    }
+    data
 }
 
 inventory::submit!(ActPackageRegister::new::<ParallelPackage>());
 
 #[cfg(test)]
 mod tests {
-    use crate::ActPackage;
+    use crate::{ActError, ActPackage, package::ActPackageRegister};
 
     #[test]
     fn pack_parallel_parse() {
@@ -77,4 +252,23 @@ mod tests {
         let meta = super::ParallelPackage::meta();
         jsonschema::validate(&meta.schema, &value).unwrap()
     }
+
+    #[test]
+    fn pack_parallel_invalid_params() {
+        let register = ActPackageRegister::new::<super::ParallelPackage>();
+        let params = serde_json::json!({ "acts": [], "max_concurrency": 0 });
+        let Err(ActError::Package(message)) = (register.create)(params) else {
+            panic!("expect package error");
+        };
+        assert!(message.contains("'acts.core.parallel'"));
+        assert!(message.contains(r#""in" is a required property"#));
+        assert!(message.contains("at '/max_concurrency'"));
+    }
+
+    #[test]
+    fn pack_parallel_invalid_complete_when() {
+        let params = serde_json::json!({ "in": [1], "acts": [], "complete_when": { "ratio": 2 } });
+        let meta = super::ParallelPackage::meta();
+        assert!(jsonschema::validate(&meta.schema, &params).is_err());
+    }
 }
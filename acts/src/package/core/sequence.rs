@@ -23,7 +23,21 @@ impl ActPackage for SequencePackage {
             version: "0.1.0",
             icon: "icon-sequence",
             doc: "",
-            schema: json!({}),
+            schema: json!({
+                "type": "object",
+                "properties": {
+                    "in": {
+                        "type": "array",
+                        "description": "the items to run the acts for each one by one"
+                    },
+                    "acts": {
+                        "type": "array",
+                        "description": "the acts to run for each item",
+                        "items": { "type": "object" }
+                    }
+                },
+                "required": ["in", "acts"]
+            }),
             run_as: ActRunAs::Func,
             resources: vec![],
             catalog: ActPackageCatalog::Core,
@@ -59,7 +73,7 @@ inventory::submit!(ActPackageRegister::new::<SequencePackage>());
 
 #[cfg(test)]
 mod tests {
-    use crate::ActPackage;
+    use crate::{ActError, ActPackage, package::ActPackageRegister};
 
     #[test]
     fn pack_sequence_parse() {
@@ -79,4 +93,15 @@ mod tests {
         serde_json::from_value::<super::SequencePackage>(value.clone()).unwrap();
         jsonschema::validate(&meta.schema, &value).unwrap()
     }
+
+    #[test]
+    fn pack_sequence_invalid_params() {
+        let register = ActPackageRegister::new::<super::SequencePackage>();
+        let params = serde_json::json!({ "in": "u1", "acts": [] });
+        let Err(ActError::Package(message)) = (register.create)(params) else {
+            panic!("expect package error");
+        };
+        assert!(message.contains("'acts.core.sequence'"));
+        assert!(message.contains("at '/in'"));
+    }
 }
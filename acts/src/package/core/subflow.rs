@@ -27,9 +27,16 @@ impl ActPackage for SubflowPackage {
             schema: json!({
                 "type": "object",
                 "properties": {
-                    "to": { "type": "string" },
-                    "options": { "type": "object"}
-                }
+                    "to": {
+                        "type": "string",
+                        "description": "the model id of the subflow"
+                    },
+                    "options": {
+                        "type": "object",
+                        "description": "the inputs to start the subflow"
+                    }
+                },
+                "required": ["to"]
             }),
             run_as: ActRunAs::Func,
             resources: vec![],
@@ -58,7 +65,7 @@ inventory::submit!(ActPackageRegister::new::<SubflowPackage>());
 
 #[cfg(test)]
 mod tests {
-    use crate::ActPackage;
+    use crate::{ActError, ActPackage, package::ActPackageRegister};
 
     #[test]
     fn pack_subflow_parse() {
@@ -74,4 +81,15 @@ mod tests {
         serde_json::from_value::<super::SubflowPackage>(value.clone()).unwrap();
         jsonschema::validate(&meta.schema, &value).unwrap()
     }
+
+    #[test]
+    fn pack_subflow_invalid_params() {
+        let register = ActPackageRegister::new::<super::SubflowPackage>();
+        let params = serde_json::json!({ "options": { "a": 1 } });
+        let Err(ActError::Package(message)) = (register.create)(params) else {
+            panic!("expect package error");
+        };
+        assert!(message.contains("'acts.core.subflow'"));
+        assert!(message.contains(r#""to" is a required property"#));
+    }
 }
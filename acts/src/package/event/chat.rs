@@ -20,7 +20,10 @@ impl ActPackage for ManualEventPackage {
             version: "0.1.0",
             icon: "icon-chat",
             doc: "",
-            schema: json!({}),
+            schema: json!({
+                "type": ["string", "null"],
+                "description": "the chat message passed to the workflow as the data var"
+            }),
             run_as: ActRunAs::Func,
             resources: vec![],
             catalog: ActPackageCatalog::Event,
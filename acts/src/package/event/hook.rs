@@ -21,7 +21,10 @@ impl ActPackage for HookEventPackage {
             version: "0.1.0",
             icon: "icon-hook",
             doc: "",
-            schema: json!({}),
+            schema: json!({
+                "type": ["object", "null"],
+                "description": "the inputs to start the workflow"
+            }),
             run_as: ActRunAs::Func,
             resources: vec![],
             catalog: ActPackageCatalog::Event,
@@ -55,7 +58,7 @@ impl ActPackageFn for HookEventPackage {
         });
 
         let params = self.0.clone().unwrap_or_default();
-        rt.start(&workflow, &params).unwrap();
+        rt.start(&workflow, &params)?;
         let ret = s.recv().await;
         Ok(Some(ret))
     }
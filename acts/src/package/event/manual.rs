@@ -20,7 +20,10 @@ impl ActPackage for ManualEventPackage {
             version: "0.1.0",
             icon: "icon-manual",
             doc: "",
-            schema: json!({}),
+            schema: json!({
+                "type": ["object", "null"],
+                "description": "the inputs to start the workflow"
+            }),
             run_as: ActRunAs::Func,
             resources: vec![],
             catalog: ActPackageCatalog::Event,
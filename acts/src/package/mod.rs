@@ -6,8 +6,9 @@ pub mod transform;
 mod tests;
 
 use crate::{
-    Engine, Result, Vars, data,
+    ActError, Engine, Result, Vars, data,
     scheduler::{Context, Runtime},
+    utils,
 };
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use std::{
@@ -35,6 +36,17 @@ pub trait ActPackageFn: Send + Sync {
         Ok(None)
     }
 
+    /// check if the act can be completed before all the child tasks are completed
+    /// the remaining child tasks will be cancelled
+    fn can_complete(&self, _ctx: &Context) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// completing with task context when all the child tasks are completed
+    fn complete(&self, _ctx: &Context) -> Result<Option<Vars>> {
+        Ok(None)
+    }
+
     /// start with non-context, such as workflow event
     async fn start(&self, _rt: &Arc<Runtime>, _options: &Vars) -> Result<Option<Vars>> {
         Ok(None)
@@ -161,7 +173,7 @@ impl ActPackageRegister {
             create: (|params: serde_json::Value| {
                 let meta = T::meta();
 
-                jsonschema::validate(&meta.schema, &params)?;
+                check_params(&meta, &params)?;
                 let ret = serde_json::from_value::<T>(params)?;
                 Ok(Box::new(ret) as Box<dyn ActPackageFn>)
             }),
@@ -169,6 +181,17 @@ impl ActPackageRegister {
     }
 }
 
+/// check the params by the package schema
+/// all of the errors are joined to a descriptive package error
+fn check_params(meta: &ActPackageMeta, params: &serde_json::Value) -> Result<()> {
+    utils::check_schema(&meta.schema, params).map_err(|err| match err {
+        ActError::Model(err) => {
+            ActError::Package(format!("invalid params of package '{}': {err}", meta.name))
+        }
+        err => err,
+    })
+}
+
 impl Default for Package {
     fn default() -> Self {
         Self::new()
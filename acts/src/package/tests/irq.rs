@@ -861,6 +861,99 @@ async fn pack_irq_do_action_rets() {
     );
 }
 
+#[tokio::test]
+async fn pack_irq_do_action_outputs_default() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::irq(|act| {
+                act.with_key("fn1")
+                    .with_output("a", json!({ "$type": "string", "$required": true }))
+                    .with_output("b", json!({ "$type": "number", "$default": 5 }))
+            })
+            .with_id("fn1"),
+        )
+    });
+
+    let pid = utils::longid();
+    let (proc, scher, emitter, tx, _) = create_proc_signal::<()>(&mut workflow, &pid);
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.state() == MessageState::Created && e.is_irq() {
+            let options = Vars::new().with("a", "abc");
+            let action = Action::new(&e.pid, &e.tid, EventAction::Next, &options);
+            s.do_action(&action).unwrap();
+        }
+    });
+
+    scher.launch(&proc);
+    tx.recv().await;
+    let fn1 = proc.task_by_nid("fn1").first().unwrap().clone();
+    assert_eq!(fn1.state(), TaskState::Completed);
+    assert_eq!(fn1.data().get::<String>("a").unwrap(), "abc");
+    assert_eq!(fn1.data().get::<i32>("b").unwrap(), 5);
+    assert_eq!(fn1.outputs().get::<i32>("b").unwrap(), 5);
+}
+
+#[tokio::test]
+async fn pack_irq_do_action_outputs_type_error() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::irq(|act| {
+                act.with_key("fn1")
+                    .with_output("a", json!({ "$type": "number", "$required": true }))
+            })
+            .with_id("fn1"),
+        )
+    });
+
+    let pid = utils::longid();
+    let (proc, scher, emitter, tx, _) = create_proc_signal::<()>(&mut workflow, &pid);
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.state() == MessageState::Created && e.is_irq() {
+            let options = Vars::new().with("a", "abc");
+            let action = Action::new(&e.pid, &e.tid, EventAction::Next, &options);
+            s.do_action(&action).unwrap();
+        }
+    });
+
+    scher.launch(&proc);
+    tx.recv().await;
+    assert_eq!(proc.state(), TaskState::Error);
+    let err = proc.task_by_nid("fn1").first().unwrap().err().unwrap();
+    assert_eq!(err.ecode, consts::ACT_ERR_OUTPUTS);
+}
+
+#[tokio::test]
+async fn pack_irq_do_action_outputs_required_error() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::irq(|act| {
+                act.with_key("fn1")
+                    .with_output("a", json!({ "$type": "string", "$required": true }))
+            })
+            .with_id("fn1"),
+        )
+    });
+
+    let pid = utils::longid();
+    let (proc, scher, emitter, tx, _) = create_proc_signal::<()>(&mut workflow, &pid);
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.state() == MessageState::Created && e.is_irq() {
+            let action = Action::new(&e.pid, &e.tid, EventAction::Next, &Vars::new());
+            s.do_action(&action).unwrap();
+        }
+    });
+
+    scher.launch(&proc);
+    tx.recv().await;
+    assert_eq!(proc.state(), TaskState::Error);
+    let err = proc.task_by_nid("fn1").first().unwrap().err().unwrap();
+    assert_eq!(err.ecode, consts::ACT_ERR_OUTPUTS);
+    assert!(err.message.contains("'a' is required"));
+}
+
 #[tokio::test]
 async fn pack_irq_do_action_no_output() {
     let mut workflow = Workflow::new().with_step(|step| {
@@ -1,6 +1,7 @@
 use crate::{
-    Act, MessageState, StmtBuild, Vars, Workflow,
+    Act, MessageState, Step, StmtBuild, Vars, Workflow,
     event::EventAction,
+    export::Executor,
     scheduler::TaskState,
     utils::{
         self, consts,
@@ -8,6 +9,10 @@ use crate::{
     },
 };
 use serde_json::json;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
 
 #[tokio::test]
 async fn pack_parallel_setup_list() {
@@ -152,3 +157,440 @@ async fn pack_parallel_setup_code() {
         "u3"
     );
 }
+
+#[tokio::test]
+async fn pack_parallel_collect() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::parallel(json!({
+                "in": ["u1", "u2", "u3"],
+                "collect": ["approved"],
+                "acts": vec![
+                    Act::irq(|act| act.with_key("act1").with_id("act1"))
+                ]
+            }))
+            .with_id("p1"),
+        )
+    });
+
+    workflow.print();
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<(String, String, String)>>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            let options = e.inputs.get::<Vars>(consts::ACT_OPTIONS_KEY).unwrap();
+            let value = options.get::<String>(consts::ACT_VALUE).unwrap();
+            rx.update(|data| data.push((e.pid.clone(), e.tid.clone(), value.clone())));
+
+            // complete the acts in reverse order to check the results order
+            let items = rx.data();
+            if items.len() == 3 {
+                for (pid, tid, value) in items.iter().rev() {
+                    e.do_action(
+                        pid,
+                        tid,
+                        EventAction::Next,
+                        &Vars::new().with("approved", value != "u2"),
+                    )
+                    .unwrap();
+                }
+            }
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    let task = proc.task_by_nid("p1").first().unwrap().clone();
+    assert_eq!(task.state(), TaskState::Completed);
+    // the package is created once in run and reused by the child completions
+    assert!(task.package().is_some());
+    assert_eq!(
+        task.data()
+            .get::<serde_json::Value>(consts::ACT_COLLECT_RESULTS),
+        Some(json!([
+            { "approved": true },
+            { "approved": false },
+            { "approved": true },
+        ]))
+    );
+}
+
+#[tokio::test]
+async fn pack_parallel_reduce() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::parallel(json!({
+                "in": ["u1", "u2"],
+                "collect": ["approved"],
+                "reduce": "results.filter(r => r.approved).length",
+                "output": "approval",
+                "acts": vec![
+                    Act::irq(|act| act.with_key("act1").with_id("act1"))
+                ]
+            }))
+            .with_id("p1"),
+        )
+    });
+
+    workflow.print();
+    let (proc, scher, emitter, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            e.do_action(
+                &e.pid,
+                &e.tid,
+                EventAction::Next,
+                &Vars::new().with("approved", true),
+            )
+            .unwrap();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    let task = proc.task_by_nid("p1").first().unwrap().clone();
+    assert_eq!(task.data().get::<i32>(consts::ACT_REDUCE_RESULT), Some(2));
+
+    // the results are exposed to the step by the output name
+    let step = proc.task_by_nid("step1").first().unwrap().clone();
+    let approval = step.data().get::<Vars>("approval").unwrap();
+    assert!(approval.contains_key(consts::ACT_COLLECT_RESULTS));
+    assert_eq!(approval.get::<i32>(consts::ACT_REDUCE_RESULT), Some(2));
+    assert!(!step.data().contains_key(consts::ACT_COLLECT_RESULTS));
+}
+
+#[tokio::test]
+async fn pack_parallel_collect_not_overwrite_vars() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_setup(|setup| setup.add(Act::set(Vars::new().with("result", "keep"))))
+            .with_act(
+                Act::parallel(json!({
+                    "in": ["u1"],
+                    "collect": ["approved"],
+                    "reduce": "results.length",
+                    "acts": vec![
+                        Act::irq(|act| act.with_key("act1").with_id("act1"))
+                    ]
+                }))
+                .with_id("p1"),
+            )
+    });
+
+    workflow.print();
+    let (proc, scher, emitter, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            e.do_action(&e.pid, &e.tid, EventAction::Next, &Vars::new())
+                .unwrap();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    let task = proc.task_by_nid("p1").first().unwrap().clone();
+    assert_eq!(task.data().get::<i32>(consts::ACT_REDUCE_RESULT), Some(1));
+
+    let step = proc.task_by_nid("step1").first().unwrap().clone();
+    assert_eq!(step.data().get::<String>("result").unwrap(), "keep");
+}
+
+async fn run_parallel_complete_when(
+    complete_when: serde_json::Value,
+    approves: Vec<&'static str>,
+) -> (Vec<String>, TaskState) {
+    let act = Act::parallel(json!({
+        "in": ["u1", "u2", "u3"],
+        "collect": ["approved"],
+        "complete_when": complete_when,
+        "acts": vec![
+            Act::irq(|act| act.with_key("act1").with_id("act1"))
+        ]
+    }))
+    .with_id("p1");
+    let mut workflow = Workflow::new();
+    workflow
+        .steps
+        .push(Step::new().with_id("step1").with_act(act));
+
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if !e.is_key("act1") {
+            return;
+        }
+        let options = e.inputs.get::<Vars>(consts::ACT_OPTIONS_KEY).unwrap();
+        let value = options.get::<String>(consts::ACT_VALUE).unwrap();
+        if e.is_state(MessageState::Created) {
+            if approves.contains(&value.as_str()) {
+                e.do_action(
+                    &e.pid,
+                    &e.tid,
+                    EventAction::Next,
+                    &Vars::new().with("approved", true),
+                )
+                .unwrap();
+            }
+        } else if e.is_state(MessageState::Cancelled) {
+            rx.update(|data| data.push(value.clone()));
+        }
+    });
+    scher.launch(&proc);
+    let cancelled = tx.recv().await;
+    proc.print();
+    let state = proc.task_by_nid("p1").first().unwrap().state();
+    (cancelled, state)
+}
+
+#[tokio::test]
+async fn pack_parallel_complete_when_any() {
+    let (cancelled, state) = run_parallel_complete_when(json!("any"), vec!["u2"]).await;
+    assert_eq!(state, TaskState::Completed);
+    assert_eq!(cancelled.len(), 2);
+    assert!(!cancelled.contains(&"u2".to_string()));
+}
+
+#[tokio::test]
+async fn pack_parallel_complete_when_count() {
+    let (cancelled, state) =
+        run_parallel_complete_when(json!({ "count": 2 }), vec!["u1", "u3"]).await;
+    assert_eq!(state, TaskState::Completed);
+    assert_eq!(cancelled, vec!["u2".to_string()]);
+}
+
+#[tokio::test]
+async fn pack_parallel_complete_when_ratio() {
+    let (cancelled, state) =
+        run_parallel_complete_when(json!({ "ratio": 0.6 }), vec!["u1", "u2"]).await;
+    assert_eq!(state, TaskState::Completed);
+    assert_eq!(cancelled, vec!["u3".to_string()]);
+}
+
+#[tokio::test]
+async fn pack_parallel_complete_when_expr() {
+    let (cancelled, state) = run_parallel_complete_when(
+        json!({ "expr": "results.filter(r => r.approved).length >= 1" }),
+        vec!["u3"],
+    )
+    .await;
+    assert_eq!(state, TaskState::Completed);
+    assert_eq!(cancelled.len(), 2);
+}
+
+#[tokio::test]
+async fn pack_parallel_complete_when_all() {
+    let (cancelled, state) = run_parallel_complete_when(json!("all"), vec!["u1", "u2", "u3"]).await;
+    assert_eq!(state, TaskState::Completed);
+    assert!(cancelled.is_empty());
+}
+
+#[tokio::test]
+async fn pack_parallel_max_concurrency_pending() {
+    let act = Act::parallel(json!({
+        "in": ["u1", "u2", "u3", "u4", "u5"],
+        "max_concurrency": 2,
+        "acts": vec![
+            Act::irq(|act| act.with_key("act1").with_id("act1"))
+        ]
+    }))
+    .with_id("p1");
+    let mut workflow = Workflow::new();
+    workflow
+        .steps
+        .push(Step::new().with_id("step1").with_act(act));
+
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            let options = e.inputs.get::<Vars>(consts::ACT_OPTIONS_KEY).unwrap();
+            let value = options.get::<String>(consts::ACT_VALUE).unwrap();
+            rx.update(|data| data.push(value.clone()));
+        }
+    });
+    scher.launch(&proc);
+    let created = tx.timeout(300).await;
+    proc.print();
+    assert_eq!(created, vec!["u1".to_string(), "u2".to_string()]);
+
+    let p1 = proc.task_by_nid("p1").first().unwrap().clone();
+    let blocks = p1.children();
+    assert_eq!(blocks.len(), 5);
+    assert_eq!(blocks.iter().filter(|t| t.state().is_pending()).count(), 3);
+}
+
+#[tokio::test]
+async fn pack_parallel_max_concurrency_complete() {
+    let act = Act::parallel(json!({
+        "in": ["u1", "u2", "u3", "u4", "u5"],
+        "max_concurrency": 2,
+        "collect": ["value"],
+        "acts": vec![
+            Act::irq(|act| act.with_key("act1").with_id("act1"))
+        ]
+    }))
+    .with_id("p1");
+    let mut workflow = Workflow::new();
+    workflow
+        .steps
+        .push(Step::new().with_id("step1").with_act(act));
+
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            let options = e.inputs.get::<Vars>(consts::ACT_OPTIONS_KEY).unwrap();
+            let value = options.get::<String>(consts::ACT_VALUE).unwrap();
+            rx.update(|data| data.push(value.clone()));
+            e.do_action(
+                &e.pid,
+                &e.tid,
+                EventAction::Next,
+                &Vars::new().with("value", value),
+            )
+            .unwrap();
+        }
+    });
+    scher.launch(&proc);
+    let created = tx.recv().await;
+    proc.print();
+    assert_eq!(
+        created,
+        vec!["u1", "u2", "u3", "u4", "u5"]
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        proc.task_by_nid("p1").first().unwrap().state(),
+        TaskState::Completed
+    );
+    assert_eq!(proc.state(), TaskState::Completed);
+}
+
+#[tokio::test]
+async fn pack_parallel_invalid_params_error() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::parallel(json!({
+                "acts": vec![Act::irq(|act| act.with_key("act1"))]
+            }))
+            .with_id("parallel1"),
+        )
+    });
+
+    workflow.print();
+    let (proc, scher, _emitter, tx, _rx) =
+        create_proc_signal::<()>(&mut workflow, &utils::longid());
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    assert!(proc.state().is_error());
+    let err = proc.err().unwrap();
+    assert!(
+        err.message
+            .contains("invalid params of package 'acts.core.parallel'")
+    );
+    assert!(err.message.contains(r#""in" is a required property"#));
+}
+
+#[tokio::test]
+async fn pack_parallel_max_concurrency_restore() {
+    let act = Act::parallel(json!({
+        "in": ["u1", "u2", "u3", "u4", "u5"],
+        "max_concurrency": 2,
+        "acts": vec![
+            Act::irq(|act| act.with_key("act1").with_id("act1"))
+        ]
+    }))
+    .with_id("p1");
+    let mut workflow = Workflow::new();
+    workflow
+        .steps
+        .push(Step::new().with_id("step1").with_act(act));
+
+    let auto = Arc::new(AtomicBool::new(false));
+    let (proc, rt, emitter, tx, rx) =
+        create_proc_signal::<Vec<(String, usize, String)>>(&mut workflow, &utils::longid());
+    let pid = proc.id().to_string();
+    let auto2 = auto.clone();
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            let options = e.inputs.get::<Vars>(consts::ACT_OPTIONS_KEY).unwrap();
+            let value = options.get::<String>(consts::ACT_VALUE).unwrap();
+            let index = options.get::<usize>(consts::ACT_INDEX).unwrap();
+            rx.update(|data| data.push((value.clone(), index, e.tid.clone())));
+            if auto2.load(Ordering::SeqCst) {
+                e.do_action(&e.pid, &e.tid, EventAction::Next, &Vars::new())
+                    .unwrap();
+            }
+        }
+    });
+    rt.launch(&proc);
+    let created = tx.timeout(300).await;
+    assert_eq!(created.len(), 2);
+
+    // reload the process from store in the middle of the batch
+    let cache = rt.cache();
+    cache.uncache(&pid);
+    cache.restore(&rt, |_| {}).unwrap();
+    let restored = rt.proc(&pid).unwrap();
+    let p1 = restored.task_by_nid("p1").first().unwrap().clone();
+    assert_eq!(
+        p1.children()
+            .iter()
+            .filter(|t| t.state().is_pending())
+            .count(),
+        3
+    );
+
+    auto.store(true, Ordering::SeqCst);
+    let executor = Executor::new(&rt);
+    for (_, _, tid) in created.iter() {
+        executor.act().complete(&pid, tid, &Vars::new()).unwrap();
+    }
+    let created = tx.recv().await;
+
+    // the remaining items start exactly once at the right index
+    assert_eq!(
+        created
+            .iter()
+            .map(|(value, index, _)| (value.as_str(), *index))
+            .collect::<Vec<_>>(),
+        vec![("u1", 0), ("u2", 1), ("u3", 2), ("u4", 3), ("u5", 4)]
+    );
+    assert_eq!(
+        restored.task_by_nid("p1").first().unwrap().state(),
+        TaskState::Completed
+    );
+    assert_eq!(restored.state(), TaskState::Completed);
+}
+
+#[tokio::test]
+async fn pack_parallel_complete_when_count_zero_error() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::parallel(json!({
+                "in": ["u1", "u2"],
+                "complete_when": { "count": 0 },
+                "acts": vec![Act::irq(|act| act.with_key("act1"))]
+            }))
+            .with_id("parallel1"),
+        )
+    });
+
+    let (proc, scher, _emitter, tx, _rx) =
+        create_proc_signal::<()>(&mut workflow, &utils::longid());
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    assert!(proc.state().is_error());
+    let err = proc.err().unwrap();
+    assert!(
+        err.message
+            .contains("invalid params of package 'acts.core.parallel'")
+    );
+    assert!(proc.task_by_nid("act1").is_empty());
+}
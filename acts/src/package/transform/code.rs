@@ -16,7 +16,10 @@ impl ActPackage for CodePackage {
             version: "0.1.0",
             icon: "icon-code",
             doc: "",
-            schema: json!({}),
+            schema: json!({
+                "type": "string",
+                "description": "the javascript code, the returned object is set to the outputs"
+            }),
             run_as: ActRunAs::Func,
             resources: vec![],
             catalog: ActPackageCatalog::Transform,
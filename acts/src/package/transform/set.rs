@@ -18,7 +18,10 @@ impl ActPackage for SetPackage {
             icon: "icon-set",
             doc: "",
             version: "0.1.0",
-            schema: json!({}),
+            schema: json!({
+                "type": "object",
+                "description": "the vars to set and expose to the next acts"
+            }),
             run_as: ActRunAs::Func,
             resources: Vec::new(),
             catalog: ActPackageCatalog::Transform,
@@ -9,7 +9,8 @@ use crate::{
     utils::{self, consts, shortid},
 };
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
-use std::{any::type_name, cell::RefCell, sync::Arc, vec};
+use serde_json::Value as JsonValue;
+use std::{any::type_name, cell::RefCell, collections::HashSet, sync::Arc, vec};
 use tracing::debug;
 
 tokio::task_local! {
@@ -310,17 +311,96 @@ impl Context {
             }
         }
 
-        // marks the state in the paths
+        // marks the tasks in the paths as backed since they are undone
+        let path_ids = paths.iter().map(|t| t.id.clone()).collect::<HashSet<_>>();
         for p in paths {
-            if p.state().is_running() {
-                p.set_state(TaskState::Completed);
-                self.emit_task(p)?;
-            } else if p.state().is_pending() {
-                p.set_state(TaskState::Skipped);
-                self.emit_task(p)?;
-            }
+            self.back_tree(p, &path_ids)?;
+        }
+
+        Ok(())
+    }
+
+    fn back_tree(&self, task: &Arc<Task>, path_ids: &HashSet<String>) -> Result<()> {
+        // the completed sibling branch is not in the path and keeps its state
+        if task.is_kind(NodeKind::Branch)
+            && task.state().is_success()
+            && !path_ids.contains(&task.id)
+        {
+            return Ok(());
         }
 
+        let state = task.state();
+        if state.is_created()
+            || state.is_running()
+            || state.is_success()
+            || state == TaskState::Submitted
+        {
+            task.set_state(TaskState::Backed);
+            self.emit_task(task)?;
+        }
+        for child in task.children() {
+            self.back_tree(&child, path_ids)?;
+        }
+
+        Ok(())
+    }
+
+    /// keep the process vars of the step visit in the step task data to rollback when going back
+    /// the internal vars are not included
+    pub fn snapshot_vars(&self, task: &Arc<Task>) -> Result<()> {
+        let Some(root) = self.proc.root() else {
+            return Ok(());
+        };
+        let vars = root.with_data(|data| {
+            data.iter()
+                .filter(|(key, _)| !key.starts_with('$'))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect::<Vars>()
+        });
+        task.set_data_with(|data| data.set(consts::STEP_VARS_SNAPSHOT, vars.clone()));
+
+        Ok(())
+    }
+
+    /// rollback the process vars to the snapshot of the step task
+    /// the value is `true` for all vars or the list of the var names
+    pub fn rollback_vars(&self, task: &Arc<Task>, value: &JsonValue) -> Result<()> {
+        let names = match value {
+            JsonValue::Null | JsonValue::Bool(false) => return Ok(()),
+            JsonValue::Bool(true) => None,
+            JsonValue::Array(_) => Some(serde_json::from_value::<Vec<String>>(value.clone())?),
+            _ => {
+                return Err(ActError::Action(format!(
+                    "invalid '{}' value '{value}' in options",
+                    consts::ACT_BACK_ROLLBACK
+                )));
+            }
+        };
+
+        let Some(root) = self.proc.root() else {
+            return Ok(());
+        };
+        let Some(vars) = task.with_data(|data| data.get::<Vars>(consts::STEP_VARS_SNAPSHOT)) else {
+            return Ok(());
+        };
+        root.set_data_with(|data| {
+            let keys = data
+                .keys()
+                .chain(vars.keys())
+                .filter(|key| !key.starts_with('$'))
+                .filter(|key| names.as_ref().is_none_or(|names| names.contains(key)))
+                .cloned()
+                .collect::<HashSet<_>>();
+            for key in keys {
+                match vars.get_value(&key) {
+                    Some(value) => data.set(&key, value.clone()),
+                    None => {
+                        data.pop(&key);
+                    }
+                }
+            }
+        });
+
         Ok(())
     }
 
@@ -361,6 +441,25 @@ impl Context {
         Ok(())
     }
 
+    /// cancel the uncompleted tasks and their children
+    pub fn cancel_tasks(&self, tasks: &[Arc<Task>]) -> Result<()> {
+        let mut tasks = tasks.to_vec();
+        while !tasks.is_empty() {
+            let mut nexts = Vec::new();
+            for t in &tasks {
+                nexts.extend_from_slice(&t.children());
+                if t.state().is_completed() {
+                    continue;
+                }
+                t.set_state(TaskState::Cancelled);
+                self.emit_task(t)?;
+            }
+            tasks = nexts;
+        }
+
+        Ok(())
+    }
+
     /// undo task
     /// the undo task is a step task, set the task as completed and set the children acts as cancelled
     pub fn undo_task(&self, task: &Arc<Task>) -> Result<()> {
@@ -392,6 +491,71 @@ impl Context {
         Ok(())
     }
 
+    /// run the compensations of the completed tasks one by one in reverse completion order
+    /// returns true if there is a compensation to wait for
+    pub fn compensate(&self, root: &Arc<Task>) -> Result<bool> {
+        if let Some(tid) = root.with_data(|data| data.get::<String>(consts::COMPENSATE_TASK))
+            && let Some(task) = self.proc.task(&tid)
+            && !task.state().is_completed()
+        {
+            return Ok(true);
+        }
+
+        let mut tids = match root
+            .with_data(|data| data.get::<Vec<String>>(consts::COMPENSATE_TASKS))
+        {
+            Some(tids) => tids,
+            None => {
+                let mut tasks = self
+                    .proc
+                    .tasks()
+                    .into_iter()
+                    .rev()
+                    .filter(|t| t.state().is_success() && !t.node().content.compensate().is_empty())
+                    .collect::<Vec<_>>();
+                tasks.sort_by_key(|t| std::cmp::Reverse(t.end_time()));
+                tasks.iter().map(|t| t.id.clone()).collect()
+            }
+        };
+
+        while !tids.is_empty() {
+            let tid = tids.remove(0);
+            let Some(target) = self.proc.task(&tid) else {
+                continue;
+            };
+
+            let act = Act::block(
+                Vars::new()
+                    .with("mode", "sequence")
+                    .with("acts", target.node().content.compensate()),
+            );
+            let node = Arc::new(Node::new(
+                &shortid(),
+                NodeContent::Act(act),
+                root.node().level + 1,
+            ));
+            let task = self.proc.create_task(&node, Some(target.clone()));
+            task.set_data(
+                &Vars::new()
+                    .with(consts::IS_EVENT_PROCESSED, true)
+                    .with(consts::COMPENSATE_FOR, &target.id),
+            );
+            root.set_data(
+                &Vars::new()
+                    .with(consts::COMPENSATE_TASKS, &tids)
+                    .with(consts::COMPENSATE_TASK, &task.id),
+            );
+
+            // keep the process running until all of the compensations are completed
+            self.proc.set_state(TaskState::Running);
+            self.runtime.push(&task);
+            return Ok(true);
+        }
+        root.set_data(&Vars::new().with(consts::COMPENSATE_TASKS, tids));
+
+        Ok(false)
+    }
+
     pub fn emit_error(&self) -> Result<()> {
         let task = self.task();
         if task.state().is_error() {
@@ -414,6 +578,14 @@ impl Context {
     pub fn emit_task(&self, task: &Arc<Task>) -> Result<()> {
         debug!("ctx::emit_task, task={:?}", task);
 
+        // run the compensations before the workflow finishes with abort or error
+        if let NodeContent::Workflow(_) = &task.node().content
+            && (task.state().is_abort() || task.state().is_error())
+            && self.compensate(task)?
+        {
+            return Ok(());
+        }
+
         // on workflow start
         if let NodeContent::Workflow(_) = &task.node().content {
             if task.state().is_created() {
@@ -426,6 +598,14 @@ impl Context {
 
         self.runtime.scher().emit_task_event(task)?;
 
+        // continue to run the next compensation
+        if task.is_compensation()
+            && task.state().is_completed()
+            && let Some(root) = self.proc.root()
+        {
+            self.emit_task(&root)?;
+        }
+
         // on workflow complete
         if let NodeContent::Workflow(_) = &task.node().content {
             if task.state().is_completed() {
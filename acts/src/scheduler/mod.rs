@@ -18,7 +18,7 @@ pub use scheduler::Scheduler;
 pub use state::TaskState;
 
 #[allow(unused_imports)]
-pub use tree::{Node, NodeContent, NodeData, NodeKind, NodeTree};
+pub use tree::{Diagram, Node, NodeContent, NodeData, NodeKind, NodeTree};
 
 pub trait ActTask: Clone + Send {
     fn init(&self, _ctx: &Context) -> Result<()> {
@@ -1,3 +1,4 @@
+mod patch;
 #[allow(clippy::module_inception)]
 mod process;
 mod task;
@@ -0,0 +1,187 @@
+use super::Process;
+use crate::{ActError, Branch, Result, Step, Workflow};
+use std::collections::HashSet;
+
+/// find the step list which contains the step id
+fn find_list<'a>(steps: &'a mut Vec<Step>, id: &str) -> Option<&'a mut Vec<Step>> {
+    if steps.iter().any(|s| s.id == id) {
+        return Some(steps);
+    }
+    for step in steps.iter_mut() {
+        let lists = step
+            .branches
+            .iter_mut()
+            .map(|b| &mut b.steps)
+            .chain(step.catches.iter_mut().map(|c| &mut c.steps))
+            .chain(step.timeout.iter_mut().map(|t| &mut t.steps))
+            .chain(step.acts.iter_mut().flat_map(|a| {
+                a.catches
+                    .iter_mut()
+                    .map(|c| &mut c.steps)
+                    .chain(a.timeout.iter_mut().map(|t| &mut t.steps))
+            }));
+        for list in lists {
+            if let Some(list) = find_list(list, id) {
+                return Some(list);
+            }
+        }
+    }
+    None
+}
+
+/// visit all of the steps in the step list and the nested lists
+fn visit_steps<F: FnMut(&mut Step)>(steps: &mut [Step], f: &mut F) {
+    for step in steps.iter_mut() {
+        f(step);
+        for branch in step.branches.iter_mut() {
+            visit_steps(&mut branch.steps, f);
+        }
+        for c in step.catches.iter_mut() {
+            visit_steps(&mut c.steps, f);
+        }
+        for t in step.timeout.iter_mut() {
+            visit_steps(&mut t.steps, f);
+        }
+        for act in step.acts.iter_mut() {
+            for c in act.catches.iter_mut() {
+                visit_steps(&mut c.steps, f);
+            }
+            for t in act.timeout.iter_mut() {
+                visit_steps(&mut t.steps, f);
+            }
+        }
+    }
+}
+
+fn visit_steps_in<F: FnMut(&mut Step)>(model: &mut Workflow, f: &mut F) {
+    visit_steps(&mut model.steps, f);
+    for c in model.catches.iter_mut() {
+        visit_steps(&mut c.steps, f);
+    }
+    for t in model.timeout.iter_mut() {
+        visit_steps(&mut t.steps, f);
+    }
+}
+
+fn find_list_in<'a>(model: &'a mut Workflow, id: &str) -> Option<&'a mut Vec<Step>> {
+    let lists = std::iter::once(&mut model.steps)
+        .chain(model.catches.iter_mut().map(|c| &mut c.steps))
+        .chain(model.timeout.iter_mut().map(|t| &mut t.steps));
+    for list in lists {
+        if let Some(list) = find_list(list, id) {
+            return Some(list);
+        }
+    }
+    None
+}
+
+fn find_step<'a>(model: &'a mut Workflow, id: &str) -> Result<&'a mut Step> {
+    find_list_in(model, id)
+        .and_then(|list| list.iter_mut().find(|s| s.id == id))
+        .ok_or(ActError::Action(format!("cannot find step '{id}'")))
+}
+
+impl Process {
+    /// insert the step after the step of 'after'
+    /// the 'next' of the 'after' step is moved to the inserted step
+    pub fn insert_step(&self, after: &str, step: &Step) -> Result<()> {
+        self.apply(|model| {
+            self.check_not_completed(after)?;
+            let list = find_list_in(model, after)
+                .ok_or(ActError::Action(format!("cannot find step '{after}'")))?;
+            let index = list.iter().position(|s| s.id == after).unwrap_or_default();
+            let mut step = step.clone();
+            if step.next.is_none() {
+                step.next = list[index].next.take();
+            }
+            list.insert(index + 1, step);
+            Ok(())
+        })
+    }
+
+    /// append the branch to the step which is not started
+    pub fn insert_branch(&self, step_id: &str, branch: &Branch) -> Result<()> {
+        self.apply(|model| {
+            self.check_not_started(step_id)?;
+            let step = find_step(model, step_id)?;
+            step.branches.push(branch.clone());
+            Ok(())
+        })
+    }
+
+    /// remove the step which is not started
+    /// the 'next' of the other steps to it is moved to the step after it, which is its 'next'
+    /// or the following step in the list, it is rejected when there is no step after it
+    pub fn remove_step(&self, id: &str) -> Result<()> {
+        self.apply(|model| {
+            self.check_not_started(id)?;
+            let list = find_list_in(model, id)
+                .ok_or(ActError::Action(format!("cannot find step '{id}'")))?;
+            let index = list.iter().position(|s| s.id == id).unwrap_or_default();
+            let next = list[index]
+                .next
+                .clone()
+                .or_else(|| list.get(index + 1).map(|s| s.id.clone()));
+            list.remove(index);
+
+            let mut refs = Vec::new();
+            visit_steps_in(model, &mut |step| {
+                if step.next.as_deref() == Some(id) {
+                    step.next = next.clone();
+                    refs.push(step.id.clone());
+                }
+            });
+            if next.is_none() && !refs.is_empty() {
+                return Err(ActError::Action(format!(
+                    "step '{id}' is the next of the steps '{}'",
+                    refs.join(",")
+                )));
+            }
+            Ok(())
+        })
+    }
+
+    /// reroute the step to the next step
+    pub fn set_next(&self, id: &str, next: &str) -> Result<()> {
+        self.apply(|model| {
+            self.check_not_completed(id)?;
+            let step = find_step(model, id)?;
+            step.next = Some(next.to_string());
+            Ok(())
+        })
+    }
+
+    /// apply the change to the model and rebuild the node tree
+    /// the tree write lock is held from reading the model to rebuilding the tree,
+    /// so the concurrent patches are applied one by one without losing the changes
+    fn apply<F: FnOnce(&mut Workflow) -> Result<()>>(&self, f: F) -> Result<()> {
+        let mut tree = self.tree_mut();
+        let mut model = *tree.model.clone();
+        f(&mut model)?;
+
+        let keeps = self
+            .tasks()
+            .iter()
+            .map(|t| t.node().id().to_string())
+            .collect::<HashSet<_>>();
+        tree.patch(&model, &keeps)
+    }
+
+    fn check_not_completed(&self, id: &str) -> Result<()> {
+        if self
+            .task_by_nid(id)
+            .iter()
+            .any(|t| t.state().is_completed())
+        {
+            return Err(ActError::Action(format!("step '{id}' is completed")));
+        }
+        Ok(())
+    }
+
+    fn check_not_started(&self, id: &str) -> Result<()> {
+        if !self.task_by_nid(id).is_empty() {
+            return Err(ActError::Action(format!("step '{id}' is started")));
+        }
+        Ok(())
+    }
+}
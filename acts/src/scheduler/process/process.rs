@@ -1,16 +1,17 @@
 use crate::event::EventAction;
 use crate::{
-    ActError, Error, NodeKind, ProcInfo, Result, ShareLock, Vars, Workflow, data,
+    ActError, Error, NodeKind, Outputs, ProcInfo, Result, ShareLock, Vars, Workflow, data,
     event::Action,
     scheduler::{
         Context, Runtime, Task, TaskLifeCycle, TaskState,
-        tree::{Node, NodeTree, TaskTree},
+        tree::{Diagram, Node, NodeTree, TaskTree},
     },
     utils::{self, consts},
 };
 use serde::Deserialize;
 use std::{
     cell::RefCell,
+    collections::HashMap,
     fmt,
     sync::{Arc, RwLock},
 };
@@ -95,6 +96,10 @@ impl Process {
         self.tree.read().unwrap()
     }
 
+    pub(crate) fn tree_mut(&self) -> std::sync::RwLockWriteGuard<'_, NodeTree> {
+        self.tree.write().unwrap()
+    }
+
     pub fn model(&self) -> Box<Workflow> {
         self.tree().model.clone()
     }
@@ -271,6 +276,11 @@ impl Process {
     #[instrument()]
     pub fn do_action(self: &Arc<Self>, action: &Action) -> Result<()> {
         let mut action = action.clone();
+        let actor = action
+            .options
+            .get::<String>(consts::FOR_ACT_KEY_UID)
+            .or_else(|| action.options.get::<String>(consts::INITIATOR))
+            .unwrap_or_default();
         let task = self.task(&action.tid).ok_or(ActError::Action(format!(
             "cannot find task by '{}' tasks={:?}",
             action.tid,
@@ -294,8 +304,16 @@ impl Process {
         // check the outputs
         let rets = task.node().outputs();
         if !rets.is_empty() {
+            let decls = Outputs::from_vars(&rets);
             let mut options = Vars::new();
             for (ref key, _) in &rets {
+                if decls.contains_key(key) {
+                    // the declared outputs are checked by the declarations later
+                    if let Some(value) = action.options.get_value(key) {
+                        options.set(key, value.clone());
+                    }
+                    continue;
+                }
                 if !action.options.contains_key(key) {
                     return Err(ActError::Action(format!(
                         "the options is not satisfied with act's outputs '{}' in task({})",
@@ -308,11 +326,33 @@ impl Process {
 
             // retset the options by rets defination
             action.options = options;
+
+            // turn the action to an error if the outputs are invalid
+            if matches!(action.event, EventAction::Next | EventAction::Submit)
+                && let Err(err) = decls.apply(&mut action.options)
+            {
+                action.event = EventAction::Error;
+                action.options = Vars::new()
+                    .with(consts::ACT_ERR_CODE, consts::ACT_ERR_OUTPUTS)
+                    .with(consts::ACT_ERR_MESSAGE, err.to_string());
+            }
         }
 
         let ctx = task.create_context();
         ctx.set_action(&action)?;
         task.update(&ctx)?;
+
+        // append the action to the history
+        let history = data::History::new(
+            &action.pid,
+            &action.tid,
+            data::HistoryKind::Action,
+            action.event.as_ref(),
+            &action.options.to_string(),
+        )
+        .with_actor(&actor);
+        self.runtime.store().history().create(&history)?;
+
         Ok(())
     }
 
@@ -461,6 +501,28 @@ impl Process {
         s.clone().into_inner()
     }
 
+    /// render the process as a mermaid flowchart, the nodes are colored by the task states
+    pub fn to_mermaid(&self) -> String {
+        self.diagram().to_mermaid()
+    }
+
+    /// render the process as a graphviz dot digraph, the nodes are colored by the task states
+    pub fn to_dot(&self) -> String {
+        self.diagram().to_dot()
+    }
+
+    fn diagram(&self) -> Diagram {
+        // the latest task state of each node, such as the loop iterations
+        let mut tasks = self.tasks();
+        tasks.sort_by_key(|t| t.timestamp);
+        let states = tasks
+            .iter()
+            .map(|t| (t.node().id().to_string(), t.state()))
+            .collect::<HashMap<_, _>>();
+
+        Diagram::new(&self.tree()).with_states(&states)
+    }
+
     pub fn into_data(self: &Arc<Self>) -> Result<data::Proc> {
         let model = self.model();
         Ok(data::Proc {
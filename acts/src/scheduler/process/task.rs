@@ -1,4 +1,5 @@
 mod act;
+mod assign;
 mod branch;
 mod hook;
 mod step;
@@ -6,10 +7,11 @@ mod workflow;
 
 use crate::utils::consts::TASK_ROOT_TID;
 use crate::{
-    Act, ActError, ActTask, Catch, Error, Message, MessageState, NodeKind, Result, ShareLock,
-    Timeout, Vars,
+    Act, ActError, ActTask, Catch, Error, Message, MessageState, NodeKind, Outputs, Result,
+    ShareLock, Sla, Timeout, TimeoutLimit, Vars,
     data::{self, MessageStatus},
     event::{EventAction, Model},
+    package::ActPackageFn,
     scheduler::{
         Context, Process, Runtime, TaskState,
         tree::{Node, NodeContent},
@@ -18,7 +20,7 @@ use crate::{
 };
 pub use hook::{StatementBatch, TaskLifeCycle};
 use serde::de::DeserializeOwned;
-use serde_json::json;
+use serde_json::{Value as JsonValue, json};
 use std::{
     collections::HashMap,
     sync::{Arc, RwLock},
@@ -58,6 +60,9 @@ pub struct Task {
     hooks: ShareLock<HashMap<TaskLifeCycle, Vec<StatementBatch>>>,
 
     runtime: Arc<Runtime>,
+
+    // the func package created once by the act params
+    package: ShareLock<Option<Arc<dyn ActPackageFn>>>,
     // sync: Arc<std::sync::Mutex<usize>>,
 }
 
@@ -78,6 +83,7 @@ impl Task {
 
             hooks: Arc::new(RwLock::new(HashMap::new())),
             runtime: rt.clone(),
+            package: Arc::new(RwLock::new(None)),
             // sync: Arc::new(std::sync::Mutex::new(0)),
         }
     }
@@ -118,6 +124,14 @@ impl Task {
         0
     }
 
+    pub fn package(&self) -> Option<Arc<dyn ActPackageFn>> {
+        self.package.read().unwrap().clone()
+    }
+
+    pub fn set_package(&self, package: &Arc<dyn ActPackageFn>) {
+        *self.package.write().unwrap() = Some(package.clone());
+    }
+
     pub fn is_emit_disabled(&self) -> bool {
         self.with_data(|data| data.get::<bool>(consts::TASK_EMIT_DISABLED))
             .unwrap_or(false)
@@ -139,6 +153,10 @@ impl Task {
             .unwrap_or(false)
     }
 
+    pub fn is_compensation(&self) -> bool {
+        self.with_data(|data| data.contains_key(consts::COMPENSATE_FOR))
+    }
+
     pub fn set_auto_complete(&self, v: bool) {
         self.set_data_with(move |data| {
             data.set(consts::TASK_AUOT_COMPLETE, v);
@@ -179,6 +197,18 @@ impl Task {
             inputs.set(consts::ACT_PARAMS_KEY, self.params());
         }
 
+        if let Some(deadline) = self.deadline() {
+            inputs.set(consts::MESSAGE_DEADLINE, deadline);
+        }
+
+        // the assignee replaces the uid in inputs
+        if let Some(assignment) = self.assignment() {
+            if let Some(uid) = &assignment.assignee {
+                inputs.set(consts::FOR_ACT_KEY_UID, uid.clone());
+            }
+            inputs.set(consts::MESSAGE_ASSIGNMENT, assignment);
+        }
+
         // add error to inputs
         if let Some(err) = self.err() {
             inputs.set(consts::ACT_ERR_CODE, err.ecode);
@@ -292,6 +322,21 @@ impl Task {
         utils::fill_outputs(&outputs, &ctx)
     }
 
+    /// check the outputs by the output declarations of the node
+    pub fn check_outputs(self: &Arc<Self>) -> Result<()> {
+        let decls = Outputs::from_vars(&self.node.content.outputs());
+        if decls.is_empty() {
+            return Ok(());
+        }
+
+        decls
+            .check(&self.outputs())
+            .map_err(|err| ActError::Exception {
+                ecode: consts::ACT_ERR_OUTPUTS.to_string(),
+                message: err.to_string(),
+            })
+    }
+
     pub fn options(self: &Arc<Self>) -> Vars {
         self.node.content.options()
     }
@@ -443,6 +488,14 @@ impl Task {
                         "cannot find 'to' value in options".to_string(),
                     ))?;
 
+                if !self
+                    .proc
+                    .node(&nid)
+                    .is_some_and(|n| n.kind() == NodeKind::Step)
+                {
+                    return Err(ActError::Action(format!("cannot find step '{nid}'")));
+                }
+
                 let mut path_tasks = Vec::new();
                 let task = self.backs(
                     &|t| t.node.kind() == NodeKind::Step && t.node.id() == nid,
@@ -454,6 +507,9 @@ impl Task {
                     nid
                 )))?;
 
+                if let Some(rollback) = ctx.get_var::<JsonValue>(consts::ACT_BACK_ROLLBACK) {
+                    ctx.rollback_vars(&task, &rollback)?;
+                }
                 ctx.back_task(&ctx.task(), &path_tasks)?;
                 ctx.redo_task(&task)?;
             }
@@ -628,10 +684,32 @@ impl Task {
 
                 false
             }
+            NodeContent::Act(_) => !self.is_concurrency_limited(),
             _ => true,
         }
     }
 
+    /// check if the running siblings reach the max concurrency of the parent
+    pub fn is_concurrency_limited(&self) -> bool {
+        let Some(parent) = self.parent() else {
+            return false;
+        };
+        let Some(max) = parent.with_data(|data| data.get::<usize>(consts::ACT_MAX_CONCURRENCY))
+        else {
+            return false;
+        };
+
+        let running = self
+            .siblings()
+            .iter()
+            .filter(|t| {
+                let state = t.state();
+                !state.is_none() && !state.is_pending() && !state.is_completed()
+            })
+            .count();
+        running >= max
+    }
+
     pub fn resume(self: &Arc<Self>, ctx: &Context) -> Result<()> {
         if self.is_ready() {
             self.set_state(TaskState::Running);
@@ -663,6 +741,34 @@ impl Task {
             .or_insert(vec![batch]);
     }
 
+    /// set the deadline and add the escalations to the timeout hooks
+    pub fn add_hook_sla(&self, value: &Sla) -> Result<()> {
+        if let Some(deadline) = &value.deadline {
+            let deadline = self.time_limit(&TimeoutLimit::parse(deadline)?)?;
+            self.set_data_with(|data| data.set(consts::TASK_DEADLINE, deadline));
+        }
+
+        let mut hooks = self.hooks.write().unwrap();
+        for escalation in &value.escalations {
+            let batch = StatementBatch::Escalation(escalation.clone());
+            hooks.entry(TaskLifeCycle::Timeout).or_default().push(batch);
+        }
+        Ok(())
+    }
+
+    /// calculate the time of the limit from the start time by the process calendar
+    pub fn time_limit(&self, limit: &TimeoutLimit) -> Result<i64> {
+        let calendar = self.proc.tree().model.calendar.clone();
+        self.runtime
+            .calendars()
+            .deadline(calendar.as_deref(), self.start_time(), limit)
+    }
+
+    /// the deadline of the task in milliseconds
+    pub fn deadline(&self) -> Option<i64> {
+        self.with_data(|data| data.get::<i64>(consts::TASK_DEADLINE))
+    }
+
     pub fn add_hook_timeout(&self, key: TaskLifeCycle, value: &Timeout) {
         let mut hooks = self.hooks.write().unwrap();
 
@@ -768,6 +874,7 @@ impl Task {
 
     pub fn into_data(self: &Arc<Self>) -> Result<data::Task> {
         let id = utils::Id::new(&self.pid, &self.id);
+        let assignment = self.assignment().unwrap_or_default();
         Ok(data::Task {
             id: id.id(),
             prev: self.prev(),
@@ -783,6 +890,12 @@ impl Task {
             hooks: serde_json::to_string(&self.hooks()).map_err(ActError::from)?,
             timestamp: self.timestamp,
             err: self.err().map(|err| err.to_string()),
+            candidates: data::Task::join_candidates(
+                &assignment.candidate_users,
+                &assignment.candidate_groups,
+            ),
+            assignee: assignment.assignee.unwrap_or_default(),
+            priority: assignment.priority,
         })
     }
 
@@ -809,9 +922,7 @@ impl Task {
                 }
 
                 // push the path tasks
-                if task.state().is_running() || task.state().is_pending() {
-                    path.push(task.clone());
-                }
+                path.push(task.clone());
 
                 prev = task.prev();
             } else {
@@ -1,7 +1,9 @@
 use super::TaskLifeCycle;
 use crate::{
-    Act, ActError, ActRunAs, ActTask, Result, TaskState, scheduler::Context, utils::consts,
+    Act, ActError, ActRunAs, ActTask, Result, TaskState, package::ActPackageFn, scheduler::Context,
+    utils::consts,
 };
+use std::sync::Arc;
 
 impl ActTask for Act {
     fn init(&self, ctx: &Context) -> Result<()> {
@@ -14,6 +16,12 @@ impl ActTask for Act {
             }
         }
 
+        // wait for a free running slot of the parent
+        if task.is_concurrency_limited() {
+            task.set_state(TaskState::Pending);
+            return Ok(());
+        }
+
         for s in self.catches.iter() {
             task.add_hook_catch(TaskLifeCycle::ErrorCatch, s);
         }
@@ -24,6 +32,10 @@ impl ActTask for Act {
             }
         }
 
+        if let Some(sla) = &self.sla {
+            task.add_hook_sla(sla)?;
+        }
+
         // run setup
         if !self.setup.is_empty() {
             ctx.dispatch_acts(self.setup.clone(), true)?;
@@ -40,9 +52,18 @@ impl ActTask for Act {
         // find the package to run
         let package = ctx.executor.pack().get(&self.uses)?;
         let schema: serde_json::Value = serde_json::from_str(&package.schema)?;
+        if self.assignment.is_some() && package.run_as != ActRunAs::Irq {
+            return Err(ActError::Action(format!(
+                "the assignment is only for the irq act, but '{}' is not",
+                self.uses
+            )));
+        }
         match package.run_as {
             ActRunAs::Irq => {
                 jsonschema::validate(&schema, &task.params())?;
+                if let Some(assignment) = &self.assignment {
+                    task.init_assignment(ctx, assignment)?;
+                }
                 task.set_state(TaskState::Interrupt);
             }
             ActRunAs::Msg => {
@@ -70,15 +91,10 @@ impl ActTask for Act {
         }
 
         if matches!(package.run_as, ActRunAs::Func) {
-            let register = ctx
-                .runtime
-                .package()
-                .get(&self.uses)
-                .ok_or(ActError::Runtime(format!(
-                    "cannot find the registed package '{}'",
-                    self.uses
-                )))?;
-            let package = (register.create)(ctx.task().params())?;
+            let package = self.func_package(ctx)?.ok_or(ActError::Runtime(format!(
+                "cannot find the registed package '{}'",
+                self.uses
+            )))?;
             if let Some(vars) = package.execute(ctx)? {
                 task.update_data(&vars);
             }
@@ -158,8 +174,24 @@ impl ActTask for Act {
                 }
             }
 
+            // start the pending tasks when the running slots are released
+            for t in tasks.iter() {
+                if t.state().is_pending() && t.is_ready() {
+                    t.set_state(TaskState::Ready);
+                    t.exec(ctx)?;
+                    ctx.set_task(&task);
+                }
+            }
+
+            // complete the act early and cancel the remaining tasks
+            if count < tasks.len() && self.can_complete(ctx)? {
+                ctx.cancel_tasks(&tasks)?;
+                count = tasks.len();
+            }
+
             if count == tasks.len() {
                 if !task.state().is_completed() {
+                    self.complete(ctx)?;
                     task.set_state(TaskState::Completed);
                 }
 
@@ -176,6 +208,46 @@ impl ActTask for Act {
 }
 
 impl Act {
+    /// get the func package of the task
+    /// it is created by the act params once and kept on the task, the params are only validated then
+    fn func_package(&self, ctx: &Context) -> Result<Option<Arc<dyn ActPackageFn>>> {
+        let task = ctx.task();
+        if let Some(package) = task.package() {
+            return Ok(Some(package));
+        }
+        if let Some(register) = ctx.runtime.package().get(&self.uses)
+            && matches!((register.meta)().run_as, ActRunAs::Func)
+        {
+            let package: Arc<dyn ActPackageFn> = Arc::from((register.create)(task.params())?);
+            task.set_package(&package);
+            return Ok(Some(package));
+        }
+
+        Ok(None)
+    }
+
+    /// check if the func package can be completed before all the child tasks are completed
+    fn can_complete(&self, ctx: &Context) -> Result<bool> {
+        match self.func_package(ctx)? {
+            Some(package) => package.can_complete(ctx),
+            None => Ok(false),
+        }
+    }
+
+    /// complete the func package when all the child tasks are completed
+    /// the returned vars are exposed as the act outputs
+    fn complete(&self, ctx: &Context) -> Result<()> {
+        let task = ctx.task();
+        if let Some(package) = self.func_package(ctx)?
+            && let Some(vars) = package.complete(ctx)?
+        {
+            task.update_data(&vars);
+            task.expose(&vars.keys().map(|k| k.as_str()).collect::<Vec<_>>());
+        }
+
+        Ok(())
+    }
+
     pub fn dispatch(&self, ctx: &Context, is_hook_event: bool) -> Result<()> {
         // let package = ctx.executor.pack().get(&self.uses)?;
         let mut act = self.clone();
@@ -0,0 +1,124 @@
+use super::Task;
+use crate::{
+    ActError, Assignment, Result, TaskAssignment, TimeoutLimit, scheduler::Context, utils,
+    utils::consts,
+};
+
+impl Task {
+    /// the assignment of the irq act
+    pub fn assignment(&self) -> Option<TaskAssignment> {
+        self.with_data(|data| data.get::<TaskAssignment>(consts::TASK_ASSIGNMENT))
+    }
+
+    fn set_assignment(&self, value: &TaskAssignment) {
+        self.set_data_with(|data| data.set(consts::TASK_ASSIGNMENT, value));
+    }
+
+    /// check and change the assignment under the task data lock
+    /// to avoid the concurrent changes overwriting each other
+    fn update_assignment<F: FnOnce(&mut TaskAssignment) -> Result<()>>(&self, f: F) -> Result<()> {
+        let mut data = self.data.write().unwrap();
+        let mut assignment = data
+            .get::<TaskAssignment>(consts::TASK_ASSIGNMENT)
+            .unwrap_or_default();
+        f(&mut assignment)?;
+        data.set(consts::TASK_ASSIGNMENT, assignment);
+        Ok(())
+    }
+
+    /// calculate the expressions and the due time to init the assignment
+    pub fn init_assignment(&self, ctx: &Context, value: &Assignment) -> Result<()> {
+        let value = utils::fill_params(&serde_json::to_value(value)?, ctx);
+        let value = serde_json::from_value::<Assignment>(value)?;
+        let due = match &value.due {
+            Some(due) => Some(self.due_time(due)?),
+            None => None,
+        };
+
+        self.set_assignment(&TaskAssignment {
+            assignee: value.assignee.filter(|uid| !uid.is_empty()),
+            owner: None,
+            candidate_users: value.candidate_users,
+            candidate_groups: value.candidate_groups,
+            due,
+            priority: value.priority,
+        });
+        Ok(())
+    }
+
+    /// claim the task by the candidate user or the user in the candidate groups
+    pub fn claim(&self, uid: &str, groups: &[String]) -> Result<()> {
+        self.update_assignment(|assignment| {
+            if let Some(assignee) = &assignment.assignee {
+                if assignee == uid {
+                    return Ok(());
+                }
+                return Err(ActError::Action(format!(
+                    "task '{}' is claimed by '{assignee}'",
+                    self.id
+                )));
+            }
+            if !assignment.is_candidate(uid, groups) {
+                return Err(ActError::Action(format!(
+                    "user '{uid}' is not the candidate of task '{}'",
+                    self.id
+                )));
+            }
+
+            assignment.assignee = Some(uid.to_string());
+            Ok(())
+        })
+    }
+
+    /// release the claimed task to the candidates
+    pub fn unclaim(&self, uid: &str) -> Result<()> {
+        self.update_assignment(|assignment| {
+            self.check_assignee(assignment, uid)?;
+
+            assignment.assignee = None;
+            assignment.owner = None;
+            Ok(())
+        })
+    }
+
+    /// delegate the task to the user and keep the owner
+    pub fn delegate(&self, uid: &str, to: &str) -> Result<()> {
+        self.update_assignment(|assignment| {
+            self.check_assignee(assignment, uid)?;
+
+            assignment.owner = assignment.owner.take().or(Some(uid.to_string()));
+            assignment.assignee = Some(to.to_string());
+            Ok(())
+        })
+    }
+
+    /// reassign the task to the user and clear the owner
+    pub fn reassign(&self, to: &str) {
+        self.update_assignment(|assignment| {
+            assignment.assignee = Some(to.to_string());
+            assignment.owner = None;
+            Ok(())
+        })
+        .unwrap_or_default();
+    }
+
+    fn check_assignee(&self, assignment: &TaskAssignment, uid: &str) -> Result<()> {
+        if assignment.assignee.as_deref() != Some(uid) {
+            return Err(ActError::Action(format!(
+                "task '{}' is not assigned to '{uid}'",
+                self.id
+            )));
+        }
+        Ok(())
+    }
+
+    fn due_time(&self, due: &str) -> Result<i64> {
+        if let Ok(limit) = TimeoutLimit::parse(due) {
+            return self.time_limit(&limit);
+        }
+
+        chrono::DateTime::parse_from_rfc3339(due)
+            .map(|time| time.timestamp_millis())
+            .map_err(|_| ActError::Action(format!("invalid due time '{due}'")))
+    }
+}
@@ -1,8 +1,11 @@
 use crate::{
     ActTask, Result,
     model::Branch,
-    scheduler::{Context, TaskState},
+    scheduler::{Context, NodeContent, Task, TaskState},
+    utils::consts,
 };
+use serde_json::Value as JsonValue;
+use std::sync::Arc;
 use tracing::debug;
 
 impl ActTask for Branch {
@@ -14,6 +17,15 @@ impl ActTask for Branch {
             return Ok(());
         }
 
+        // the branch is routed by the parent step switch
+        if let Some(value) = self.switch_value(&task) {
+            debug!("switch = {}", value);
+            if !self.is_switch_matched(&task, &value) {
+                task.set_state(TaskState::Skipped);
+            }
+            return Ok(());
+        }
+
         match &self.r#if {
             Some(expr) => {
                 let is_true = ctx.eval::<bool>(expr)?;
@@ -82,3 +94,40 @@ impl ActTask for Branch {
         Ok(false)
     }
 }
+
+impl Branch {
+    /// get the evaluated switch value if the parent step is a switch step
+    fn switch_value(&self, task: &Arc<Task>) -> Option<JsonValue> {
+        let parent = task.parent()?;
+        match &parent.node().content {
+            NodeContent::Step(step) if step.switch.is_some() => Some(
+                parent
+                    .with_data(|data| data.get_value(consts::STEP_SWITCH_VALUE).cloned())
+                    .unwrap_or(JsonValue::Null),
+            ),
+            _ => None,
+        }
+    }
+
+    /// the case branch runs when its case matches the switch value
+    /// the default branch runs only when no case branch matches
+    fn is_switch_matched(&self, task: &Arc<Task>, value: &JsonValue) -> bool {
+        if self.is_case(value) {
+            return true;
+        }
+
+        if self.default {
+            let branches = task
+                .node()
+                .parent()
+                .map(|parent| parent.children())
+                .unwrap_or_default();
+            return !branches.iter().any(|node| match &node.content {
+                NodeContent::Branch(branch) => branch.is_case(value),
+                _ => false,
+            });
+        }
+
+        false
+    }
+}
@@ -1,8 +1,6 @@
 use crate::{
-    Act, ActTask, Catch, Context, Result, TaskState, Timeout,
-    model::TimeoutLimit,
-    scheduler::tree::NodeOutputKind,
-    utils::{self, consts},
+    Act, ActTask, Action, Catch, Context, Escalation, Result, TaskState, Timeout,
+    model::TimeoutLimit, scheduler::tree::NodeOutputKind, utils::consts,
 };
 use serde::{Deserialize, Serialize};
 use tracing::debug;
@@ -24,6 +22,7 @@ pub enum StatementBatch {
     Statement(Act),
     Catch(Catch),
     Timeout(Timeout),
+    Escalation(Escalation),
 }
 
 impl StatementBatch {
@@ -74,9 +73,8 @@ impl StatementBatch {
                     return Ok(());
                 }
 
-                let millis = utils::time::time_millis() - task.start_time();
-                let on = TimeoutLimit::parse(&t.on)?;
-                if millis >= on.as_secs() * 1000 {
+                let on = task.time_limit(&TimeoutLimit::parse(&t.on)?)?;
+                if ctx.runtime.calendars().now() >= on {
                     task.set_data_with(|data| data.set(&key, true));
                     for node in &task
                         .node()
@@ -86,6 +84,45 @@ impl StatementBatch {
                     }
                 }
             }
+            StatementBatch::Escalation(e) => {
+                let task = ctx.task();
+                if task.state().is_completed() {
+                    return Ok(());
+                }
+                let key = format!("{}{}", consts::IS_ESCALATED_PREFIX, e.on);
+                let is_escalated = task
+                    .with_data(|data| data.get::<bool>(&key))
+                    .unwrap_or_default();
+                if is_escalated {
+                    return Ok(());
+                }
+
+                let on = task.time_limit(&TimeoutLimit::parse(&e.on)?)?;
+                if ctx.runtime.calendars().now() < on {
+                    return Ok(());
+                }
+
+                debug!("run escalation: {:?}", e);
+                task.set_data_with(|data| data.set(&key, true));
+                ctx.runtime.cache().upsert(&task)?;
+                if let Some(msg) = &e.msg {
+                    ctx.dispatch_act(&Act::msg(|act| act.with_key(msg)), true)?;
+                }
+                if let Some(uid) = &e.reassign {
+                    task.reassign(uid);
+                    ctx.runtime.cache().upsert(&task)?;
+                    ctx.runtime.emitter().emit_message(&task.create_message());
+                }
+                if let Some(action) = &e.action {
+                    ctx.set_action(&Action::new(
+                        &task.pid,
+                        &task.id,
+                        action.clone(),
+                        &e.options,
+                    ))?;
+                    task.update(ctx)?;
+                }
+            }
         }
 
         Ok(())
@@ -1,9 +1,11 @@
 use super::hook::TaskLifeCycle;
 use crate::{
-    ActTask, Result,
-    model::Step,
+    ActError, ActTask, Result, Vars,
+    model::{LOOP_MAX_DEFAULT, Step},
     scheduler::{Context, TaskState},
+    utils::{self, consts},
 };
+use serde_json::Value as JsonValue;
 
 impl ActTask for Step {
     fn init(&self, ctx: &Context) -> Result<()> {
@@ -16,6 +18,15 @@ impl ActTask for Step {
             }
         }
 
+        // keep the process vars of this visit to rollback when going back to the step
+        ctx.snapshot_vars(&task)?;
+
+        // validate the step inputs
+        if let Some(schema) = &self.input_schema {
+            let inputs = utils::fill_inputs(&self.inputs, ctx);
+            utils::check_inputs(&format!("step '{}'", self.id), schema, &inputs)?;
+        }
+
         // add catch hooks
         if !self.catches.is_empty() {
             for c in &self.catches {
@@ -30,15 +41,46 @@ impl ActTask for Step {
             }
         }
 
+        // add sla hooks
+        if let Some(sla) = &self.sla {
+            task.add_hook_sla(sla)?;
+        }
+
         // run setup
         if !self.setup.is_empty() {
             ctx.dispatch_acts(self.setup.clone(), true)?;
         }
 
+        // evaluate the switch expression once for the case branches
+        if let Some(expr) = &self.switch {
+            let value = ctx.eval::<JsonValue>(expr)?;
+            // no branch would run without a matched case or a default branch
+            if !self.branches.iter().any(|b| b.default || b.is_case(&value)) {
+                return Err(ActError::Runtime(format!(
+                    "no case matches the switch value '{value}' of step '{}' and no default branch is set",
+                    self.id
+                )));
+            }
+            task.set_data(&Vars::new().with(consts::STEP_SWITCH_VALUE, value));
+        }
+
+        // keep the for_each items for the later iterations
+        if let Some(expr) = &self.for_each {
+            let items = ctx.eval::<Vec<JsonValue>>(expr)?;
+            task.set_data(&Vars::new().with(consts::STEP_LOOP_ITEMS, items));
+        }
+
         Ok(())
     }
 
     fn run(&self, ctx: &Context) -> Result<()> {
+        if self.is_loop() {
+            if self.iterate(ctx, 0)? {
+                self.sched_iteration(ctx);
+            }
+            return Ok(());
+        }
+
         let task = ctx.task();
         let children = task.node.children();
         if !children.is_empty() {
@@ -74,7 +116,22 @@ impl ActTask for Step {
             }
 
             if count == tasks.len() {
+                match self.next_iteration(ctx) {
+                    Ok(true) => return Ok(true),
+                    Ok(false) => {}
+                    Err(err) => {
+                        task.set_err(&err.into());
+                        task.error(ctx)?;
+                        return Ok(false);
+                    }
+                }
+
                 if !task.state().is_completed() {
+                    if let Err(err) = task.check_outputs() {
+                        task.set_err(&err.into());
+                        task.error(ctx)?;
+                        return Ok(false);
+                    }
                     task.set_state(TaskState::Completed);
                 }
 
@@ -114,7 +171,22 @@ impl ActTask for Step {
             }
 
             if count == tasks.len() {
+                match self.next_iteration(ctx) {
+                    Ok(true) => return Ok(false),
+                    Ok(false) => {}
+                    Err(err) => {
+                        task.set_err(&err.into());
+                        task.error(ctx)?;
+                        return Ok(false);
+                    }
+                }
+
                 if !task.state().is_completed() {
+                    if let Err(err) = task.check_outputs() {
+                        task.set_err(&err.into());
+                        task.error(ctx)?;
+                        return Ok(false);
+                    }
                     task.set_state(TaskState::Completed);
                 }
 
@@ -136,3 +208,95 @@ impl ActTask for Step {
         Ok(false)
     }
 }
+
+impl Step {
+    /// check the loop condition and set the iteration vars to the step task
+    /// returns true if the iteration at index should run
+    fn iterate(&self, ctx: &Context, index: u32) -> Result<bool> {
+        let task = ctx.task();
+        if self.for_each.is_some() {
+            let items = task
+                .with_data(|data| data.get::<Vec<JsonValue>>(consts::STEP_LOOP_ITEMS))
+                .unwrap_or_default();
+            return match items.get(index as usize) {
+                Some(value) => {
+                    task.set_data(
+                        &Vars::new()
+                            .with(consts::ACT_INDEX, index)
+                            .with(consts::ACT_VALUE, value),
+                    );
+                    Ok(true)
+                }
+                None => Ok(false),
+            };
+        }
+
+        if let Some(r#loop) = &self.r#loop {
+            if index >= r#loop.max() {
+                // reaching the default max means the loop condition is never met
+                if r#loop.max.is_none() {
+                    return Err(ActError::Runtime(format!(
+                        "the loop of step '{}' reaches the default max {} iterations, set 'max' to allow more",
+                        self.id, LOOP_MAX_DEFAULT
+                    )));
+                }
+                return Ok(false);
+            }
+
+            // the until expression is checked after the last iteration
+            if index > 0
+                && let Some(expr) = &r#loop.until
+                && ctx.eval::<bool>(expr)?
+            {
+                return Ok(false);
+            }
+
+            task.set_data(&Vars::new().with(consts::ACT_INDEX, index));
+            if let Some(expr) = &r#loop.r#while
+                && !ctx.eval::<bool>(expr)?
+            {
+                return Ok(false);
+            }
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    /// schedule the step children for the current iteration
+    /// the iteration vars are set to the child tasks to distinguish each iteration
+    fn sched_iteration(&self, ctx: &Context) {
+        let task = ctx.task();
+        let mut vars = Vars::new();
+        for key in [consts::ACT_INDEX, consts::ACT_VALUE] {
+            if let Some(value) = task.with_data(|data| data.get_value(key).cloned()) {
+                vars.set(key, value);
+            }
+        }
+
+        for child in task.node.children() {
+            let child = ctx.proc.create_task(&child, Some(task.clone()));
+            child.set_data(&vars);
+            ctx.runtime.push(&child);
+        }
+    }
+
+    /// move to the next iteration if the step is a loop
+    /// returns false when the loop is finished
+    fn next_iteration(&self, ctx: &Context) -> Result<bool> {
+        if !self.is_loop() {
+            return Ok(false);
+        }
+
+        let task = ctx.task();
+        let index = task
+            .with_data(|data| data.get::<u32>(consts::ACT_INDEX))
+            .unwrap_or_default();
+        if self.iterate(ctx, index + 1)? {
+            self.sched_iteration(ctx);
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+}
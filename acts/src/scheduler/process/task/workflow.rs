@@ -1,7 +1,10 @@
-use crate::{ActTask, Result, TaskState, Workflow, scheduler::Context};
+use super::hook::TaskLifeCycle;
+use crate::{ActTask, NodeKind, Result, TaskState, Workflow, scheduler::Context};
 
 impl ActTask for Workflow {
     fn init(&self, ctx: &Context) -> Result<()> {
+        let task = ctx.task();
+
         // init process env
         if !self.env.is_empty() {
             ctx.proc.with_env_mut(|data| {
@@ -11,6 +14,16 @@ impl ActTask for Workflow {
             });
         }
 
+        // add catch hooks
+        for c in &self.catches {
+            task.add_hook_catch(TaskLifeCycle::ErrorCatch, c);
+        }
+
+        // add timeout hooks
+        for s in &self.timeout {
+            task.add_hook_timeout(TaskLifeCycle::Timeout, s);
+        }
+
         // run setup
         if !self.setup.is_empty() {
             ctx.dispatch_acts(self.setup.clone(), true)?;
@@ -43,10 +56,37 @@ impl ActTask for Workflow {
         let task = ctx.task();
         let state = task.state();
         if state.is_running() {
+            // wait for the other running steps, such as the steps triggered by the workflow timeout
+            if ctx
+                .proc
+                .tasks()
+                .iter()
+                .any(|t| t.is_kind(NodeKind::Step) && t.state().is_running())
+            {
+                return Ok(false);
+            }
+            if let Err(err) = task.check_outputs() {
+                task.set_err(&err.into());
+                task.error(ctx)?;
+                return Ok(false);
+            }
             task.set_state(TaskState::Completed);
             return Ok(true);
         }
 
         Ok(false)
     }
+
+    fn error(&self, ctx: &Context) -> Result<()> {
+        // the workflow catches should run before the process finishes with error
+        let task = ctx.task();
+        task.run_hooks_by(TaskLifeCycle::ErrorCatch, ctx)?;
+        if !task.state().is_error() {
+            ctx.proc.set_state(task.state());
+            ctx.emit_task(&task)?;
+            return Ok(());
+        }
+
+        ctx.emit_error()
+    }
 }
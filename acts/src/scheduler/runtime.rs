@@ -5,13 +5,20 @@ use super::{Process, Scheduler, Task, TaskState};
 use crate::{
     ActError, Action, Config, Engine, Package, Result, Vars, Workflow,
     cache::Cache,
+    calendar::Calendars,
     data,
     env::Enviroment,
     event::{Emitter, EventAction},
     store::Store,
     utils::{self, consts},
 };
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicI64, Ordering},
+    },
+    time::Duration,
+};
 
 #[derive(Debug, Clone)]
 pub struct Runtime {
@@ -21,6 +28,7 @@ pub struct Runtime {
     cache: Arc<Cache>,
     emitter: Arc<Emitter>,
     package: Arc<Package>,
+    calendars: Arc<Calendars>,
 }
 
 impl Runtime {
@@ -53,6 +61,10 @@ impl Runtime {
         &self.package
     }
 
+    pub fn calendars(&self) -> &Arc<Calendars> {
+        &self.calendars
+    }
+
     pub fn store(&self) -> Arc<Store> {
         self.cache.store().clone()
     }
@@ -67,10 +79,25 @@ impl Runtime {
         !self.scher.is_closed()
     }
 
-    pub fn init(&self, engine: &Engine) {
+    pub fn init(self: &Arc<Self>, engine: &Engine) -> Result<()> {
         self.scher.init(engine);
-        self.cache.init(engine);
+        // the engine can not run without the store, a mem store that fails to open or replay
+        // its journal would otherwise start empty and drop the persisted processes
+        self.cache.init(engine)?;
         self.emitter.init(&engine.runtime());
+
+        // resume the running processes which are reloaded from the persisted mem store
+        if self.config.mem_store().is_some() {
+            self.cache
+                .restore(self, |proc| {
+                    if proc.state().is_none() {
+                        proc.start();
+                    }
+                })
+                .unwrap_or_else(|err| error!("runtime.init restore={}", err));
+        }
+
+        Ok(())
     }
 
     pub fn start(self: &Arc<Self>, model: &Workflow, options: &Vars) -> Result<Arc<Process>> {
@@ -90,6 +117,12 @@ impl Runtime {
 
         let mut w = model.clone();
         w.set_inputs(options);
+        w.check_inputs()?;
+        if let Some(name) = &w.calendar
+            && self.calendars.get(name).is_none()
+        {
+            return Err(ActError::Runtime(format!("cannot find calendar '{name}'")));
+        }
 
         let proc = Process::new(&proc_id, self);
         proc.load(&w)?;
@@ -162,6 +195,7 @@ impl Runtime {
         let cache = Arc::new(Cache::new(config.cache_cap() as usize));
         let emitter = Arc::new(Emitter::new());
         let package = Arc::new(Package::new());
+        let calendars = Arc::new(Calendars::new(config));
         let runtime = Arc::new(Runtime {
             config: Arc::new(config.clone()),
             emitter,
@@ -169,6 +203,7 @@ impl Runtime {
             env,
             cache,
             package,
+            calendars,
         });
 
         runtime.initialize(config);
@@ -199,12 +234,27 @@ impl Runtime {
                             rt.return_to_act(&ppid, &ptid, proc);
                         }
 
-                        if !rt.config.keep_processes() {
+                        // the finished processes will be purged by the retention policy
+                        if !rt.config.keep_processes() && rt.config.retention().is_none() {
                             debug!("remove: {:?}", proc.tasks());
                             cache.remove(proc.id()).unwrap_or_else(|err| {
                                 error!("scher.initialize remove={}", err);
                                 false
                             });
+                        } else {
+                            // the vars snapshots are only used to go back in the running process
+                            for task in proc.tasks() {
+                                if task
+                                    .with_data(|data| data.contains_key(consts::STEP_VARS_SNAPSHOT))
+                                {
+                                    task.set_data_with(|data| {
+                                        data.pop(consts::STEP_VARS_SNAPSHOT);
+                                    });
+                                    cache.upsert(&task).unwrap_or_else(|err| {
+                                        error!("scher.initialize upsert={}", err)
+                                    });
+                                }
+                            }
                         }
 
                         cache
@@ -262,6 +312,10 @@ impl Runtime {
 
             let evt = self.emitter().clone();
             let cache = self.cache.clone();
+            let retention = options.retention().cloned();
+            let last_purge_time = Arc::new(AtomicI64::new(0));
+            let mem_store = options.mem_store().cloned();
+            let last_snapshot_time = Arc::new(AtomicI64::new(utils::time::time_millis()));
             self.emitter().on_tick(move |_| {
                 // do the process tick works
                 for proc in cache.procs().iter() {
@@ -278,6 +332,37 @@ impl Runtime {
                         evt.emit_message(m);
                     },
                 );
+
+                // archive and purge the expired processes
+                if let Some(retention) = &retention {
+                    let now = utils::time::time_millis();
+                    if now - last_purge_time.load(Ordering::Relaxed)
+                        >= retention.interval_secs() * 1000
+                    {
+                        let count = cache.purge(retention, now).unwrap_or_else(|err| {
+                            error!("scher.initialize purge={}", err);
+                            0
+                        });
+                        // go on purging the rest in the next tick when the batch is full
+                        if count < retention.batch_size() {
+                            last_purge_time.store(now, Ordering::Relaxed);
+                        }
+                    }
+                }
+
+                // write the mem store snapshot
+                if let Some(mem_store) = &mem_store {
+                    let now = utils::time::time_millis();
+                    if now - last_snapshot_time.load(Ordering::Relaxed)
+                        >= mem_store.snapshot_interval_secs() * 1000
+                    {
+                        last_snapshot_time.store(now, Ordering::Relaxed);
+                        cache
+                            .store()
+                            .snapshot()
+                            .unwrap_or_else(|err| error!("scher.initialize snapshot={}", err));
+                    }
+                }
             });
 
             let evt = self.emitter().clone();
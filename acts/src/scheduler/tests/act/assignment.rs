@@ -0,0 +1,76 @@
+use crate::{
+    Act, MessageState, TaskAssignment, TaskInfo, Workflow, scheduler::tests::create_proc_signal,
+    utils,
+};
+use serde_json::json;
+
+#[tokio::test]
+async fn sch_act_assignment_message() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_input("initiator", json!("u1"))
+            .with_act(Act::irq(|act| act.with_key("act1")).with_assignment(|a| {
+                a.with_assignee("{{ initiator }}")
+                    .with_candidate_group("managers")
+                    .with_due("1d")
+                    .with_priority(3)
+            }))
+    });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Option<(String, TaskAssignment)>>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            let uid = e.inputs.get::<String>("uid").unwrap_or_default();
+            rx.send(e.assignment().map(|a| (uid, a)));
+        }
+    });
+
+    scher.launch(&proc);
+    let (uid, assignment) = tx.recv().await.unwrap();
+    assert_eq!(uid, "u1");
+    assert_eq!(assignment.assignee.as_deref(), Some("u1"));
+    assert_eq!(assignment.owner, None);
+    assert_eq!(assignment.candidate_groups, ["managers"]);
+    assert_eq!(assignment.priority, 3);
+
+    let task = proc.find_tasks(|t| t.node().key() == "act1").pop().unwrap();
+    let due = assignment.due.unwrap();
+    assert!((due - task.start_time() - 24 * 60 * 60 * 1000).abs() < 1000);
+    assert_eq!(TaskInfo::from(&task).assignment, Some(assignment));
+}
+
+#[tokio::test]
+async fn sch_act_assignment_due_time() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::irq(|act| act.with_key("act1"))
+                .with_assignment(|a| a.with_due("2026-10-01T18:00:00+08:00")),
+        )
+    });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Option<i64>>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            rx.send(e.assignment().and_then(|a| a.due));
+        }
+    });
+
+    scher.launch(&proc);
+    let due = tx.recv().await;
+    let expected = chrono::DateTime::parse_from_rfc3339("2026-10-01T18:00:00+08:00").unwrap();
+    assert_eq!(due, Some(expected.timestamp_millis()));
+}
+
+#[tokio::test]
+async fn sch_act_assignment_not_irq() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::msg(|act| act.with_key("msg1")).with_assignment(|a| a.with_assignee("u1")),
+        )
+    });
+    let (proc, scher, _, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+
+    scher.launch(&proc);
+    tx.recv().await;
+    assert!(proc.state().is_error());
+}
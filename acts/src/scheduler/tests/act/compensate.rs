@@ -0,0 +1,155 @@
+use crate::{
+    Act, Action, MessageState, Step, StmtBuild, Vars, Workflow,
+    event::EventAction,
+    scheduler::TaskState,
+    utils::{self, consts, test::create_proc_signal},
+};
+
+fn create_compensate_workflow() -> Workflow {
+    Workflow::new()
+        .with_step(|step| {
+            step.with_id("step1").with_act(
+                Act::irq(|act| act.with_key("act1"))
+                    .with_compensate(|stmts| stmts.add(Act::msg(|act| act.with_key("undo1")))),
+            )
+        })
+        .with_step(|step| {
+            step.with_id("step2").with_act(
+                Act::irq(|act| act.with_key("act2"))
+                    .with_compensate(|stmts| stmts.add(Act::msg(|act| act.with_key("undo2")))),
+            )
+        })
+        .with_step(|step| {
+            step.with_id("step3").with_act(
+                Act::irq(|act| act.with_key("act3"))
+                    .with_compensate(|stmts| stmts.add(Act::msg(|act| act.with_key("undo3")))),
+            )
+        })
+}
+
+#[tokio::test]
+async fn sch_act_compensate_abort() {
+    let mut workflow = create_compensate_workflow();
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.key.starts_with("undo") {
+            rx.update(|data| data.push(e.key.clone()));
+        }
+        if !e.is_state(MessageState::Created) {
+            return;
+        }
+        if e.is_key("act1") || e.is_key("act2") {
+            let action = Action::new(&e.pid, &e.tid, EventAction::Next, &Vars::new());
+            s.do_action(&action).unwrap();
+        } else if e.is_key("act3") {
+            let action = Action::new(&e.pid, &e.tid, EventAction::Abort, &Vars::new());
+            s.do_action(&action).unwrap();
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret, vec!["undo2".to_string(), "undo1".to_string()]);
+    assert_eq!(proc.state(), TaskState::Aborted);
+}
+
+#[tokio::test]
+async fn sch_act_compensate_error() {
+    let mut workflow = create_compensate_workflow();
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.key.starts_with("undo") {
+            rx.update(|data| data.push(e.key.clone()));
+        }
+        if !e.is_state(MessageState::Created) {
+            return;
+        }
+        if e.is_key("act1") {
+            let action = Action::new(&e.pid, &e.tid, EventAction::Next, &Vars::new());
+            s.do_action(&action).unwrap();
+        } else if e.is_key("act2") {
+            let options = Vars::new().with(consts::ACT_ERR_CODE, "err1");
+            let action = Action::new(&e.pid, &e.tid, EventAction::Error, &options);
+            s.do_action(&action).unwrap();
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret, vec!["undo1".to_string()]);
+    assert_eq!(proc.state(), TaskState::Error);
+}
+
+#[tokio::test]
+async fn sch_act_compensate_task() {
+    let mut workflow = Workflow::new();
+    workflow.steps.push(
+        Step::new()
+            .with_id("step1")
+            .with_act(Act::irq(|act| act.with_key("act1")))
+            .with_compensate(|stmts| stmts.add(Act::irq(|act| act.with_key("undo1")))),
+    );
+    workflow.steps.push(
+        Step::new()
+            .with_id("step2")
+            .with_act(Act::irq(|act| act.with_key("act2"))),
+    );
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if !e.is_state(MessageState::Created) {
+            return;
+        }
+        if e.is_key("act1") || e.is_key("undo1") {
+            rx.update(|data| data.push(e.key.clone()));
+            let action = Action::new(&e.pid, &e.tid, EventAction::Next, &Vars::new());
+            s.do_action(&action).unwrap();
+        } else if e.is_key("act2") {
+            let action = Action::new(&e.pid, &e.tid, EventAction::Abort, &Vars::new());
+            s.do_action(&action).unwrap();
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret, vec!["act1".to_string(), "undo1".to_string()]);
+    assert_eq!(proc.state(), TaskState::Aborted);
+
+    let step1 = proc.task_by_nid("step1").first().unwrap().clone();
+    let compensation = proc
+        .tasks()
+        .into_iter()
+        .find(|t| t.is_compensation())
+        .unwrap();
+    assert_eq!(compensation.state(), TaskState::Completed);
+    assert_eq!(
+        compensation.with_data(|data| data.get::<String>(consts::COMPENSATE_FOR)),
+        Some(step1.id.clone())
+    );
+}
+
+#[tokio::test]
+async fn sch_act_compensate_not_run_on_completed() {
+    let mut workflow = create_compensate_workflow();
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.key.starts_with("undo") {
+            rx.update(|data| data.push(e.key.clone()));
+        } else if e.is_type("act") && e.is_state(MessageState::Created) {
+            let action = Action::new(&e.pid, &e.tid, EventAction::Next, &Vars::new());
+            s.do_action(&action).unwrap();
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert!(ret.is_empty());
+    assert_eq!(proc.state(), TaskState::Completed);
+}
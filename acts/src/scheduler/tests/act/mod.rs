@@ -1,6 +1,9 @@
 mod acts;
+mod assignment;
 mod catch;
+mod compensate;
 mod hooks;
 mod r#if;
 mod setup;
+mod sla;
 mod timeout;
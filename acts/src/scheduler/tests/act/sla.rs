@@ -0,0 +1,165 @@
+use crate::{
+    Act, Action, Calendar, MessageState, TaskInfo, TimeoutLimit, Vars, Workflow,
+    event::EventAction, scheduler::tests::create_proc_signal, utils,
+};
+use serde_json::json;
+
+#[tokio::test]
+async fn sch_act_sla_deadline() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::irq(|act| act.with_key("act1")).with_sla(|sla| sla.with_deadline("1d")))
+    });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Option<i64>>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            rx.send(e.deadline());
+        }
+    });
+
+    scher.launch(&proc);
+    let deadline = tx.recv().await.unwrap();
+    let task = proc.find_tasks(|t| t.node().key() == "act1").pop().unwrap();
+    assert!((deadline - task.start_time() - 24 * 60 * 60 * 1000).abs() < 1000);
+    assert_eq!(TaskInfo::from(&task).deadline, Some(deadline));
+}
+
+#[tokio::test]
+async fn sch_act_sla_business_deadline() {
+    let mut workflow = Workflow::new().with_calendar("all").with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::irq(|act| act.with_key("act1")).with_sla(|sla| sla.with_deadline("2bd")))
+    });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Option<i64>>(&mut workflow, &utils::longid());
+    let calendar = Calendar::new()
+        .with_work_hours("00:00", "23:59")
+        .with_weekends(&[]);
+    scher.calendars().register("all", &calendar);
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            rx.send(e.deadline());
+        }
+    });
+
+    scher.launch(&proc);
+    let deadline = tx.recv().await.unwrap();
+    let task = proc.find_tasks(|t| t.node().key() == "act1").pop().unwrap();
+    let expected = calendar
+        .deadline(task.start_time(), &TimeoutLimit::parse("2bd").unwrap())
+        .unwrap();
+    assert!((deadline - expected).abs() < 1000);
+}
+
+#[tokio::test]
+async fn sch_act_sla_escalations() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::irq(|act| act.with_key("act1")).with_sla(|sla| {
+                sla.with_escalation(|e| e.with_on("1s").with_msg("remind"))
+                    .with_escalation(|e| {
+                        e.with_on("2s")
+                            .with_action(EventAction::Next)
+                            .with_options(Vars::new().with("approved", true))
+                    })
+            }))
+    });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("remind") {
+            rx.update(|data| data.push(e.key.clone()));
+        }
+        if e.is_key("act1") && e.is_state(MessageState::Completed) {
+            rx.update(|data| data.push(e.key.clone()));
+        }
+    });
+
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    assert_eq!(ret, ["remind", "act1"]);
+    assert!(proc.state().is_completed());
+}
+
+#[tokio::test]
+async fn sch_act_sla_reassign() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::irq(|act| act.with_key("act1").with_input("uid", json!("u1")))
+                .with_sla(|sla| sla.with_escalation(|e| e.with_on("1s").with_reassign("u2"))),
+        )
+    });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            let uid = e.inputs.get::<String>("uid").unwrap_or_default();
+            rx.update(|data| data.push(uid.clone()));
+            if uid == "u2" {
+                let action = Action::new(&e.pid, &e.tid, EventAction::Next, &Vars::new());
+                s.do_action(&action).unwrap();
+            }
+        }
+    });
+
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    assert_eq!(ret, ["u1", "u2"]);
+}
+
+#[tokio::test]
+async fn sch_act_sla_completed() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::irq(|act| act.with_key("act1"))
+                .with_sla(|sla| sla.with_escalation(|e| e.with_on("1s").with_msg("remind"))),
+        )
+    });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<bool>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            let action = Action::new(&e.pid, &e.tid, EventAction::Next, &Vars::new());
+            s.do_action(&action).unwrap();
+        }
+        if e.is_key("remind") {
+            rx.send(true);
+        }
+    });
+
+    scher.launch(&proc);
+    let ret = tx.timeout(2000).await;
+    assert!(!ret);
+    assert!(proc.state().is_completed());
+}
+
+#[tokio::test]
+async fn sch_act_sla_restore() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(
+            Act::irq(|act| act.with_key("act1"))
+                .with_sla(|sla| sla.with_escalation(|e| e.with_on("1s").with_msg("remind"))),
+        )
+    });
+    let (proc, rt, emitter, tx, rx) = create_proc_signal::<bool>(&mut workflow, &utils::longid());
+    let cache = rt.cache().clone();
+    let pid = proc.id().to_string();
+    let rt2 = rt.clone();
+    emitter.on_message(move |e| {
+        // reload the process from store before the escalation
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            cache.uncache(&pid);
+            cache.restore(&rt2, |_| {}).unwrap();
+        }
+        if e.is_key("remind") {
+            rx.send(true);
+        }
+    });
+
+    rt.launch(&proc);
+    let ret = tx.recv().await;
+    assert!(ret);
+}
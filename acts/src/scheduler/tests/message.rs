@@ -509,6 +509,245 @@ async fn sch_message_act_back() {
     assert!(ret);
 }
 
+fn back_workflow() -> Workflow {
+    Workflow::new()
+        .with_input("amount", json!(0))
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_act(Act::irq(|act| act.with_key("act1")))
+        })
+        .with_step(|step| {
+            step.with_id("step2")
+                .with_act(Act::irq(|act| act.with_key("act2")))
+        })
+        .with_step(|step| {
+            step.with_id("step3")
+                .with_act(Act::irq(|act| act.with_key("act3")))
+        })
+}
+
+/// go back from 'act3' to 'step1' with the back options
+async fn run_back(back: Vars) -> (Vec<String>, Vars) {
+    let mut workflow = back_workflow();
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    let count = Arc::new(Mutex::new(0));
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_state(MessageState::Backed) {
+            rx.update(|data| data.push(e.key.clone()));
+        }
+        if !e.is_state(MessageState::Created) {
+            return;
+        }
+        if e.is_key("act1") {
+            let mut count = count.lock().unwrap();
+            *count += 1;
+            if *count > 1 {
+                rx.close();
+                return;
+            }
+            let options = Vars::new().with("amount", 10);
+            let action = Action::new(&e.pid, &e.tid, EventAction::Next, &options);
+            s.do_action(&action).unwrap();
+        } else if e.is_key("act2") {
+            let options = Vars::new().with("amount", 20);
+            let action = Action::new(&e.pid, &e.tid, EventAction::Next, &options);
+            s.do_action(&action).unwrap();
+        } else if e.is_key("act3") {
+            let action = Action::new(&e.pid, &e.tid, EventAction::Back, &back);
+            s.do_action(&action).unwrap();
+        }
+    });
+    scher.launch(&proc);
+    let mut ret = tx.recv().await;
+    ret.sort();
+    (ret, proc.data())
+}
+
+#[tokio::test]
+async fn sch_message_act_back_to_step() {
+    let (backs, data) = run_back(Vars::new().with("to", "step1")).await;
+    assert_eq!(backs, ["act2", "act3", "step2", "step3"]);
+    assert_eq!(data.get::<i64>("amount"), Some(20));
+}
+
+#[tokio::test]
+async fn sch_message_act_back_rollback_all() {
+    let (_, data) = run_back(Vars::new().with("to", "step1").with("rollback", true)).await;
+    assert_eq!(data.get::<i64>("amount"), Some(0));
+}
+
+#[tokio::test]
+async fn sch_message_act_back_rollback_names() {
+    let (_, data) = run_back(
+        Vars::new()
+            .with("to", "step1")
+            .with("rollback", json!(["not_exists"])),
+    )
+    .await;
+    assert_eq!(data.get::<i64>("amount"), Some(20));
+
+    let (_, data) = run_back(
+        Vars::new()
+            .with("to", "step1")
+            .with("rollback", json!(["amount"])),
+    )
+    .await;
+    assert_eq!(data.get::<i64>("amount"), Some(0));
+}
+
+#[tokio::test]
+async fn sch_message_act_back_rollback_reentry() {
+    let mut workflow = back_workflow();
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<i64>>(&mut workflow, &utils::longid());
+    let count = Arc::new(Mutex::new(0));
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if !e.is_state(MessageState::Created) {
+            return;
+        }
+        let mut count = count.lock().unwrap();
+        if e.is_key("act1") {
+            *count += 1;
+            if *count > 2 {
+                rx.close();
+                return;
+            }
+            let options = Vars::new().with("amount", *count * 10);
+            let action = Action::new(&e.pid, &e.tid, EventAction::Next, &options);
+            s.do_action(&action).unwrap();
+        } else if e.is_key("act2") {
+            let options = Vars::new().with("amount", *count * 10 + 5);
+            let action = Action::new(&e.pid, &e.tid, EventAction::Next, &options);
+            s.do_action(&action).unwrap();
+        } else if e.is_key("act3") {
+            // the first back keeps the vars and the second one rolls back to the second visit
+            let mut options = Vars::new().with("to", "step1");
+            if *count > 1 {
+                options.set("rollback", true);
+            }
+            rx.update(|data| data.push(*count));
+            let action = Action::new(&e.pid, &e.tid, EventAction::Back, &options);
+            s.do_action(&action).unwrap();
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    assert_eq!(ret, [1, 2]);
+    // the snapshot of the second visit is taken after the first back
+    assert_eq!(proc.data().get::<i64>("amount"), Some(15));
+}
+
+#[tokio::test]
+async fn sch_message_act_back_sibling_branch_completed() {
+    let mut workflow = Workflow::new()
+        .with_step(|step| {
+            step.with_id("step0")
+                .with_act(Act::irq(|act| act.with_key("act0")))
+        })
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_branch(|b| {
+                    b.with_id("b1").with_if("true").with_step(|step| {
+                        step.with_id("s1")
+                            .with_act(Act::irq(|act| act.with_key("a1")))
+                    })
+                })
+                .with_branch(|b| {
+                    b.with_id("b2").with_if("true").with_step(|step| {
+                        step.with_id("s2")
+                            .with_act(Act::irq(|act| act.with_key("a2")))
+                    })
+                })
+        });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    let count = Arc::new(Mutex::new(0));
+    // the a1 completed flag and the a2 task id
+    let state = Arc::new(Mutex::new((false, None::<String>)));
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_state(MessageState::Backed) {
+            rx.update(|data| data.push(e.key.clone()));
+        }
+
+        let mut state = state.lock().unwrap();
+        if e.is_key("act0") && e.is_state(MessageState::Created) {
+            let mut count = count.lock().unwrap();
+            *count += 1;
+            if *count > 1 {
+                rx.close();
+                return;
+            }
+            let action = Action::new(&e.pid, &e.tid, EventAction::Next, &Vars::new());
+            s.do_action(&action).unwrap();
+        } else if e.is_key("a1") && e.is_state(MessageState::Created) {
+            let action = Action::new(&e.pid, &e.tid, EventAction::Next, &Vars::new());
+            s.do_action(&action).unwrap();
+        } else if e.is_key("a1") && e.is_state(MessageState::Completed) {
+            state.0 = true;
+        } else if e.is_key("a2") && e.is_state(MessageState::Created) {
+            state.1 = Some(e.tid.clone());
+        }
+
+        // go back when the sibling branch is completed
+        if state.0
+            && let Some(tid) = state.1.take()
+        {
+            let options = Vars::new().with("to", "step0");
+            let action = Action::new(&e.pid, &tid, EventAction::Back, &options);
+            s.do_action(&action).unwrap();
+        }
+    });
+    scher.launch(&proc);
+    let backs = tx.recv().await;
+    assert!(backs.contains(&"a2".to_string()));
+    assert!(backs.contains(&"s2".to_string()));
+    assert!(backs.contains(&"step1".to_string()));
+    for key in ["a1", "s1", "b1"] {
+        assert!(!backs.contains(&key.to_string()));
+    }
+    let b1 = proc.task_by_nid("b1").first().unwrap().clone();
+    assert_eq!(b1.state(), crate::TaskState::Completed);
+}
+
+#[tokio::test]
+async fn sch_message_act_back_error() {
+    let mut workflow = back_workflow();
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<bool>>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if !e.is_state(MessageState::Created) {
+            return;
+        }
+        if e.is_key("act1") {
+            let action = Action::new(&e.pid, &e.tid, EventAction::Next, &Vars::new());
+            s.do_action(&action).unwrap();
+        } else if e.is_key("act2") {
+            let errs = [
+                // not a step
+                Vars::new().with("to", "not_exists"),
+                // not in the executed path
+                Vars::new().with("to", "step3"),
+                Vars::new().with("to", "step1").with("rollback", "abc"),
+            ]
+            .iter()
+            .map(|options| {
+                let action = Action::new(&e.pid, &e.tid, EventAction::Back, options);
+                s.do_action(&action).is_err()
+            })
+            .collect::<Vec<_>>();
+            rx.send(errs);
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    assert_eq!(ret, [true, true, true]);
+}
+
 #[tokio::test]
 async fn sch_message_act_cancel() {
     let act_req_id = Arc::new(Mutex::new(None));
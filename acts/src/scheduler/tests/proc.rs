@@ -1,5 +1,6 @@
 use crate::{
-    Workflow,
+    Act, Action, Branch, MessageState, Step, Vars, Workflow,
+    event::EventAction,
     scheduler::{
         NodeTree, TaskState,
         tests::{create_proc, create_proc_signal},
@@ -65,3 +66,257 @@ async fn sch_proc_task() {
     let task = proc.create_task(node, None);
     assert!(proc.task(&task.id).is_some())
 }
+
+#[tokio::test]
+async fn sch_proc_to_mermaid() {
+    let mut workflow = Workflow::new()
+        .with_step(|step| step.with_id("step1"))
+        .with_step(|step| {
+            step.with_id("step2")
+                .with_act(Act::irq(|act| act.with_key("act1")).with_id("act1"))
+        });
+    let (proc, scher, .., tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    scher.launch(&proc);
+    let proc2 = proc.clone();
+    tx.timeout(200).await;
+    rx.close();
+
+    let text = proc2.to_mermaid();
+    assert!(text.contains("classDef completed fill:#d3f9d8,stroke:#2b8a3e"));
+    assert!(text.contains("classDef running"));
+    assert!(text.contains("classDef interrupted"));
+    assert!(text.contains("class n1 completed"));
+}
+
+#[tokio::test]
+async fn sch_proc_to_dot() {
+    let mut workflow = Workflow::new().with_step(|step| step.with_id("step1"));
+    let (proc, scher, .., tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    scher.launch(&proc);
+    tx.recv().await;
+
+    let text = proc.to_dot();
+    assert!(text.contains(
+        r##"n1 [label="step1" shape=box fillcolor="#d3f9d8" color="#2b8a3e" style="filled"];"##
+    ));
+}
+
+#[tokio::test]
+async fn sch_proc_patch_keep_running_node() {
+    let mut workflow = patch_workflow();
+    let ret = run_patch(&mut workflow, |proc| {
+        let task = proc.task_by_nid("step1").first().unwrap().clone();
+        proc.insert_step(
+            "step1",
+            &Step::new()
+                .with_id("step4")
+                .with_act(Act::msg(|act| act.with_key("msg4"))),
+        )?;
+
+        // the running task node is the one in the tree and linked to the new step
+        let node = proc.node("step1").unwrap();
+        assert!(std::sync::Arc::ptr_eq(task.node(), &node));
+        let next = node.next().upgrade().unwrap();
+        assert_eq!(next.id(), "step4");
+        assert!(std::sync::Arc::ptr_eq(
+            &next.prev().upgrade().unwrap(),
+            &node
+        ));
+        Ok(())
+    })
+    .await;
+    assert_eq!(ret, ["msg4", "msg2", "msg3"]);
+}
+
+#[tokio::test]
+async fn sch_proc_patch_concurrent() {
+    let mut workflow = patch_workflow();
+    let ret = run_patch(&mut workflow, |proc| {
+        std::thread::scope(|s| {
+            for i in 0..4 {
+                s.spawn(move || {
+                    proc.insert_branch(
+                        "step2",
+                        &Branch::new().with_id(&format!("b{i}")).with_if("false"),
+                    )
+                    .unwrap();
+                });
+            }
+        });
+
+        // all of the patches are applied
+        let model = proc.model();
+        let step = model.step("step2").unwrap();
+        assert_eq!(step.branches.len(), 4);
+        Ok(())
+    })
+    .await;
+    assert_eq!(ret, ["msg2", "msg3"]);
+}
+
+#[tokio::test]
+async fn sch_proc_patch_started_node_error() {
+    let mut workflow = patch_workflow();
+    let ret = run_patch(&mut workflow, |proc| {
+        let mut model = proc.model();
+        let step = model.steps.iter_mut().find(|s| s.id == "step1").unwrap();
+        step.r#if = Some("false".to_string());
+        step.next = Some("step3".to_string());
+
+        let keeps = proc
+            .tasks()
+            .iter()
+            .map(|t| t.node().id().to_string())
+            .collect();
+        let ret = proc.tree_mut().patch(&model, &keeps);
+        assert!(matches!(ret, Err(crate::ActError::Action(msg)) if msg.contains("'step1'")));
+
+        // the links of the started node can still be changed
+        let mut model = proc.model();
+        let step = model.steps.iter_mut().find(|s| s.id == "step1").unwrap();
+        step.next = Some("step3".to_string());
+        proc.tree_mut().patch(&model, &keeps)
+    })
+    .await;
+    assert_eq!(ret, ["msg3"]);
+}
+
+/// run the workflow and patch the process when the irq 'act1' is created
+async fn run_patch<F>(workflow: &mut Workflow, patch: F) -> Vec<String>
+where
+    F: Fn(&std::sync::Arc<crate::scheduler::Process>) -> crate::Result<()> + Send + Sync + 'static,
+{
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(workflow, &utils::longid());
+    let s = scher.clone();
+    let p = proc.clone();
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            patch(&p).unwrap();
+            let action = Action::new(&e.pid, &e.tid, EventAction::Next, &Vars::new());
+            s.do_action(&action).unwrap();
+        }
+        if e.is_key("msg2") || e.is_key("msg3") || e.is_key("msg4") {
+            rx.update(|data| data.push(e.key.clone()));
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await
+}
+
+fn patch_workflow() -> Workflow {
+    Workflow::new()
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_act(Act::irq(|act| act.with_key("act1")))
+        })
+        .with_step(|step| {
+            step.with_id("step2")
+                .with_act(Act::msg(|act| act.with_key("msg2")))
+        })
+        .with_step(|step| {
+            step.with_id("step3")
+                .with_act(Act::msg(|act| act.with_key("msg3")))
+        })
+}
+
+#[tokio::test]
+async fn sch_proc_insert_step() {
+    let mut workflow = patch_workflow();
+    let ret = run_patch(&mut workflow, |proc| {
+        proc.insert_step(
+            "step1",
+            &Step::new()
+                .with_id("step4")
+                .with_act(Act::msg(|act| act.with_key("msg4"))),
+        )
+    })
+    .await;
+    assert_eq!(ret, ["msg4", "msg2", "msg3"]);
+}
+
+#[tokio::test]
+async fn sch_proc_remove_step() {
+    let mut workflow = patch_workflow();
+    let ret = run_patch(&mut workflow, |proc| proc.remove_step("step2")).await;
+    assert_eq!(ret, ["msg3"]);
+}
+
+#[tokio::test]
+async fn sch_proc_remove_step_moves_next() {
+    let mut workflow = patch_workflow().with_step(|step| {
+        step.with_id("step4")
+            .with_act(Act::msg(|act| act.with_key("msg4")))
+    });
+    workflow.steps[0].next = Some("step2".to_string());
+    workflow.steps[1].next = Some("step4".to_string());
+    let ret = run_patch(&mut workflow, |proc| {
+        proc.remove_step("step2")?;
+        let model = proc.model();
+        assert_eq!(model.steps[0].next.as_deref(), Some("step4"));
+        Ok(())
+    })
+    .await;
+    assert_eq!(ret, ["msg4"]);
+}
+
+#[tokio::test]
+async fn sch_proc_remove_step_moves_next_to_following() {
+    let mut workflow = patch_workflow();
+    workflow.steps[0].next = Some("step2".to_string());
+    let ret = run_patch(&mut workflow, |proc| proc.remove_step("step2")).await;
+    assert_eq!(ret, ["msg3"]);
+}
+
+#[tokio::test]
+async fn sch_proc_remove_step_next_ref_error() {
+    let mut workflow = patch_workflow();
+    workflow.steps[0].next = Some("step3".to_string());
+    let ret = run_patch(&mut workflow, |proc| {
+        // there is no step after the last step to move the 'next' to
+        assert!(proc.remove_step("step3").is_err());
+        assert!(proc.node("step3").is_some());
+        Ok(())
+    })
+    .await;
+    assert_eq!(ret, ["msg3"]);
+}
+
+#[tokio::test]
+async fn sch_proc_set_next() {
+    let mut workflow = patch_workflow();
+    let ret = run_patch(&mut workflow, |proc| proc.set_next("step1", "step3")).await;
+    assert_eq!(ret, ["msg3"]);
+}
+
+#[tokio::test]
+async fn sch_proc_insert_branch() {
+    let mut workflow = patch_workflow();
+    let ret = run_patch(&mut workflow, |proc| {
+        proc.insert_branch(
+            "step2",
+            &Branch::new()
+                .with_id("b1")
+                .with_if("true")
+                .with_step(|step| {
+                    step.with_id("step4")
+                        .with_act(Act::msg(|act| act.with_key("msg4")))
+                }),
+        )
+    })
+    .await;
+    assert_eq!(ret, ["msg2", "msg4", "msg3"]);
+}
+
+#[tokio::test]
+async fn sch_proc_remove_started_step_error() {
+    let mut workflow = patch_workflow();
+    let ret = run_patch(&mut workflow, |proc| {
+        assert!(proc.remove_step("step1").is_err());
+        assert!(proc.set_next("step1", "not_exists").is_err());
+        assert!(proc.node("step2").is_some());
+        Ok(())
+    })
+    .await;
+    assert_eq!(ret, ["msg2", "msg3"]);
+}
@@ -1,5 +1,8 @@
 mod acts;
 mod catch;
 mod hooks;
+mod inputs;
+mod loops;
 mod setup;
+mod sla;
 mod timeout;
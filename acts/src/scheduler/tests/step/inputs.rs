@@ -0,0 +1,52 @@
+use crate::{Act, TaskState, Workflow, scheduler::tests::create_proc_signal, utils};
+use serde_json::json;
+
+#[tokio::test]
+async fn sch_step_input_schema_ok() {
+    let mut workflow = Workflow::new()
+        .with_input("amount", json!(100))
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_input("amount", json!("{{ amount }}"))
+                .with_input_schema(json!({
+                    "type": "object",
+                    "properties": { "amount": { "type": "number" } },
+                    "required": ["amount"]
+                }))
+                .with_act(Act::msg(|act| act.with_key("msg1")))
+        });
+    let (proc, scher, _, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(proc.state(), TaskState::Completed);
+}
+
+#[tokio::test]
+async fn sch_step_input_schema_error() {
+    let mut workflow = Workflow::new()
+        .with_input("amount", json!("abc"))
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_input("amount", json!("{{ amount }}"))
+                .with_input_schema(json!({
+                    "type": "object",
+                    "properties": { "amount": { "type": "number" } },
+                    "required": ["amount"]
+                }))
+                .with_act(Act::msg(|act| act.with_key("msg1")))
+        });
+    let (proc, scher, _, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(proc.state(), TaskState::Error);
+    let step1 = proc.task_by_nid("step1").first().unwrap().clone();
+    assert!(
+        step1
+            .err()
+            .unwrap()
+            .message
+            .contains("invalid inputs of step 'step1'")
+    );
+}
@@ -0,0 +1,207 @@
+use serde_json::json;
+
+use crate::event::EventAction;
+use crate::{
+    Act, Message, MessageState, TaskInfo, Vars, Workflow,
+    model::LOOP_MAX_DEFAULT,
+    scheduler::tests::create_proc_signal,
+    utils::{self, consts},
+};
+
+#[tokio::test]
+async fn sch_step_loop_for_each() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_for_each(r#"["a", "b", "c"]"#)
+            .with_act(Act::msg(|msg| {
+                msg.with_id("msg1")
+                    .with_key("msg1")
+                    .with_input("v", "{{ $value }}")
+            }))
+    });
+
+    workflow.print();
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<Message>>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_msg() {
+            rx.update(|data| data.push(e.inner().clone()));
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret.len(), 3);
+    assert_eq!(
+        ret.iter()
+            .map(|m| m.inputs.get_value("v").cloned().unwrap())
+            .collect::<Vec<_>>(),
+        vec![json!("a"), json!("b"), json!("c")]
+    );
+
+    let mut indexes = proc
+        .task_by_nid("msg1")
+        .iter()
+        .map(|t| TaskInfo::from(t).index.unwrap())
+        .collect::<Vec<_>>();
+    indexes.sort();
+    assert_eq!(indexes, vec![0, 1, 2]);
+}
+
+#[tokio::test]
+async fn sch_step_loop_for_each_empty() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_for_each("[]")
+            .with_act(Act::msg(|msg| msg.with_key("msg1")))
+    });
+
+    workflow.print();
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<Message>>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_msg() {
+            rx.update(|data| data.push(e.inner().clone()));
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert!(ret.is_empty());
+    assert!(proc.state().is_completed());
+}
+
+#[tokio::test]
+async fn sch_step_loop_while() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_loop(|l| l.with_while("$index < 3"))
+            .with_act(Act::msg(|msg| msg.with_key("msg1")))
+    });
+
+    workflow.print();
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<Message>>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_msg() {
+            rx.update(|data| data.push(e.inner().clone()));
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret.len(), 3);
+}
+
+#[tokio::test]
+async fn sch_step_loop_until() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_loop(|l| l.with_until("$index >= 1"))
+            .with_act(Act::msg(|msg| msg.with_key("msg1")))
+    });
+
+    workflow.print();
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<Message>>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_msg() {
+            rx.update(|data| data.push(e.inner().clone()));
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret.len(), 2);
+}
+
+#[tokio::test]
+async fn sch_step_loop_max() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_loop(|l| l.with_while("true").with_max(5))
+            .with_act(Act::msg(|msg| msg.with_key("msg1")))
+    });
+
+    workflow.print();
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<Message>>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_msg() {
+            rx.update(|data| data.push(e.inner().clone()));
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret.len(), 5);
+}
+
+#[tokio::test]
+async fn sch_step_loop_default_max_error() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_loop(|l| l.with_while("true"))
+            .with_act(Act::msg(|msg| msg.with_key("msg1")))
+    });
+
+    workflow.print();
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<Message>>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_msg() {
+            rx.update(|data| data.push(e.inner().clone()));
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    assert_eq!(ret.len(), LOOP_MAX_DEFAULT as usize);
+    assert!(proc.state().is_error());
+    let step1 = proc.task_by_nid("step1").first().unwrap().clone();
+    assert!(
+        step1
+            .err()
+            .unwrap()
+            .message
+            .contains("reaches the default max")
+    );
+}
+
+#[tokio::test]
+async fn sch_step_loop_irq_acts() {
+    let mut workflow = Workflow::new()
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_for_each("[1, 2]")
+                .with_act(Act::irq(|act| act.with_key("act1")))
+                .with_act(Act::irq(|act| act.with_key("act2")))
+        })
+        .with_step(|step| step.with_id("step2"));
+
+    workflow.print();
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<Message>>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_type("act") && e.is_state(MessageState::Created) {
+            rx.update(|data| data.push(e.inner().clone()));
+            e.do_action(&e.pid, &e.tid, EventAction::Next, &Vars::new())
+                .unwrap();
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(
+        ret.iter().map(|m| m.key.clone()).collect::<Vec<_>>(),
+        vec!["act1", "act2", "act1", "act2"]
+    );
+    assert_eq!(proc.task_by_nid("step2").len(), 1);
+    assert_eq!(
+        proc.task_by_nid("step1")
+            .first()
+            .unwrap()
+            .data()
+            .get::<u32>(consts::ACT_INDEX),
+        Some(1)
+    );
+}
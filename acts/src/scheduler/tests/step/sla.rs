@@ -0,0 +1,27 @@
+use crate::{Act, MessageState, Workflow, scheduler::tests::create_proc_signal, utils};
+
+#[tokio::test]
+async fn sch_step_sla_escalation() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::irq(|act| act.with_key("act1")))
+            .with_sla(|sla| {
+                sla.with_deadline("1h")
+                    .with_escalation(|e| e.with_on("1s").with_msg("remind"))
+            })
+    });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Option<i64>>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("step1") && e.is_state(MessageState::Created) {
+            rx.update(|data| *data = e.deadline());
+        }
+        if e.is_key("remind") {
+            rx.close();
+        }
+    });
+
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    assert!(ret.is_some());
+}
@@ -1,4 +1,12 @@
-use crate::{Act, Message, Workflow, scheduler::tests::create_proc_signal, utils};
+use crate::{Act, Clock, Message, Workflow, scheduler::tests::create_proc_signal, utils};
+use std::sync::Arc;
+
+struct FixedClock(i64);
+impl Clock for FixedClock {
+    fn now(&self) -> i64 {
+        self.0
+    }
+}
 
 #[tokio::test]
 async fn sch_step_timeout_one() {
@@ -64,3 +72,56 @@ async fn sch_step_timeout_many() {
     proc.print();
     assert_eq!(ret.len(), 2)
 }
+
+#[tokio::test]
+async fn sch_step_timeout_business_days() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_timeout(|t| {
+                t.with_on("1bd").with_step(|step| {
+                    step.with_id("step2")
+                        .with_act(Act::msg(|msg| msg.with_key("msg1")))
+                })
+            })
+            .with_act(Act::irq(|act| act.with_key("act1")))
+    });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<bool>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("msg1") {
+            rx.send(true);
+        }
+    });
+
+    // one business day is less than five days even with the weekend
+    let now = utils::time::time_millis() + 5 * 24 * 60 * 60 * 1000;
+    scher.calendars().set_clock(Arc::new(FixedClock(now)));
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    assert!(ret)
+}
+
+#[tokio::test]
+async fn sch_step_timeout_business_days_not_reached() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_timeout(|t| {
+                t.with_on("1bd").with_step(|step| {
+                    step.with_id("step2")
+                        .with_act(Act::msg(|msg| msg.with_key("msg1")))
+                })
+            })
+            .with_act(Act::irq(|act| act.with_key("act1")))
+    });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<bool>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("msg1") {
+            rx.send(true);
+        }
+    });
+
+    scher.launch(&proc);
+    let ret = tx.timeout(2000).await;
+    assert!(!ret)
+}
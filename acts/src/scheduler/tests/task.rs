@@ -441,3 +441,152 @@ async fn sch_task_branch_needs_state() {
         TaskState::Pending
     );
 }
+
+#[tokio::test]
+async fn sch_task_branch_switch_case() {
+    let mut workflow = Workflow::new()
+        .with_input("v", "b".into())
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_switch("v")
+                .with_branch(|branch| {
+                    branch
+                        .with_id("b1")
+                        .with_case("a")
+                        .with_step(|step| step.with_id("step11"))
+                })
+                .with_branch(|branch| {
+                    branch
+                        .with_id("b2")
+                        .with_case("b")
+                        .with_step(|step| step.with_id("step21"))
+                })
+                .with_branch(|branch| {
+                    branch
+                        .with_id("b3")
+                        .with_default(true)
+                        .with_step(|step| step.with_id("step31"))
+                })
+        });
+
+    let id = utils::longid();
+    let (proc, scher, _, tx, _) = create_proc_signal::<()>(&mut workflow, &id);
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(
+        proc.task_by_nid("b1").first().unwrap().state(),
+        TaskState::Skipped
+    );
+    assert_eq!(
+        proc.task_by_nid("b2").first().unwrap().state(),
+        TaskState::Completed
+    );
+    assert_eq!(
+        proc.task_by_nid("b3").first().unwrap().state(),
+        TaskState::Skipped
+    );
+}
+
+#[tokio::test]
+async fn sch_task_branch_switch_number_case() {
+    let mut workflow = Workflow::new().with_input("v", 1.into()).with_step(|step| {
+        step.with_id("step1")
+            .with_switch("v + 1")
+            .with_branch(|branch| {
+                branch
+                    .with_id("b1")
+                    .with_case(1)
+                    .with_step(|step| step.with_id("step11"))
+            })
+            .with_branch(|branch| {
+                branch
+                    .with_id("b2")
+                    .with_case(2)
+                    .with_step(|step| step.with_id("step21"))
+            })
+    });
+
+    let id = utils::longid();
+    let (proc, scher, _, tx, _) = create_proc_signal::<()>(&mut workflow, &id);
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(
+        proc.task_by_nid("b1").first().unwrap().state(),
+        TaskState::Skipped
+    );
+    assert_eq!(
+        proc.task_by_nid("b2").first().unwrap().state(),
+        TaskState::Completed
+    );
+}
+
+#[tokio::test]
+async fn sch_task_branch_switch_default() {
+    let mut workflow = Workflow::new()
+        .with_input("v", "c".into())
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_switch("v")
+                .with_branch(|branch| {
+                    branch
+                        .with_id("b1")
+                        .with_default(true)
+                        .with_step(|step| step.with_id("step11"))
+                })
+                .with_branch(|branch| {
+                    branch
+                        .with_id("b2")
+                        .with_case("a")
+                        .with_step(|step| step.with_id("step21"))
+                })
+        });
+
+    let id = utils::longid();
+    let (proc, scher, _, tx, _) = create_proc_signal::<()>(&mut workflow, &id);
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(
+        proc.task_by_nid("b1").first().unwrap().state(),
+        TaskState::Completed
+    );
+    assert_eq!(
+        proc.task_by_nid("b2").first().unwrap().state(),
+        TaskState::Skipped
+    );
+}
+
+#[tokio::test]
+async fn sch_task_branch_switch_no_match() {
+    let mut workflow = Workflow::new()
+        .with_input("v", "c".into())
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_switch("v")
+                .with_branch(|branch| {
+                    branch
+                        .with_id("b1")
+                        .with_case("a")
+                        .with_step(|step| step.with_id("step11"))
+                })
+        })
+        .with_step(|step| step.with_id("step2"));
+
+    let id = utils::longid();
+    let (proc, scher, _, tx, _) = create_proc_signal::<()>(&mut workflow, &id);
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(proc.state(), TaskState::Error);
+    let step1 = proc.task_by_nid("step1").first().unwrap().clone();
+    assert!(
+        step1
+            .err()
+            .unwrap()
+            .message
+            .contains("no case matches the switch value")
+    );
+    assert!(proc.task_by_nid("step2").is_empty());
+}
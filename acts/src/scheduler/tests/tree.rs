@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use crate::{
-    Act, NodeKind, Workflow,
+    Act, ActError, NodeKind, Workflow,
     scheduler::{
         Node,
         tree::{NodeContent, NodeTree},
@@ -319,3 +319,179 @@ async fn sch_tree_node_act2_ser_de() {
     // not care the parent for act when resume data from string
     assert!(act.parent().is_none());
 }
+
+#[tokio::test]
+async fn sch_tree_step_loop_and_for_each_error() {
+    let mut workflow = Workflow::new().with_id("w1").with_step(|step| {
+        step.with_id("step1")
+            .with_for_each("[1, 2]")
+            .with_loop(|l| l.with_max(2))
+    });
+    let tree = NodeTree::build(&mut workflow).unwrap();
+    assert!(tree.error.is_some());
+}
+
+#[tokio::test]
+async fn sch_tree_step_loop_max_error() {
+    let mut workflow = Workflow::new()
+        .with_id("w1")
+        .with_step(|step| step.with_id("step1").with_loop(|l| l.with_max(0)));
+    let tree = NodeTree::build(&mut workflow).unwrap();
+    assert!(tree.error.is_some());
+}
+
+#[tokio::test]
+async fn sch_tree_next_error_location() {
+    let text = r#"
+id: m1
+steps:
+  - id: step1
+  - id: step2
+    next: step3
+"#;
+    let model = Workflow::from_yml(text).unwrap();
+    let mut tree = NodeTree::new();
+    tree.load(&model).unwrap();
+    let Some(ActError::Model(err)) = tree.error else {
+        panic!("expect model error");
+    };
+    assert_eq!(err.node.as_deref(), Some("step2"));
+    assert_eq!(err.path, "steps[1].next");
+    assert_eq!(err.line, Some(6));
+    assert_eq!(err.column, Some(11));
+}
+
+#[tokio::test]
+async fn sch_tree_next_forward() {
+    let mut workflow = Workflow::new()
+        .with_id("w1")
+        .with_step(|step| step.with_id("step1").with_next("step3"))
+        .with_step(|step| step.with_id("step2"))
+        .with_step(|step| step.with_id("step3"));
+    let tree = NodeTree::build(&mut workflow).unwrap();
+    assert!(tree.error.is_none());
+    let step1 = tree.node("step1").unwrap();
+    assert_eq!(step1.next().upgrade().unwrap().id(), "step3");
+}
+
+#[tokio::test]
+async fn sch_tree_step_loop_max_error_location() {
+    let text = r#"
+id: m1
+steps:
+  - loop:
+      max: 0
+"#;
+    let mut model = Workflow::from_yml(text).unwrap();
+    let tree = NodeTree::build(&mut model).unwrap();
+    let Some(ActError::Model(err)) = tree.error else {
+        panic!("expect model error");
+    };
+    assert_eq!(err.node.as_ref(), Some(&model.steps[0].id));
+    assert_eq!(err.path, "steps[0].loop.max");
+    assert_eq!(err.line, Some(5));
+}
+
+#[tokio::test]
+async fn sch_tree_switch_error_location() {
+    let text = r#"
+id: m1
+steps:
+  - id: step1
+    switch: "{{ a }}"
+    branches:
+      - id: b1
+        default: true
+      - id: b2
+        default: true
+"#;
+    let mut model = Workflow::from_yml(text).unwrap();
+    let tree = NodeTree::build(&mut model).unwrap();
+    let Some(ActError::Model(err)) = tree.error else {
+        panic!("expect model error");
+    };
+    assert_eq!(err.node.as_deref(), Some("step1"));
+    assert_eq!(err.path, "steps[0].switch");
+    assert_eq!(err.line, Some(5));
+}
+
+#[tokio::test]
+async fn sch_tree_use_template_not_resolved_error() {
+    let text = r#"
+id: m1
+steps:
+  - id: step1
+    use_template:
+      id: approval
+"#;
+    let mut model = Workflow::from_yml(text).unwrap();
+    let tree = NodeTree::build(&mut model).unwrap();
+    let Some(ActError::Model(err)) = tree.error else {
+        panic!("expect model error");
+    };
+    assert_eq!(err.node.as_deref(), Some("step1"));
+    assert_eq!(err.path, "steps[0].use_template");
+}
+
+const DIAGRAM_WORKFLOW: &str = r#"
+id: m1
+name: model "1"
+steps:
+  - id: step1
+    name: step1
+    acts:
+      - uses: acts.core.irq
+        id: act1
+        key: act1
+    catches:
+      - on: err1
+        steps:
+          - id: catch1
+  - id: step2
+    branches:
+      - id: b1
+        if: v > 0
+        steps:
+          - id: step3
+      - id: b2
+        else: true
+        steps:
+          - id: step4
+  - id: step5
+    next: step1
+"#;
+
+#[tokio::test]
+async fn sch_tree_to_mermaid() {
+    let workflow = Workflow::from_yml(DIAGRAM_WORKFLOW).unwrap();
+    let text = workflow.to_mermaid().unwrap();
+    assert!(text.starts_with("flowchart TD\n"));
+    assert!(text.contains(r#"(["model #quot;1#quot;"])"#));
+    assert!(text.contains(r#"["step1"]"#));
+    assert!(text.contains(r#"("act1 (acts.core.irq)")"#));
+    assert!(text.contains(r#"{"b1"}"#));
+    assert!(text.contains(r#"-->|"if: v > 0"|"#));
+    assert!(text.contains(r#"-->|"else"|"#));
+    assert!(text.contains(r#"-.->|"catch: err1"|"#));
+    assert!(text.contains(r#"==>|"next"|"#));
+    assert!(!text.contains("classDef"));
+}
+
+#[tokio::test]
+async fn sch_tree_to_dot() {
+    let workflow = Workflow::from_yml(DIAGRAM_WORKFLOW).unwrap();
+    let text = workflow.to_dot().unwrap();
+    assert!(text.starts_with("digraph \"model \\\"1\\\"\" {\n"));
+    assert!(text.contains(r#"[label="step1" shape=box];"#));
+    assert!(text.contains(r#"[label="b1" shape=diamond];"#));
+    assert!(text.contains(r#"[label="if: v > 0"];"#));
+    assert!(text.contains(r#"[label="catch: err1" style=dashed];"#));
+    assert!(text.contains(r#"[label="next" style=bold];"#));
+    assert!(text.ends_with("}\n"));
+}
+
+#[tokio::test]
+async fn sch_tree_to_mermaid_error() {
+    let workflow = Workflow::new().with_step(|step| step.with_id("step1").with_next("step2"));
+    assert!(workflow.to_mermaid().is_err());
+}
@@ -1,10 +1,10 @@
 use crate::event::EventAction;
 use crate::{
-    Act, Action, MessageState, Vars, Workflow,
+    Act, Action, MessageState, TaskState, Vars, Workflow,
     scheduler::tests::create_proc_signal,
     utils::{self, consts},
 };
-use serde_json::json;
+use serde_json::{Value as JsonValue, json};
 
 #[tokio::test]
 async fn sch_vars_workflow_inputs() {
@@ -175,6 +175,98 @@ async fn sch_vars_one_step_outputs() {
     );
 }
 
+#[tokio::test]
+async fn sch_vars_step_outputs_declaration_default() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_output("var1", json!({ "$type": "number", "$default": 10 }))
+    });
+
+    let (proc, scher, _, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    scher.launch(&proc);
+    tx.recv().await;
+    assert_eq!(proc.state(), TaskState::Completed);
+    assert_eq!(
+        proc.task_by_nid("step1")
+            .first()
+            .unwrap()
+            .outputs()
+            .get::<i64>("var1")
+            .unwrap(),
+        10
+    );
+}
+
+#[tokio::test]
+async fn sch_vars_step_outputs_literal_object() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_output("var1", json!({ "required": true }))
+            .with_output("var2", json!({ "type": "x" }))
+    });
+
+    let (proc, scher, _, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    scher.launch(&proc);
+    tx.recv().await;
+    assert_eq!(proc.state(), TaskState::Completed);
+    let outputs = proc.task_by_nid("step1").first().unwrap().outputs();
+    assert_eq!(
+        outputs.get::<JsonValue>("var1").unwrap(),
+        json!({ "required": true })
+    );
+    assert_eq!(
+        outputs.get::<JsonValue>("var2").unwrap(),
+        json!({ "type": "x" })
+    );
+}
+
+#[tokio::test]
+async fn sch_vars_step_outputs_declaration_error() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_input("var1", json!("abc"))
+            .with_output("var1", json!({ "$type": "number", "$required": true }))
+    });
+
+    let (proc, scher, _, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(proc.state(), TaskState::Error);
+    let err = proc.task_by_nid("step1").first().unwrap().err().unwrap();
+    assert_eq!(err.ecode, consts::ACT_ERR_OUTPUTS);
+}
+
+#[tokio::test]
+async fn sch_vars_step_outputs_legacy_declaration_error() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_input("var1", json!("abc"))
+            .with_output("var1", json!({ "type": "number", "required": true }))
+    });
+
+    let (proc, scher, _, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    scher.launch(&proc);
+    tx.recv().await;
+    assert_eq!(proc.state(), TaskState::Error);
+    let err = proc.task_by_nid("step1").first().unwrap().err().unwrap();
+    assert_eq!(err.ecode, consts::ACT_ERR_OUTPUTS);
+}
+
+#[tokio::test]
+async fn sch_vars_workflow_outputs_declaration_error() {
+    let mut workflow = Workflow::new()
+        .with_output("var1", json!({ "$type": "string", "$required": true }))
+        .with_step(|step| step.with_id("step1"));
+
+    let (proc, scher, _, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    scher.launch(&proc);
+    tx.recv().await;
+    assert_eq!(proc.state(), TaskState::Error);
+    let err = proc.root().unwrap().err().unwrap();
+    assert_eq!(err.ecode, consts::ACT_ERR_OUTPUTS);
+}
+
 #[tokio::test]
 async fn sch_vars_step_default_expose() {
     let mut workflow = Workflow::new().with_step(|step| {
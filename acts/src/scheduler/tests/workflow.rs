@@ -1,8 +1,9 @@
 use crate::event::EventAction;
 use crate::utils::test::create_proc_signal_with_auto_clomplete;
 use crate::{
-    Act, Message, MessageState, StmtBuild, TaskState, Vars, Workflow,
-    scheduler::tests::create_proc_signal, utils,
+    Act, Action, Message, MessageState, StmtBuild, TaskState, Vars, Workflow,
+    scheduler::tests::create_proc_signal,
+    utils::{self, consts},
 };
 
 #[tokio::test]
@@ -387,3 +388,197 @@ async fn sch_workflow_hooks_store() {
     proc.print();
     assert_eq!(ret, 5);
 }
+
+#[tokio::test]
+async fn sch_workflow_catch_error() {
+    let mut workflow = Workflow::new()
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_act(Act::irq(|act| act.with_key("act1")))
+        })
+        .with_step(|step| {
+            step.with_id("step2")
+                .with_act(Act::irq(|act| act.with_key("act2")))
+        })
+        .with_catch(|c| {
+            c.with_on("err1")
+                .with_step(|step| step.with_act(Act::msg(|act| act.with_key("catch1"))))
+        });
+    workflow.print();
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            let options = Vars::new().with(consts::ACT_ERR_CODE, "err1");
+            let action = Action::new(&e.pid, &e.tid, EventAction::Error, &options);
+            s.do_action(&action).unwrap();
+        }
+        if e.is_key("catch1") || e.is_key("act2") {
+            rx.update(|data| data.push(e.key.clone()));
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret, vec!["catch1".to_string()]);
+    assert_eq!(proc.state(), TaskState::Completed);
+}
+
+#[tokio::test]
+async fn sch_workflow_catch_not_matched() {
+    let mut workflow = Workflow::new()
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_act(Act::irq(|act| act.with_key("act1")))
+        })
+        .with_catch(|c| {
+            c.with_on("err2")
+                .with_step(|step| step.with_act(Act::msg(|act| act.with_key("catch1"))))
+        });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            let options = Vars::new().with(consts::ACT_ERR_CODE, "err1");
+            let action = Action::new(&e.pid, &e.tid, EventAction::Error, &options);
+            s.do_action(&action).unwrap();
+        }
+        if e.is_key("catch1") {
+            rx.update(|data| data.push(e.key.clone()));
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert!(ret.is_empty());
+    assert_eq!(proc.state(), TaskState::Error);
+}
+
+#[tokio::test]
+async fn sch_workflow_timeout() {
+    let mut workflow = Workflow::new()
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_act(Act::irq(|act| act.with_key("act1")))
+        })
+        .with_timeout(|t| {
+            t.with_on("1s")
+                .with_step(|step| step.with_act(Act::msg(|act| act.with_key("msg1"))))
+        });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    let act1 = std::sync::Arc::new(std::sync::Mutex::new(None));
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            *act1.lock().unwrap() = Some((e.pid.clone(), e.tid.clone()));
+        }
+        if e.is_key("msg1") {
+            rx.update(|data| data.push(e.key.clone()));
+
+            // the process is still running after the timeout steps
+            let (pid, tid) = act1.lock().unwrap().clone().unwrap();
+            let action = Action::new(&pid, &tid, EventAction::Next, &Vars::new());
+            s.do_action(&action).unwrap();
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret, vec!["msg1".to_string()]);
+    assert_eq!(proc.state(), TaskState::Completed);
+    assert_eq!(
+        proc.task_by_nid("step1").first().unwrap().state(),
+        TaskState::Completed
+    );
+}
+
+#[tokio::test]
+async fn sch_workflow_from_bpmn() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<definitions xmlns="http://www.omg.org/spec/BPMN/20100524/MODEL">
+  <process id="p1">
+    <startEvent id="start" />
+    <sequenceFlow id="f1" sourceRef="start" targetRef="gw1" />
+    <exclusiveGateway id="gw1" default="f3" />
+    <sequenceFlow id="f2" sourceRef="gw1" targetRef="task1">
+      <conditionExpression>${amount &gt; 100}</conditionExpression>
+    </sequenceFlow>
+    <sequenceFlow id="f3" sourceRef="gw1" targetRef="task2" />
+    <userTask id="task1" />
+    <userTask id="task2" />
+    <sequenceFlow id="f4" sourceRef="task1" targetRef="gw2" />
+    <sequenceFlow id="f5" sourceRef="task2" targetRef="gw2" />
+    <exclusiveGateway id="gw2" />
+    <sequenceFlow id="f6" sourceRef="gw2" targetRef="task3" />
+    <userTask id="task3" />
+    <sequenceFlow id="f7" sourceRef="task3" targetRef="end" />
+    <endEvent id="end" />
+  </process>
+</definitions>"#;
+    let mut workflow = Workflow::from_bpmn(xml)
+        .unwrap()
+        .with_input("amount", 200.into());
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_irq() && e.is_state(MessageState::Created) {
+            rx.update(|data| data.push(e.key.clone()));
+            let action = Action::new(&e.pid, &e.tid, EventAction::Next, &Vars::new());
+            s.do_action(&action).unwrap();
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret, vec!["task1".to_string(), "task3".to_string()]);
+    assert_eq!(proc.state(), TaskState::Completed);
+}
+
+#[tokio::test]
+async fn sch_workflow_from_bpmn_error_end() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<definitions xmlns="http://www.omg.org/spec/BPMN/20100524/MODEL">
+  <error id="Error_1" errorCode="err1" />
+  <process id="p1">
+    <startEvent id="start" />
+    <sequenceFlow id="f1" sourceRef="start" targetRef="end" />
+    <endEvent id="end">
+      <errorEventDefinition errorRef="Error_1" />
+    </endEvent>
+  </process>
+</definitions>"#;
+    let mut workflow = Workflow::from_bpmn(xml).unwrap();
+    let (proc, scher, _, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(proc.state(), TaskState::Error);
+    assert_eq!(proc.err().unwrap().ecode, "err1");
+}
+
+#[tokio::test]
+async fn sch_workflow_step_next_forward() {
+    let mut workflow = Workflow::new()
+        .with_step(|step| step.with_id("step1").with_next("step3"))
+        .with_step(|step| {
+            step.with_id("step2")
+                .with_act(Act::msg(|act| act.with_key("msg2")))
+        })
+        .with_step(|step| step.with_id("step3"));
+
+    let (proc, scher, _emitter, tx, _rx) =
+        create_proc_signal::<()>(&mut workflow, &utils::longid());
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(proc.state(), TaskState::Completed);
+    assert!(proc.task_by_nid("step2").is_empty());
+    assert_eq!(
+        proc.task_by_nid("step3").first().unwrap().state(),
+        TaskState::Completed
+    );
+}
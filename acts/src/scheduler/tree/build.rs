@@ -3,7 +3,7 @@ use super::{
     node_tree::NodeTree,
 };
 use crate::{
-    Act, ActError, Branch, Result, Step, Workflow,
+    Act, ActError, Assignment, Branch, ModelError, Result, Sla, Step, TimeoutLimit, Workflow,
     utils::{longid, shortid},
 };
 use std::sync::Arc;
@@ -30,6 +30,50 @@ pub fn build_workflow(workflow: &mut Workflow, tree: &mut NodeTree) -> Result<()
         )?;
     }
 
+    if !workflow.catches.is_empty() {
+        let mut catch_prev = root.clone();
+        for catch in workflow.catches.iter_mut() {
+            for step in catch.steps.iter_mut() {
+                build_step(
+                    step,
+                    tree,
+                    &root,
+                    &mut catch_prev,
+                    level + 1,
+                    NodeOutputKind::Catch,
+                    catch.on.clone(),
+                )?;
+            }
+        }
+    }
+    if !workflow.timeout.is_empty() {
+        let mut timeout_prev = root.clone();
+        for timeout in workflow.timeout.iter_mut() {
+            for step in timeout.steps.iter_mut() {
+                build_step(
+                    step,
+                    tree,
+                    &root,
+                    &mut timeout_prev,
+                    level + 1,
+                    NodeOutputKind::Timeout,
+                    Some(timeout.on.clone()),
+                )?;
+            }
+        }
+    }
+
+    for (node, next) in std::mem::take(&mut tree.nexts) {
+        match &tree.node(&next) {
+            Some(next) => node.set_next(next, false),
+            None => tree.set_error(model_error(
+                node.id(),
+                "next",
+                format!("found next node error by '{next}'"),
+            )),
+        }
+    }
+
     tree.model = Box::new(workflow.clone());
     tree.set_root(&root);
 
@@ -57,15 +101,61 @@ pub fn build_step(
         node.set_parent_in(typ, on, parent);
     }
 
+    if let Err(err) = check_switch(step) {
+        tree.set_error(err);
+    }
+    if let Some(t) = &step.use_template {
+        // the templates are only resolved when deploying the model
+        tree.set_error(model_error(
+            &step.id,
+            "use_template",
+            format!(
+                "the template '{}' of step '{}' is not resolved",
+                t.id, step.id
+            ),
+        ));
+    }
+    if let Some(sla) = &step.sla {
+        check_sla(&step.id, sla)?;
+    }
+
+    if let Some(schema) = &step.input_schema {
+        jsonschema::validator_for(schema).map_err(|err| {
+            model_error(
+                &step.id,
+                "input_schema",
+                format!("invalid input_schema of step '{}': {err}", step.id),
+            )
+        })?;
+    }
+
+    if step.r#loop.is_some() && step.for_each.is_some() {
+        tree.set_error(model_error(
+            &step.id,
+            "for_each",
+            format!("step '{}' cannot set both 'loop' and 'for_each'", step.id),
+        ));
+    }
+    if let Some(r#loop) = &step.r#loop
+        && r#loop.max() == 0
+    {
+        tree.set_error(model_error(
+            &step.id,
+            "loop.max",
+            format!(
+                "the loop max of step '{}' should be greater than 0",
+                step.id
+            ),
+        ));
+    }
+
     match &step.next {
         Some(next) => match &tree.node(next) {
             Some(next) => {
                 node.set_next(next, false);
             }
-            None => tree.set_error(ActError::Runtime(format!(
-                "found next node error by '{}'",
-                next
-            ))),
+            // the next step is after the current one, link it when all nodes are built
+            None => tree.nexts.push((node.clone(), next.clone())),
         },
         None => {
             if !step.branches.is_empty() {
@@ -122,6 +212,159 @@ pub fn build_step(
     Ok(())
 }
 
+/// check the switch step to make sure that only one branch is matched
+fn check_switch(step: &Step) -> Result<()> {
+    if step.switch.is_none() {
+        if let Some((index, branch)) = step
+            .branches
+            .iter()
+            .enumerate()
+            .find(|(_, b)| b.case.is_some() || b.default)
+        {
+            return Err(model_error(
+                &step.id,
+                &format!("branches[{index}]"),
+                format!(
+                    "branch '{}' sets 'case' or 'default' but step '{}' has no 'switch'",
+                    branch.id, step.id
+                ),
+            ));
+        }
+        return Ok(());
+    }
+
+    let mut cases: Vec<&Branch> = Vec::new();
+    let mut default_count = 0;
+    for (index, branch) in step.branches.iter().enumerate() {
+        let path = format!("branches[{index}]");
+        if branch.r#if.is_some() || branch.r#else {
+            return Err(model_error(
+                &step.id,
+                &path,
+                format!(
+                    "branch '{}' in switch step '{}' cannot set 'if' or 'else'",
+                    branch.id, step.id
+                ),
+            ));
+        }
+
+        if branch.default {
+            default_count += 1;
+        }
+
+        if let Some(case) = &branch.case {
+            if cases.iter().any(|b| b.is_case(case)) {
+                return Err(model_error(
+                    &step.id,
+                    &format!("{path}.case"),
+                    format!("duplicate case '{}' in switch step '{}'", case, step.id),
+                ));
+            }
+            cases.push(branch);
+        } else if !branch.default {
+            return Err(model_error(
+                &step.id,
+                &path,
+                format!(
+                    "branch '{}' in switch step '{}' should set 'case' or 'default'",
+                    branch.id, step.id
+                ),
+            ));
+        }
+    }
+
+    if default_count > 1 {
+        return Err(model_error(
+            &step.id,
+            "switch",
+            format!("switch step '{}' has more than one default branch", step.id),
+        ));
+    }
+
+    Ok(())
+}
+
+/// check the sla times and make sure the escalations are in time order
+fn check_sla(id: &str, sla: &Sla) -> Result<()> {
+    if let Some(deadline) = &sla.deadline {
+        TimeoutLimit::parse(deadline).map_err(|_| {
+            model_error(
+                id,
+                "sla.deadline",
+                format!("invalid sla deadline '{deadline}' of '{id}'"),
+            )
+        })?;
+    }
+
+    let mut prev: Option<TimeoutLimit> = None;
+    for (index, escalation) in sla.escalations.iter().enumerate() {
+        let path = format!("sla.escalations[{index}]");
+        let limit = TimeoutLimit::parse(&escalation.on).map_err(|_| {
+            model_error(
+                id,
+                &format!("{path}.on"),
+                format!("invalid escalation time '{}' of '{id}'", escalation.on),
+            )
+        })?;
+        if let Some(prev) = &prev {
+            // the business units depend on the calendar, so they are not comparable
+            // with the other units, such as '9bh' and '1bd'
+            if prev.unit != limit.unit && (prev.is_business() || limit.is_business()) {
+                return Err(model_error(
+                    id,
+                    &format!("{path}.on"),
+                    format!(
+                        "the escalations of '{id}' cannot mix the business unit in '{prev}' and '{limit}'"
+                    ),
+                ));
+            }
+            if limit.as_secs() <= prev.as_secs() {
+                return Err(model_error(
+                    id,
+                    &format!("{path}.on"),
+                    format!("the escalations of '{id}' should be in time order"),
+                ));
+            }
+        }
+        prev = Some(limit);
+
+        if escalation.msg.is_none() && escalation.reassign.is_none() && escalation.action.is_none()
+        {
+            return Err(model_error(
+                id,
+                &path,
+                format!(
+                    "the escalation of '{id}' should set one of 'msg', 'reassign' and 'action'"
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// check the due time which is not an expression
+fn check_assignment(id: &str, assignment: &Assignment) -> Result<()> {
+    if let Some(due) = &assignment.due
+        && !due.contains("{{")
+        && TimeoutLimit::parse(due).is_err()
+        && chrono::DateTime::parse_from_rfc3339(due).is_err()
+    {
+        return Err(model_error(
+            id,
+            "assignment.due",
+            format!("invalid assignment due time '{due}' of '{id}'"),
+        ));
+    }
+
+    Ok(())
+}
+
+/// the model error of the node, the path is relative to the node
+fn model_error(node: &str, path: &str, message: String) -> ActError {
+    ActError::Model(ModelError::from(message).with_node(node).with_path(path))
+}
+
 pub fn build_branch(
     branch: &mut Branch,
     tree: &mut NodeTree,
@@ -170,6 +413,12 @@ pub fn build_act(
     let data = NodeContent::Act(act.clone());
 
     let node = tree.make(&act.id, data, level)?;
+    if let Some(sla) = &act.sla {
+        check_sla(&act.id, sla)?;
+    }
+    if let Some(assignment) = &act.assignment {
+        check_assignment(&act.id, assignment)?;
+    }
 
     if is_sequence {
         // set the act order one by one
@@ -0,0 +1,279 @@
+use super::{
+    node::{Node, NodeContent, NodeKind, NodeOutputKind},
+    node_tree::NodeTree,
+};
+use crate::TaskState;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    sync::Arc,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum EdgeKind {
+    Flow,
+    Jump,
+    Catch,
+    Timeout,
+}
+
+#[derive(Debug, Clone)]
+struct DiagramNode {
+    id: String,
+    kind: NodeKind,
+    label: String,
+    state: Option<TaskState>,
+}
+
+#[derive(Debug, Clone)]
+struct DiagramEdge {
+    from: String,
+    to: String,
+    label: String,
+    kind: EdgeKind,
+}
+
+/// the node tree graph to render as mermaid or graphviz dot
+#[derive(Debug, Default, Clone)]
+pub struct Diagram {
+    title: String,
+    nodes: Vec<DiagramNode>,
+    edges: Vec<DiagramEdge>,
+    ids: HashMap<String, String>,
+}
+
+/// the fill and stroke colors of the task state
+fn state_color(state: &TaskState) -> Option<(&'static str, &'static str)> {
+    match state {
+        TaskState::None => None,
+        TaskState::Ready | TaskState::Pending => Some(("#f1f3f5", "#868e96")),
+        TaskState::Running | TaskState::Interrupt => Some(("#d0ebff", "#1c7ed6")),
+        TaskState::Completed | TaskState::Submitted => Some(("#d3f9d8", "#2b8a3e")),
+        TaskState::Backed | TaskState::Cancelled | TaskState::Skipped | TaskState::Removed => {
+            Some(("#fff3bf", "#f08c00"))
+        }
+        TaskState::Error | TaskState::Aborted => Some(("#ffe3e3", "#c92a2a")),
+    }
+}
+
+fn mermaid_text(text: &str) -> String {
+    text.replace('"', "#quot;").replace('\n', " ")
+}
+
+fn dot_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', " ")
+}
+
+impl Diagram {
+    pub fn new(tree: &NodeTree) -> Self {
+        let mut diagram = Self {
+            title: tree.model.name.clone(),
+            ..Default::default()
+        };
+        if let Some(root) = &tree.root {
+            let mut visits = HashSet::new();
+            diagram.visit(root, &mut visits);
+        }
+        diagram
+    }
+
+    /// set the task states by node id to color the nodes
+    pub fn with_states(mut self, states: &HashMap<String, TaskState>) -> Self {
+        for node in self.nodes.iter_mut() {
+            node.state = states.get(&node.id).cloned();
+        }
+        self
+    }
+
+    pub fn to_mermaid(&self) -> String {
+        let mut s = String::from("flowchart TD\n");
+        for node in &self.nodes {
+            let id = &self.ids[&node.id];
+            let label = mermaid_text(&node.label);
+            let _ = match node.kind {
+                NodeKind::Workflow => writeln!(s, "    {id}([\"{label}\"])"),
+                NodeKind::Branch => writeln!(s, "    {id}{{\"{label}\"}}"),
+                NodeKind::Step => writeln!(s, "    {id}[\"{label}\"]"),
+                NodeKind::Act => writeln!(s, "    {id}(\"{label}\")"),
+            };
+        }
+
+        for edge in &self.edges {
+            let arrow = match edge.kind {
+                EdgeKind::Flow => "-->",
+                EdgeKind::Jump => "==>",
+                EdgeKind::Catch | EdgeKind::Timeout => "-.->",
+            };
+            let (from, to) = (&self.ids[&edge.from], &self.ids[&edge.to]);
+            let _ = if edge.label.is_empty() {
+                writeln!(s, "    {from} {arrow} {to}")
+            } else {
+                writeln!(
+                    s,
+                    "    {from} {arrow}|\"{}\"| {to}",
+                    mermaid_text(&edge.label)
+                )
+            };
+        }
+
+        let mut classes: Vec<(String, Vec<&str>)> = Vec::new();
+        for node in &self.nodes {
+            let Some(state) = &node.state else {
+                continue;
+            };
+            if state_color(state).is_none() {
+                continue;
+            }
+            let name = state.to_string();
+            let id = self.ids[&node.id].as_str();
+            match classes.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, ids)) => ids.push(id),
+                None => classes.push((name, vec![id])),
+            }
+        }
+        for (name, ids) in &classes {
+            let (fill, stroke) = state_color(&TaskState::from(name.as_str())).unwrap();
+            let _ = writeln!(s, "    classDef {name} fill:{fill},stroke:{stroke}");
+            let _ = writeln!(s, "    class {} {name}", ids.join(","));
+        }
+
+        s
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut s = format!("digraph \"{}\" {{\n", dot_text(&self.title));
+        s.push_str("    rankdir=TB;\n");
+        for node in &self.nodes {
+            let id = &self.ids[&node.id];
+            let mut attrs = vec![format!("label=\"{}\"", dot_text(&node.label))];
+            let mut styles = Vec::new();
+            match node.kind {
+                NodeKind::Workflow => attrs.push("shape=oval".to_string()),
+                NodeKind::Branch => attrs.push("shape=diamond".to_string()),
+                NodeKind::Step => attrs.push("shape=box".to_string()),
+                NodeKind::Act => {
+                    attrs.push("shape=box".to_string());
+                    styles.push("rounded");
+                }
+            }
+            if let Some((fill, stroke)) = node.state.as_ref().and_then(state_color) {
+                styles.push("filled");
+                attrs.push(format!("fillcolor=\"{fill}\""));
+                attrs.push(format!("color=\"{stroke}\""));
+            }
+            if !styles.is_empty() {
+                attrs.push(format!("style=\"{}\"", styles.join(",")));
+            }
+            let _ = writeln!(s, "    {id} [{}];", attrs.join(" "));
+        }
+
+        for edge in &self.edges {
+            let mut attrs = Vec::new();
+            if !edge.label.is_empty() {
+                attrs.push(format!("label=\"{}\"", dot_text(&edge.label)));
+            }
+            match edge.kind {
+                EdgeKind::Flow => {}
+                EdgeKind::Jump => attrs.push("style=bold".to_string()),
+                EdgeKind::Catch | EdgeKind::Timeout => attrs.push("style=dashed".to_string()),
+            }
+            let (from, to) = (&self.ids[&edge.from], &self.ids[&edge.to]);
+            let _ = if attrs.is_empty() {
+                writeln!(s, "    {from} -> {to};")
+            } else {
+                writeln!(s, "    {from} -> {to} [{}];", attrs.join(" "))
+            };
+        }
+        s.push_str("}\n");
+
+        s
+    }
+
+    fn visit(&mut self, node: &Arc<Node>, visits: &mut HashSet<String>) {
+        if !visits.insert(node.id().to_string()) {
+            return;
+        }
+        self.push_node(node);
+
+        let children = node.children.read().unwrap().clone();
+        for child in &children {
+            let (label, kind) = match child.typ {
+                NodeOutputKind::Normal => (branch_label(&child.node), EdgeKind::Flow),
+                NodeOutputKind::Catch => match &child.on {
+                    Some(on) => (format!("catch: {on}"), EdgeKind::Catch),
+                    None => ("catch".to_string(), EdgeKind::Catch),
+                },
+                NodeOutputKind::Timeout => (
+                    format!("timeout: {}", child.on.clone().unwrap_or_default()),
+                    EdgeKind::Timeout,
+                ),
+            };
+            self.push_edge(node, &child.node, &label, kind);
+            self.visit(&child.node, visits);
+        }
+
+        if let Some(next) = node.next().upgrade() {
+            // the next node is not linked back when it is set by the 'next' property
+            let is_jump = next
+                .prev()
+                .upgrade()
+                .is_none_or(|prev| prev.id() != node.id());
+            if is_jump {
+                self.push_edge(node, &next, "next", EdgeKind::Jump);
+            } else {
+                self.push_edge(node, &next, "", EdgeKind::Flow);
+            }
+            self.visit(&next, visits);
+        }
+    }
+
+    fn push_node(&mut self, node: &Arc<Node>) {
+        let label = match node.name() {
+            name if name.is_empty() => node.id().to_string(),
+            name => name,
+        };
+        let label = match &node.content {
+            NodeContent::Act(act) if !act.uses.is_empty() => format!("{label} ({})", act.uses),
+            _ => label,
+        };
+        self.ids
+            .insert(node.id().to_string(), format!("n{}", self.nodes.len()));
+        self.nodes.push(DiagramNode {
+            id: node.id().to_string(),
+            kind: node.kind(),
+            label,
+            state: None,
+        });
+    }
+
+    fn push_edge(&mut self, from: &Arc<Node>, to: &Arc<Node>, label: &str, kind: EdgeKind) {
+        self.edges.push(DiagramEdge {
+            from: from.id().to_string(),
+            to: to.id().to_string(),
+            label: label.to_string(),
+            kind,
+        });
+    }
+}
+
+/// the condition label of the branch node
+fn branch_label(node: &Arc<Node>) -> String {
+    let NodeContent::Branch(branch) = &node.content else {
+        return "".to_string();
+    };
+    if let Some(expr) = &branch.r#if {
+        return format!("if: {expr}");
+    }
+    if let Some(case) = &branch.case {
+        return format!("case: {case}");
+    }
+    if branch.default {
+        return "default".to_string();
+    }
+    if branch.r#else {
+        return "else".to_string();
+    }
+    "".to_string()
+}
@@ -1,10 +1,12 @@
 mod build;
+mod diagram;
 mod node;
 mod node_tree;
 mod task_tree;
 mod visit;
 
 pub use build::dyn_build_act;
+pub use diagram::Diagram;
 pub use node::{Node, NodeContent, NodeData, NodeKind, NodeOutputKind};
 pub use node_tree::NodeTree;
 pub use task_tree::TaskTree;
@@ -112,6 +112,14 @@ impl NodeContent {
         }
     }
 
+    pub fn compensate(&self) -> Vec<Act> {
+        match self {
+            NodeContent::Step(node) => node.compensate.clone(),
+            NodeContent::Act(node) => node.compensate.clone(),
+            _ => Vec::new(),
+        }
+    }
+
     pub fn tag(&self) -> String {
         match self {
             NodeContent::Workflow(node) => node.tag.clone(),
@@ -166,17 +174,22 @@ impl Node {
         });
     }
 
-    pub fn set_parent(&self, parent: &Arc<Node>) {
+    pub fn set_parent(self: &Arc<Node>, parent: &Arc<Node>) {
         self.set_parent_in(NodeOutputKind::Normal, None, parent);
     }
 
     /// set parent in the node tree with the given type
-    pub fn set_parent_in(&self, typ: NodeOutputKind, on: Option<String>, parent: &Arc<Node>) {
+    pub fn set_parent_in(
+        self: &Arc<Node>,
+        typ: NodeOutputKind,
+        on: Option<String>,
+        parent: &Arc<Node>,
+    ) {
         *self.parent.write().unwrap() = Arc::downgrade(parent);
         parent.children.write().unwrap().push(NodeOutput {
             typ,
             on,
-            node: Arc::new(self.clone()),
+            node: self.clone(),
         });
     }
 
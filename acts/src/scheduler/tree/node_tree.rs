@@ -3,14 +3,21 @@ use super::{
     node::{Node, NodeContent},
     visit::VisitRoot,
 };
-use crate::{ActError, Result, ShareLock, Workflow};
-use std::{cell::RefCell, collections::HashMap, sync::Arc};
+use crate::{ActError, ModelError, Result, ShareLock, Workflow};
+use serde_json::Value as JsonValue;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 #[derive(Default, Clone)]
 pub struct NodeTree {
     pub(crate) root: Option<Arc<Node>>,
     pub(crate) node_map: ShareLock<HashMap<String, Arc<Node>>>,
     pub(crate) error: Option<ActError>,
+    /// the step nodes with the 'next' to link after building
+    pub(crate) nexts: Vec<(Arc<Node>, String)>,
     pub(crate) model: Box<Workflow>,
 }
 
@@ -25,7 +32,10 @@ impl NodeTree {
 
     pub fn build(workflow: &mut Workflow) -> Result<NodeTree> {
         let mut tree = NodeTree::new();
-        build::build_workflow(workflow, &mut tree)?;
+        build::build_workflow(workflow, &mut tree).map_err(|err| workflow.locate(err))?;
+        if let Some(err) = tree.error.take() {
+            tree.error = Some(workflow.locate(err));
+        }
 
         Ok(tree)
     }
@@ -33,24 +43,97 @@ impl NodeTree {
     pub fn load(&mut self, model: &Workflow) -> Result<()> {
         let mut model = model.clone();
 
-        for on in model.on.iter() {
+        for (index, on) in model.on.iter().enumerate() {
             let data = NodeContent::Act(on.clone());
             // make an act node to check if the act is valid
             // not to generate the on event node id
             if on.id.is_empty() {
-                return Err(ActError::Model("workflow event id is empty".to_string()));
+                return Err(model.locate(ActError::Model(
+                    ModelError::new("workflow event id is empty")
+                        .with_path(&format!("on[{index}]")),
+                )));
             }
-            self.make(&on.id, data, 0)?;
+            self.make(&on.id, data, 0)
+                .map_err(|err| model.locate(err))?;
         }
 
-        build::build_workflow(&mut model, self)
+        // locate the errors by the built model which has the generated node ids
+        let ret = build::build_workflow(&mut model, self);
+        if let Some(err) = self.error.take() {
+            self.error = Some(model.locate(err));
+        }
+        ret.map_err(|err| model.locate(err))
+    }
+
+    /// rebuild the tree with the patched model for the running process
+    /// the old nodes in `keeps` are still referenced by the tasks, so they are
+    /// kept in the new tree instead of the rebuilt ones to have one live node per id
+    /// only the links of the kept nodes can be changed, the other changes of them are rejected
+    pub fn patch(&mut self, model: &Workflow, keeps: &HashSet<String>) -> Result<()> {
+        let mut tree = NodeTree::new();
+        tree.load(model)?;
+        if let Some(err) = tree.error.take() {
+            return Err(err);
+        }
+
+        {
+            let old_map = self.node_map.read().unwrap();
+            let mut node_map = tree.node_map.write().unwrap();
+            let olds = keeps
+                .iter()
+                .filter_map(|id| old_map.get(id).map(|node| (id.clone(), node.clone())))
+                .collect::<HashMap<_, _>>();
+
+            for (id, old) in olds.iter() {
+                if let Some(node) = node_map.get(id)
+                    && definition(&old.content)? != definition(&node.content)?
+                {
+                    return Err(ActError::Action(format!(
+                        "cannot change the started node '{id}' except its links"
+                    )));
+                }
+            }
+
+            // point the rebuilt nodes to the kept ones
+            for node in node_map.values() {
+                relink(node, &olds);
+            }
+            for (id, old) in olds.iter() {
+                // the dynamic nodes are not in the model and keep their links
+                if let Some(node) = node_map.get(id) {
+                    let parent = node.parent.read().unwrap().clone();
+                    let prev = node.prev();
+                    let next = node.next();
+                    let children = node.children.read().unwrap().clone();
+                    *old.parent.write().unwrap() = parent;
+                    *old.prev.write().unwrap() = prev;
+                    *old.next.write().unwrap() = next;
+                    *old.children.write().unwrap() = children;
+                }
+                node_map.insert(id.clone(), old.clone());
+            }
+            if let Some(root) = &tree.root
+                && let Some(old) = olds.get(root.id())
+            {
+                tree.root = Some(old.clone());
+            }
+        }
+
+        self.root = tree.root;
+        self.node_map = tree.node_map;
+        self.model = tree.model;
+        self.error = None;
+
+        Ok(())
     }
 
     pub fn make(&self, id: &str, data: NodeContent, level: usize) -> Result<Arc<Node>> {
         let node = Arc::new(Node::new(id, data, level));
         let mut node_map = self.node_map.write().unwrap();
         if node_map.contains_key(node.id()) {
-            return Err(ActError::Model(format!("dup node id with '{}'", node.id())));
+            return Err(ActError::Model(
+                ModelError::from(format!("dup node id with '{}'", node.id())).with_node(node.id()),
+            ));
         }
         node_map.insert(node.id().to_string(), node.clone());
 
@@ -115,3 +198,47 @@ impl NodeTree {
         self.error = Some(err);
     }
 }
+
+/// the node content without the parts which are linked in the tree
+/// such as the 'next', the branches and the steps of the catches and timeouts
+/// the ids are also removed since the ids of the nested acts are generated when building
+fn definition(content: &NodeContent) -> Result<JsonValue> {
+    fn strip(value: &mut JsonValue) {
+        match value {
+            JsonValue::Object(map) => {
+                map.remove("id");
+                map.remove("steps");
+                map.remove("branches");
+                map.values_mut().for_each(strip);
+            }
+            JsonValue::Array(items) => items.iter_mut().for_each(strip),
+            _ => {}
+        }
+    }
+
+    let mut value = serde_json::to_value(content)?;
+    if let Some(data) = value
+        .as_object_mut()
+        .and_then(|map| map.values_mut().next())
+    {
+        if let Some(map) = data.as_object_mut() {
+            map.remove("next");
+        }
+        strip(data);
+    }
+    Ok(value)
+}
+
+/// replace the links of the node with the nodes in `olds` by the same id
+fn relink(node: &Node, olds: &HashMap<String, Arc<Node>>) {
+    let swap = |n: Arc<Node>| olds.get(n.id()).cloned().unwrap_or(n);
+    for link in [&node.parent, &node.prev, &node.next] {
+        let mut link = link.write().unwrap();
+        if let Some(n) = link.upgrade() {
+            *link = Arc::downgrade(&swap(n));
+        }
+    }
+    for child in node.children.write().unwrap().iter_mut() {
+        child.node = swap(child.node.clone());
+    }
+}
@@ -0,0 +1,25 @@
+use crate::store::{DbCollectionIden, StoreIden};
+use serde::{Deserialize, Serialize};
+
+/// the archived process with its tasks, messages and history
+#[derive(Default, Deserialize, Serialize, Debug, Clone)]
+pub struct Archive {
+    /// the process id
+    pub id: String,
+    pub mid: String,
+    pub name: String,
+    pub state: String,
+
+    /// json data of the process, tasks, messages and history
+    pub data: String,
+
+    pub end_time: i64,
+    pub create_time: i64,
+    pub timestamp: i64,
+}
+
+impl DbCollectionIden for Archive {
+    fn iden() -> StoreIden {
+        StoreIden::Archives
+    }
+}
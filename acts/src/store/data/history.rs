@@ -0,0 +1,64 @@
+use crate::{
+    store::{DbCollectionIden, StoreIden},
+    utils,
+};
+use serde::{Deserialize, Serialize};
+use strum::AsRefStr;
+
+#[derive(Debug, Clone, AsRefStr, PartialEq)]
+pub enum HistoryKind {
+    /// task state transition
+    #[strum(serialize = "state")]
+    State,
+    /// action applied to the task
+    #[strum(serialize = "action")]
+    Action,
+    /// task vars mutation
+    #[strum(serialize = "vars")]
+    Vars,
+}
+
+#[derive(Default, Deserialize, Serialize, Debug, Clone)]
+pub struct History {
+    pub id: String,
+    pub pid: String,
+    pub tid: String,
+    pub kind: String,
+
+    /// the task state for state kind or the action name for action kind
+    pub name: String,
+    pub actor: String,
+
+    /// json data, the previous state, action options or changed vars
+    pub data: String,
+
+    pub create_time: i64,
+    pub timestamp: i64,
+}
+
+impl DbCollectionIden for History {
+    fn iden() -> StoreIden {
+        StoreIden::History
+    }
+}
+
+impl History {
+    pub fn new(pid: &str, tid: &str, kind: HistoryKind, name: &str, data: &str) -> Self {
+        Self {
+            id: utils::longid(),
+            pid: pid.to_string(),
+            tid: tid.to_string(),
+            kind: kind.as_ref().to_string(),
+            name: name.to_string(),
+            actor: String::new(),
+            data: data.to_string(),
+            create_time: utils::time::time_millis(),
+            timestamp: utils::time::timestamp(),
+        }
+    }
+
+    pub fn with_actor(mut self, actor: &str) -> Self {
+        self.actor = actor.to_string();
+        self
+    }
+}
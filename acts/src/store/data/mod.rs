@@ -1,11 +1,15 @@
+mod archive;
 mod event;
+mod history;
 mod message;
 mod model;
 mod package;
 mod proc;
 mod task;
 
+pub use archive::Archive;
 pub use event::Event;
+pub use history::{History, HistoryKind};
 pub use message::{Message, MessageStatus};
 pub use model::Model;
 pub use package::Package;
@@ -21,6 +21,18 @@ pub struct Task {
     pub end_time: i64,
     pub hooks: String,
     pub timestamp: i64,
+
+    /// the assignee of the irq act, empty when it is not assigned
+    #[serde(default)]
+    pub assignee: String,
+
+    /// the candidate users and groups such as `,user:u1,group:g1,` to query by `contains`
+    #[serde(default)]
+    pub candidates: String,
+
+    /// the priority of the irq act
+    #[serde(default)]
+    pub priority: i32,
 }
 
 impl DbCollectionIden for Task {
@@ -39,4 +51,27 @@ impl Task {
     pub fn set_end_time(&mut self, time: i64) {
         self.end_time = time;
     }
+
+    /// join the candidate users and groups to the `candidates` column
+    pub fn join_candidates(users: &[String], groups: &[String]) -> String {
+        if users.is_empty() && groups.is_empty() {
+            return String::new();
+        }
+        let items = users
+            .iter()
+            .map(|u| format!("user:{u}"))
+            .chain(groups.iter().map(|g| format!("group:{g}")))
+            .collect::<Vec<_>>();
+        format!(",{},", items.join(","))
+    }
+
+    /// the candidate user pattern in the `candidates` column
+    pub fn candidate_user(uid: &str) -> String {
+        format!(",user:{uid},")
+    }
+
+    /// the candidate group pattern in the `candidates` column
+    pub fn candidate_group(group: &str) -> String {
+        format!(",group:{group},")
+    }
 }
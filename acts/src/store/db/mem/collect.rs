@@ -9,14 +9,18 @@ use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Debug;
 use std::marker::PhantomData;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, RwLock, RwLockReadGuard};
 
 use super::DbDocument;
+use super::journal::{Journal, JournalEntry, JournalOp};
+
+pub type Docs = BTreeMap<String, HashMap<String, JsonValue>>;
 
 #[derive(Debug)]
 pub struct Collect<T> {
     name: String,
-    db: ShareLock<BTreeMap<String, HashMap<String, JsonValue>>>,
+    db: ShareLock<Docs>,
+    journal: Option<Arc<Journal>>,
     _t: PhantomData<T>,
 }
 
@@ -25,9 +29,71 @@ impl<T> Collect<T> {
         Self {
             name: name.to_string(),
             db: Arc::new(RwLock::new(BTreeMap::new())),
+            journal: None,
             _t: PhantomData,
         }
     }
+
+    pub fn new_with_journal(name: &str, journal: &Arc<Journal>) -> Self {
+        Self {
+            journal: Some(journal.clone()),
+            ..Self::new(name)
+        }
+    }
+
+    fn write_journal(
+        &self,
+        op: JournalOp,
+        id: &str,
+        doc: Option<HashMap<String, JsonValue>>,
+    ) -> Result<()> {
+        if let Some(journal) = &self.journal {
+            journal.append(&JournalEntry {
+                op,
+                name: self.name.clone(),
+                id: id.to_string(),
+                doc,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// raw documents access for the snapshot and journal
+pub trait DocSet: Send + Sync {
+    fn name(&self) -> &str;
+    fn read(&self) -> RwLockReadGuard<'_, Docs>;
+    fn set_docs(&self, docs: Docs);
+    /// apply the journal entry without writing the journal again
+    fn apply(&self, entry: &JournalEntry);
+}
+
+impl<T: Send + Sync> DocSet for Collect<T> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Docs> {
+        self.db.read().unwrap()
+    }
+
+    fn set_docs(&self, docs: Docs) {
+        *self.db.write().unwrap() = docs;
+    }
+
+    fn apply(&self, entry: &JournalEntry) {
+        let mut db = self.db.write().unwrap();
+        match entry.op {
+            JournalOp::Put => {
+                if let Some(doc) = &entry.doc {
+                    db.insert(entry.id.clone(), doc.clone());
+                }
+            }
+            JournalOp::Delete => {
+                db.remove(&entry.id);
+            }
+        }
+    }
 }
 
 impl<T> DbCollection for Collect<T>
@@ -102,21 +168,8 @@ where
             rows.sort_by(|a, b| {
                 let mut ret = Ordering::Equal;
                 for (order, rev) in q.order_by() {
-                    if *rev {
-                        ret = ret.then(
-                            b.get(order)
-                                .unwrap()
-                                .to_string()
-                                .cmp(&a.get(order).unwrap().to_string()),
-                        );
-                    } else {
-                        ret = ret.then(
-                            a.get(order)
-                                .unwrap()
-                                .to_string()
-                                .cmp(&b.get(order).unwrap().to_string()),
-                        );
-                    }
+                    let (a, b) = if *rev { (b, a) } else { (a, b) };
+                    ret = ret.then(cmp_value(a.get(order), b.get(order)));
                 }
 
                 ret
@@ -143,26 +196,34 @@ where
 
     fn create(&self, data: &Self::Item) -> Result<bool> {
         debug!("mem::{}.create({:?})", self.name, data);
-        self.db
-            .write()
-            .unwrap()
-            .insert(data.id().to_string(), data.doc()?);
+        let doc = data.doc()?;
+
+        // write the journal first and keep the db locked until the doc is applied to have the same order
+        // the map is not changed when failing to write the journal
+        let mut db = self.db.write().unwrap();
+        self.write_journal(JournalOp::Put, data.id(), Some(doc.clone()))?;
+        db.insert(data.id().to_string(), doc);
         Ok(true)
     }
 
     fn update(&self, data: &Self::Item) -> Result<bool> {
         debug!("mem::{}.update({:?})", self.name, data);
-        self.db
-            .write()
-            .unwrap()
-            .entry(data.id().to_string())
-            .and_modify(|iter| *iter = data.doc().unwrap());
+        let doc = data.doc()?;
+        let mut db = self.db.write().unwrap();
+        if let Some(iter) = db.get_mut(data.id()) {
+            self.write_journal(JournalOp::Put, data.id(), Some(doc.clone()))?;
+            *iter = doc;
+        }
         Ok(true)
     }
 
     fn delete(&self, id: &str) -> crate::Result<bool> {
         debug!("mem::{}.delete({:?})", self.name, id);
-        self.db.write().unwrap().remove(id);
+        let mut db = self.db.write().unwrap();
+        if db.contains_key(id) {
+            self.write_journal(JournalOp::Delete, id, None)?;
+            db.remove(id);
+        }
         Ok(true)
     }
 }
@@ -242,6 +303,12 @@ impl Expr {
                 }
                 false
             }
+            ExprOp::Contains => {
+                if let (serde_json::Value::String(v1), serde_json::Value::String(v2)) = (l, r) {
+                    return v1.contains(v2.as_str());
+                }
+                false
+            }
         }
     }
 }
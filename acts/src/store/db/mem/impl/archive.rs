@@ -0,0 +1,25 @@
+use crate::{
+    Result,
+    store::{Archive, db::mem::DbDocument},
+};
+use serde_json::{Value as JsonValue, json};
+use std::collections::HashMap;
+
+impl DbDocument for Archive {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn doc(&self) -> Result<HashMap<String, JsonValue>> {
+        let mut map = HashMap::new();
+        map.insert("id".to_string(), json!(self.id.clone()));
+        map.insert("mid".to_string(), json!(self.mid.clone()));
+        map.insert("name".to_string(), json!(self.name.clone()));
+        map.insert("state".to_string(), json!(self.state.clone()));
+        map.insert("data".to_string(), json!(self.data.clone()));
+        map.insert("end_time".to_string(), json!(self.end_time));
+        map.insert("create_time".to_string(), json!(self.create_time));
+        map.insert("timestamp".to_string(), json!(self.timestamp));
+        Ok(map)
+    }
+}
@@ -0,0 +1,26 @@
+use crate::{
+    Result,
+    store::{History, db::mem::DbDocument},
+};
+use serde_json::{Value as JsonValue, json};
+use std::collections::HashMap;
+
+impl DbDocument for History {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn doc(&self) -> Result<HashMap<String, JsonValue>> {
+        let mut map = HashMap::new();
+        map.insert("id".to_string(), json!(self.id.clone()));
+        map.insert("pid".to_string(), json!(self.pid.clone()));
+        map.insert("tid".to_string(), json!(self.tid.clone()));
+        map.insert("kind".to_string(), json!(self.kind.clone()));
+        map.insert("name".to_string(), json!(self.name.clone()));
+        map.insert("actor".to_string(), json!(self.actor.clone()));
+        map.insert("data".to_string(), json!(self.data.clone()));
+        map.insert("create_time".to_string(), json!(self.create_time));
+        map.insert("timestamp".to_string(), json!(self.timestamp));
+        Ok(map)
+    }
+}
@@ -1,4 +1,6 @@
+mod archive;
 mod event;
+mod history;
 mod message;
 mod model;
 mod package;
@@ -24,6 +24,9 @@ impl DbDocument for Task {
         map.insert("end_time".to_string(), json!(self.end_time));
         map.insert("hooks".to_string(), json!(self.hooks.clone()));
         map.insert("timestamp".to_string(), json!(self.timestamp));
+        map.insert("assignee".to_string(), json!(self.assignee.clone()));
+        map.insert("candidates".to_string(), json!(self.candidates.clone()));
+        map.insert("priority".to_string(), json!(self.priority));
         Ok(map)
     }
 }
@@ -0,0 +1,140 @@
+use crate::{ActError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+const JOURNAL_FILE: &str = "journal.log";
+const SNAPSHOT_FILE: &str = "snapshot.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalOp {
+    Put,
+    Delete,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub op: JournalOp,
+    pub name: String,
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<HashMap<String, JsonValue>>,
+}
+
+/// append-only journal with the snapshot for the mem store
+#[derive(Debug)]
+pub struct Journal {
+    dir: PathBuf,
+    file: Mutex<File>,
+    sync: bool,
+}
+
+impl Journal {
+    pub fn open(dir: &Path, sync: bool) -> Result<Self> {
+        fs::create_dir_all(dir).map_err(map_io_err)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join(JOURNAL_FILE))
+            .map_err(map_io_err)?;
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            file: Mutex::new(file),
+            sync,
+        })
+    }
+
+    pub fn append(&self, entry: &JournalEntry) -> Result<()> {
+        let mut line = serde_json::to_string(entry).map_err(map_io_err)?;
+        line.push('\n');
+
+        let mut file = self.file.lock().unwrap();
+        file.write_all(line.as_bytes()).map_err(map_io_err)?;
+        if self.sync {
+            file.sync_data().map_err(map_io_err)?;
+        }
+        Ok(())
+    }
+
+    /// read the journal entries in order
+    /// the last incomplete line may be caused by crash when writing, the file is truncated
+    /// to the end of the last valid line so that the next append starts from a new line
+    pub fn entries(&self) -> Result<Vec<JournalEntry>> {
+        let file = File::open(self.dir.join(JOURNAL_FILE)).map_err(map_io_err)?;
+        let mut reader = BufReader::new(file);
+        let mut ret = Vec::new();
+        let mut valid_len = 0;
+        let mut torn = false;
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            let len = reader.read_until(b'\n', &mut line).map_err(map_io_err)?;
+            if len == 0 {
+                break;
+            }
+            if line.last() != Some(&b'\n') {
+                torn = true;
+                break;
+            }
+            let text = &line[..len - 1];
+            if !text.is_empty() {
+                match serde_json::from_slice::<JournalEntry>(text) {
+                    Ok(entry) => ret.push(entry),
+                    Err(_) => {
+                        torn = true;
+                        break;
+                    }
+                }
+            }
+            valid_len += len as u64;
+        }
+
+        if torn {
+            let file = self.file.lock().unwrap();
+            file.set_len(valid_len).map_err(map_io_err)?;
+            file.sync_all().map_err(map_io_err)?;
+        }
+        Ok(ret)
+    }
+
+    pub fn snapshot(&self) -> Result<Option<JsonValue>> {
+        let path = self.dir.join(SNAPSHOT_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let text = fs::read_to_string(path).map_err(map_io_err)?;
+        serde_json::from_str(&text).map(Some).map_err(map_io_err)
+    }
+
+    /// write the snapshot and truncate the journal
+    /// the journal is locked while writing to make sure no entry is lost
+    /// the collections should be locked before calling it as the writers lock the journal last
+    pub fn write_snapshot<F: FnOnce() -> JsonValue>(&self, f: F) -> Result<()> {
+        let file = self.file.lock().unwrap();
+        let value = f();
+
+        // write to a temp file first to keep the old snapshot when failing
+        let tmp = self.dir.join(format!("{SNAPSHOT_FILE}.tmp"));
+        let mut tmp_file = File::create(&tmp).map_err(map_io_err)?;
+        tmp_file
+            .write_all(value.to_string().as_bytes())
+            .map_err(map_io_err)?;
+        tmp_file.sync_all().map_err(map_io_err)?;
+        fs::rename(&tmp, self.dir.join(SNAPSHOT_FILE)).map_err(map_io_err)?;
+
+        file.set_len(0).map_err(map_io_err)?;
+        file.sync_all().map_err(map_io_err)
+    }
+}
+
+fn map_io_err(err: impl std::error::Error) -> ActError {
+    ActError::Store(format!("mem store journal: {err}"))
+}
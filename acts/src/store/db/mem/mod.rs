@@ -1,14 +1,20 @@
 mod collect;
 mod r#impl;
+mod journal;
 
 use crate::{
-    Result, data,
+    ActError, Result,
+    config::ConfigMemStore,
+    data,
     store::{DbCollection, data::*},
 };
 pub use collect::Collect;
+use collect::{DocSet, Docs};
+use journal::Journal;
 use serde::{Serialize, de::DeserializeOwned};
-use serde_json::Value as JsonValue;
-use std::{collections::HashMap, sync::Arc};
+use serde_json::{Map, Value as JsonValue};
+use std::{collections::HashMap, path::Path, sync::Arc};
+use tracing::debug;
 
 #[derive(Debug, Clone)]
 pub struct MemStore {
@@ -18,6 +24,9 @@ pub struct MemStore {
     packages: Arc<Collect<Package>>,
     messages: Arc<Collect<Message>>,
     events: Arc<Collect<Event>>,
+    history: Arc<Collect<History>>,
+    archives: Arc<Collect<Archive>>,
+    journal: Option<Arc<Journal>>,
 }
 
 trait DbDocument: Serialize + DeserializeOwned {
@@ -33,6 +42,8 @@ impl MemStore {
         let packages = Collect::new("packages");
         let messages = Collect::new("messages");
         let events = Collect::new("events");
+        let history = Collect::new("history");
+        let archives = Collect::new("archives");
 
         Self {
             models: Arc::new(models),
@@ -41,9 +52,84 @@ impl MemStore {
             packages: Arc::new(packages),
             messages: Arc::new(messages),
             events: Arc::new(events),
+            history: Arc::new(history),
+            archives: Arc::new(archives),
+            journal: None,
         }
     }
 
+    /// open the mem store with the snapshot and journal in the dir
+    /// it loads the snapshot first and then replays the journal
+    pub fn open(options: &ConfigMemStore) -> Result<Self> {
+        debug!("mem::open({:?})", options);
+        let journal = Arc::new(Journal::open(
+            Path::new(&options.dir),
+            options.sync_journal(),
+        )?);
+        let store = Self {
+            models: Arc::new(Collect::new_with_journal("models", &journal)),
+            procs: Arc::new(Collect::new_with_journal("procs", &journal)),
+            tasks: Arc::new(Collect::new_with_journal("tasks", &journal)),
+            packages: Arc::new(Collect::new_with_journal("packages", &journal)),
+            messages: Arc::new(Collect::new_with_journal("messages", &journal)),
+            events: Arc::new(Collect::new_with_journal("events", &journal)),
+            history: Arc::new(Collect::new_with_journal("history", &journal)),
+            archives: Arc::new(Collect::new_with_journal("archives", &journal)),
+            journal: Some(journal.clone()),
+        };
+
+        if let Some(snapshot) = journal.snapshot()? {
+            for collect in store.collects() {
+                if let Some(value) = snapshot.get(collect.name()) {
+                    let docs: Docs = serde_json::from_value(value.clone())
+                        .map_err(|err| ActError::Store(err.to_string()))?;
+                    collect.set_docs(docs);
+                }
+            }
+        }
+
+        let collects = store.collects();
+        for entry in journal.entries()? {
+            if let Some(collect) = collects.iter().find(|c| c.name() == entry.name) {
+                collect.apply(&entry);
+            }
+        }
+
+        Ok(store)
+    }
+
+    /// write the snapshot and truncate the journal
+    /// it is ignored when the store is not opened with dir
+    pub fn snapshot(&self) -> Result<()> {
+        if let Some(journal) = &self.journal {
+            debug!("mem::snapshot");
+            // lock the collections before the journal in the same order as the writers
+            let collects = self.collects();
+            let docs = collects.map(|collect| (collect.name(), collect.read()));
+            journal.write_snapshot(|| {
+                let mut value = Map::new();
+                for (name, docs) in docs.iter() {
+                    value.insert(name.to_string(), serde_json::json!(**docs));
+                }
+                JsonValue::Object(value)
+            })?;
+        }
+        Ok(())
+    }
+
+    fn collects(&self) -> [&dyn DocSet; 8] {
+        [
+            self.models.as_ref(),
+            self.procs.as_ref(),
+            self.tasks.as_ref(),
+            self.packages.as_ref(),
+            self.messages.as_ref(),
+            self.events.as_ref(),
+            self.history.as_ref(),
+            self.archives.as_ref(),
+        ]
+    }
+
     pub fn tasks(&self) -> Arc<dyn DbCollection<Item = data::Task> + Send + Sync> {
         self.tasks.clone()
     }
@@ -67,4 +153,12 @@ impl MemStore {
     pub fn events(&self) -> Arc<dyn DbCollection<Item = data::Event> + Send + Sync> {
         self.events.clone()
     }
+
+    pub fn history(&self) -> Arc<dyn DbCollection<Item = data::History> + Send + Sync> {
+        self.history.clone()
+    }
+
+    pub fn archives(&self) -> Arc<dyn DbCollection<Item = data::Archive> + Send + Sync> {
+        self.archives.clone()
+    }
 }
@@ -36,6 +36,10 @@ pub enum StoreIden {
     Messages,
     #[strum(serialize = "events")]
     Events,
+    #[strum(serialize = "history")]
+    History,
+    #[strum(serialize = "archives")]
+    Archives,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -1,6 +1,6 @@
 use serde::Serialize;
 use serde_json::{Value, json};
-use std::{collections::HashSet, slice::IterMut};
+use std::{cmp::Ordering, collections::HashSet, slice::IterMut};
 
 #[derive(Debug, Clone)]
 pub struct Query {
@@ -23,7 +23,9 @@ pub struct Cond {
     pub result: HashSet<Box<[u8]>>,
 }
 
+/// the expression operators, the stores should return an error for the unsupported ones
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum ExprOp {
     /// equal
     EQ,
@@ -42,6 +44,9 @@ pub enum ExprOp {
 
     /// greater and equal
     GE,
+
+    /// the string contains the value
+    Contains,
 }
 
 #[derive(Debug, Clone)]
@@ -106,6 +111,14 @@ impl Expr {
             value: json!(v),
         }
     }
+
+    pub fn contains(key: &str, v: &str) -> Self {
+        Self {
+            op: ExprOp::Contains,
+            key: key.to_string(),
+            value: json!(v),
+        }
+    }
 }
 
 impl Cond {
@@ -159,19 +172,22 @@ impl Query {
         &self.conds
     }
 
+    /// intersect the results of all the conditions
+    /// the query result is empty when any of the condition results is empty
     pub fn calc(&self) -> HashSet<Box<[u8]>> {
-        let mut result = HashSet::new();
+        let mut result: Option<HashSet<Box<[u8]>>> = None;
         for cond in self.conds.iter() {
-            if result.is_empty() {
-                result = cond.result.clone();
-            } else {
-                result = result
-                    .intersection(&cond.result)
-                    .cloned()
-                    .collect::<HashSet<_>>()
-            }
+            result = match result {
+                None => Some(cond.result.clone()),
+                Some(result) => Some(
+                    result
+                        .intersection(&cond.result)
+                        .cloned()
+                        .collect::<HashSet<_>>(),
+                ),
+            };
         }
-        result
+        result.unwrap_or_default()
     }
 
     pub fn push(mut self, cond: Cond) -> Self {
@@ -225,6 +241,18 @@ impl Query {
     }
 }
 
+/// compare the values to order the rows for the stores without sql
+/// the numbers are compared by value, the others are compared by the json text
+pub fn cmp_value(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    match (a, b) {
+        (Some(Value::Number(a)), Some(Value::Number(b))) => a
+            .as_f64()
+            .unwrap_or_default()
+            .total_cmp(&b.as_f64().unwrap_or_default()),
+        (a, b) => a.map(|v| v.to_string()).cmp(&b.map(|v| v.to_string())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Expr;
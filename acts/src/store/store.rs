@@ -1,6 +1,8 @@
-use super::{DbCollection, DbCollectionIden, StoreIden, data, db::MemStore};
+use super::{
+    DbCollection, DbCollectionIden, PageData, StoreIden, data, db::MemStore, query::Query,
+};
 use crate::{
-    ActError, Result, ShareLock, Workflow,
+    ActError, Config, Result, ShareLock, Workflow,
     store::{Model, Package},
     utils,
 };
@@ -8,7 +10,10 @@ use std::{
     any::Any,
     collections::HashMap,
     convert::AsRef,
-    sync::{Arc, RwLock},
+    sync::{
+        Arc, OnceLock, RwLock,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
 };
 use strum::IntoEnumIterator;
 use tracing::trace;
@@ -16,8 +21,101 @@ use tracing::trace;
 #[derive(Clone)]
 pub struct DynDbSetRef<T>(Arc<dyn DbCollection<Item = T>>);
 
+/// the barrier to block the collection writes only when a read barrier is running
+///
+/// the writes just count themselves without locking when there is no read barrier,
+/// the read barrier waits for the counted writes and blocks the new ones by the lock
+#[derive(Default)]
+struct Barrier {
+    active: AtomicBool,
+    writes: AtomicUsize,
+    lock: RwLock<()>,
+}
+
+/// decrease the running writes even if the write panics
+struct WriteGuard<'a>(&'a AtomicUsize);
+
+impl Drop for WriteGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Barrier {
+    fn write<T, F: FnOnce() -> T>(&self, f: F) -> T {
+        self.writes.fetch_add(1, Ordering::SeqCst);
+        let guard = WriteGuard(&self.writes);
+        if self.active.load(Ordering::SeqCst) {
+            drop(guard);
+            let _lock = self.lock.read().unwrap();
+            return f();
+        }
+        f()
+    }
+
+    fn read<T, F: FnOnce() -> T>(&self, f: F) -> T {
+        let _lock = self.lock.write().unwrap();
+        self.active.store(true, Ordering::SeqCst);
+        while self.writes.load(Ordering::SeqCst) > 0 {
+            std::thread::yield_now();
+        }
+        let ret = f();
+        self.active.store(false, Ordering::SeqCst);
+        ret
+    }
+}
+
+/// the collection wrapper to block the writes when the store read barrier is set
+/// the version is increased after each write to let the readers know the collection is changed
+struct BarrierCollection<T> {
+    inner: Arc<dyn DbCollection<Item = T> + Send + Sync>,
+    barrier: Arc<Barrier>,
+    ver: Option<Arc<AtomicUsize>>,
+}
+
+impl<T> BarrierCollection<T> {
+    fn changed(&self, ret: Result<bool>) -> Result<bool> {
+        if let Some(ver) = &self.ver {
+            ver.fetch_add(1, Ordering::SeqCst);
+        }
+        ret
+    }
+}
+
+impl<T> DbCollection for BarrierCollection<T> {
+    type Item = T;
+
+    fn exists(&self, id: &str) -> Result<bool> {
+        self.inner.exists(id)
+    }
+
+    fn find(&self, id: &str) -> Result<Self::Item> {
+        self.inner.find(id)
+    }
+
+    fn query(&self, query: &Query) -> Result<PageData<Self::Item>> {
+        self.inner.query(query)
+    }
+
+    fn create(&self, data: &Self::Item) -> Result<bool> {
+        self.changed(self.barrier.write(|| self.inner.create(data)))
+    }
+
+    fn update(&self, data: &Self::Item) -> Result<bool> {
+        self.changed(self.barrier.write(|| self.inner.update(data)))
+    }
+
+    fn delete(&self, id: &str) -> Result<bool> {
+        self.changed(self.barrier.write(|| self.inner.delete(id)))
+    }
+}
+
 pub struct Store {
     collections: ShareLock<HashMap<StoreIden, Arc<dyn Any + Send + Sync + 'static>>>,
+    mem: OnceLock<MemStore>,
+    barrier: Arc<Barrier>,
+    packages_ver: Arc<AtomicUsize>,
+    task_history: AtomicBool,
 }
 
 impl Default for Store {
@@ -30,6 +128,10 @@ impl Store {
     pub fn new() -> Self {
         Self {
             collections: Arc::new(RwLock::new(HashMap::new())),
+            mem: OnceLock::new(),
+            barrier: Arc::new(Barrier::default()),
+            packages_ver: Arc::new(AtomicUsize::new(0)),
+            task_history: AtomicBool::new(true),
         }
     }
 
@@ -62,7 +164,40 @@ impl Store {
         DATA: DbCollectionIden + 'static,
     {
         let mut collections = self.collections.write().unwrap();
-        collections.insert(DATA::iden(), Arc::new(DynDbSetRef::<DATA>(collection)));
+        collections.insert(DATA::iden(), self.set_ref(collection));
+        if DATA::iden() == StoreIden::Packages {
+            self.packages_ver.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /// whether to record the task state transitions and vars mutations to the history
+    pub fn is_task_history(&self) -> bool {
+        self.task_history.load(Ordering::SeqCst)
+    }
+
+    /// the version of the packages collection which is changed by each write
+    pub fn packages_ver(&self) -> usize {
+        self.packages_ver.load(Ordering::SeqCst)
+    }
+
+    /// run the reads with all of the collection writes blocked to get a consistent view
+    pub fn read_barrier<T, F: FnOnce() -> T>(&self, f: F) -> T {
+        self.barrier.read(f)
+    }
+
+    fn set_ref<DATA>(
+        &self,
+        collection: Arc<dyn DbCollection<Item = DATA> + Send + Sync + 'static>,
+    ) -> Arc<dyn Any + Send + Sync + 'static>
+    where
+        DATA: DbCollectionIden + 'static,
+    {
+        let ver = (DATA::iden() == StoreIden::Packages).then(|| self.packages_ver.clone());
+        Arc::new(DynDbSetRef::<DATA>(Arc::new(BarrierCollection {
+            inner: collection,
+            barrier: self.barrier.clone(),
+            ver,
+        })))
     }
 
     pub fn tasks(&self) -> Arc<dyn DbCollection<Item = data::Task>> {
@@ -89,6 +224,14 @@ impl Store {
         self.collection()
     }
 
+    pub fn history(&self) -> Arc<dyn DbCollection<Item = data::History>> {
+        self.collection()
+    }
+
+    pub fn archives(&self) -> Arc<dyn DbCollection<Item = data::Archive>> {
+        self.collection()
+    }
+
     pub fn publish(&self, pack: &Package) -> Result<bool> {
         trace!("store::publish({})", pack.id);
         if pack.id.is_empty() {
@@ -152,21 +295,50 @@ impl Store {
         }
     }
 
-    pub fn init(&self) {
-        let mem = MemStore::new();
+    pub fn init(&self, config: &Config) -> Result<()> {
+        self.task_history
+            .store(config.task_history(), Ordering::SeqCst);
+
+        // the mem store is created once and reloads the snapshot and journal when configured
+        let mem = match self.mem.get() {
+            Some(mem) => mem,
+            None => {
+                let mem = match config.mem_store() {
+                    Some(options) => MemStore::open(options).map_err(|err| {
+                        ActError::Store(format!(
+                            "failed to open mem store from dir '{}' with '{err}'",
+                            options.dir
+                        ))
+                    })?,
+                    None => MemStore::new(),
+                };
+                self.mem.get_or_init(|| mem)
+            }
+        };
         let mut collections = self.collections.write().unwrap();
         for item in StoreIden::iter() {
             // fill the mem store when there is no collection
             collections
                 .entry(item.clone())
                 .or_insert_with(|| match item {
-                    StoreIden::Packages => Arc::new(DynDbSetRef(mem.packages())),
-                    StoreIden::Models => Arc::new(DynDbSetRef(mem.models())),
-                    StoreIden::Procs => Arc::new(DynDbSetRef(mem.procs())),
-                    StoreIden::Tasks => Arc::new(DynDbSetRef(mem.tasks())),
-                    StoreIden::Messages => Arc::new(DynDbSetRef(mem.messages())),
-                    StoreIden::Events => Arc::new(DynDbSetRef(mem.events())),
+                    StoreIden::Packages => self.set_ref(mem.packages()),
+                    StoreIden::Models => self.set_ref(mem.models()),
+                    StoreIden::Procs => self.set_ref(mem.procs()),
+                    StoreIden::Tasks => self.set_ref(mem.tasks()),
+                    StoreIden::Messages => self.set_ref(mem.messages()),
+                    StoreIden::Events => self.set_ref(mem.events()),
+                    StoreIden::History => self.set_ref(mem.history()),
+                    StoreIden::Archives => self.set_ref(mem.archives()),
                 });
         }
+        Ok(())
+    }
+
+    /// write the mem store snapshot when the mem store is persisted
+    pub fn snapshot(&self) -> Result<()> {
+        match self.mem.get() {
+            Some(mem) => mem.snapshot(),
+            None => Ok(()),
+        }
     }
 }
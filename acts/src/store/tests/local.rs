@@ -257,6 +257,9 @@ async fn store_local_task_create() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
     tasks.create(&task).unwrap();
     assert!(tasks.exists(&task.id).unwrap());
@@ -282,6 +285,9 @@ async fn store_local_task_find() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
     tasks.create(&task).unwrap();
     assert_eq!(tasks.find(&tid).unwrap().id, tid);
@@ -308,6 +314,9 @@ async fn store_local_task_query() {
             timestamp: 0,
             data: "{}".to_string(),
             err: None,
+            assignee: String::new(),
+            candidates: String::new(),
+            priority: 0,
         };
         tasks.create(&task).unwrap();
     }
@@ -338,6 +347,9 @@ async fn store_local_task_update() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
     table.create(&task).unwrap();
 
@@ -369,6 +381,9 @@ async fn store_local_task_delete() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
     table.create(&task).unwrap();
     table.delete(&task.id).unwrap();
@@ -1,5 +1,6 @@
 use crate::{
     MessageState, TaskState, Vars,
+    config::ConfigMemStore,
     scheduler::NodeKind,
     store::{Cond, Query, data::*, db::MemStore, query::Expr},
     utils,
@@ -76,6 +77,39 @@ async fn store_mem_model_query() {
     assert_eq!(items.count, 5);
 }
 
+#[tokio::test]
+async fn store_mem_model_query_empty_cond() {
+    let store = store().await;
+    let models = store.models();
+    let name = utils::longid();
+    for _ in 0..2 {
+        let model = Model {
+            id: utils::longid(),
+            name: name.clone(),
+            ver: 1,
+            size: 1245,
+            create_time: 3333,
+            update_time: 0,
+            data: "{}".to_string(),
+            timestamp: 0,
+        };
+        models.create(&model).unwrap();
+    }
+
+    // the conditions are intersected, an empty condition result makes the query empty
+    let q = Query::new()
+        .push(Cond::and().push(Expr::eq("name", "not_exists")))
+        .push(Cond::and().push(Expr::eq("name", name.clone())));
+    let items = models.query(&q).unwrap();
+    assert_eq!(items.count, 0);
+
+    let q = Query::new()
+        .push(Cond::and().push(Expr::eq("name", name.clone())))
+        .push(Cond::and().push(Expr::eq("name", "not_exists")));
+    let items = models.query(&q).unwrap();
+    assert_eq!(items.count, 0);
+}
+
 #[tokio::test]
 async fn store_mem_model_update() {
     let store = store().await;
@@ -255,6 +289,9 @@ async fn store_mem_task_create() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
     tasks.create(&task).unwrap();
     assert!(tasks.exists(&task.id).unwrap());
@@ -280,6 +317,9 @@ async fn store_mem_task_find() {
         hooks: "{}".to_string(),
         timestamp: 0,
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
     tasks.create(&task).unwrap();
     assert_eq!(tasks.find(&tid).unwrap().id, tid);
@@ -306,6 +346,9 @@ async fn store_mem_task_query() {
             timestamp: 0,
             data: "{}".to_string(),
             err: None,
+            assignee: String::new(),
+            candidates: String::new(),
+            priority: 0,
         };
         tasks.create(&task).unwrap();
     }
@@ -336,6 +379,9 @@ async fn store_mem_task_update() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
     table.create(&task).unwrap();
 
@@ -367,6 +413,9 @@ async fn store_mem_task_delete() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
     table.create(&task).unwrap();
     table.delete(&task.id).unwrap();
@@ -650,3 +699,146 @@ async fn store_mem_package_remove() {
     let ret = store.packages().find(&package.id);
     assert!(ret.is_err());
 }
+
+#[tokio::test]
+async fn store_mem_history_create() {
+    let store = store().await;
+
+    let history = History::new(
+        &utils::longid(),
+        &utils::shortid(),
+        HistoryKind::Action,
+        "next",
+        "{}",
+    )
+    .with_actor("u1");
+    store.history().create(&history).unwrap();
+    let ret = store.history().find(&history.id).unwrap();
+    assert_eq!(ret.kind, "action");
+    assert_eq!(ret.actor, "u1");
+}
+
+#[tokio::test]
+async fn store_mem_history_query() {
+    let store = store().await;
+
+    let pid = utils::longid();
+    for name in ["running", "completed"] {
+        let history = History::new(&pid, "tid", HistoryKind::State, name, "{}");
+        store.history().create(&history).unwrap();
+    }
+
+    let q = Query::new()
+        .push(Cond::and().push(Expr::eq("pid", pid)))
+        .push_order("timestamp", false);
+    let items = store.history().query(&q).unwrap();
+    assert_eq!(items.count, 2);
+    assert_eq!(items.rows[0].name, "running");
+    assert_eq!(items.rows[1].name, "completed");
+}
+
+#[tokio::test]
+async fn store_mem_journal_replay() {
+    let dir = std::env::temp_dir().join(utils::longid());
+    let options = ConfigMemStore {
+        dir: dir.to_string_lossy().to_string(),
+        snapshot_interval_secs: None,
+        sync_journal: None,
+    };
+
+    let store = MemStore::open(&options).unwrap();
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        let history = History::new(&utils::longid(), "tid", HistoryKind::State, "running", "{}");
+        store.history().create(&history).unwrap();
+        ids.push(history.id);
+    }
+    let mut history = store.history().find(&ids[0]).unwrap();
+    history.name = "completed".to_string();
+    store.history().update(&history).unwrap();
+    store.history().delete(&ids[1]).unwrap();
+
+    let store = MemStore::open(&options).unwrap();
+    assert_eq!(store.history().find(&ids[0]).unwrap().name, "completed");
+    assert!(!store.history().exists(&ids[1]).unwrap());
+    assert!(store.history().exists(&ids[2]).unwrap());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn store_mem_journal_no_sync() {
+    let dir = std::env::temp_dir().join(utils::longid());
+    let options = ConfigMemStore {
+        dir: dir.to_string_lossy().to_string(),
+        snapshot_interval_secs: None,
+        sync_journal: Some(false),
+    };
+
+    let store = MemStore::open(&options).unwrap();
+    let history = History::new(&utils::longid(), "tid", HistoryKind::State, "running", "{}");
+    store.history().create(&history).unwrap();
+    drop(store);
+
+    let store = MemStore::open(&options).unwrap();
+    assert!(store.history().exists(&history.id).unwrap());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn store_mem_journal_torn_line() {
+    let dir = std::env::temp_dir().join(utils::longid());
+    let options = ConfigMemStore {
+        dir: dir.to_string_lossy().to_string(),
+        snapshot_interval_secs: None,
+        sync_journal: None,
+    };
+
+    let store = MemStore::open(&options).unwrap();
+    let history = History::new(&utils::longid(), "tid", HistoryKind::State, "running", "{}");
+    store.history().create(&history).unwrap();
+    drop(store);
+
+    // simulate a crash in the middle of writing the last line
+    let path = dir.join("journal.log");
+    let mut text = std::fs::read_to_string(&path).unwrap();
+    text.push_str(r#"{"op":"put","name":"history","id":"#);
+    std::fs::write(&path, text).unwrap();
+
+    let store = MemStore::open(&options).unwrap();
+    assert!(store.history().exists(&history.id).unwrap());
+    let history2 = History::new(&utils::longid(), "tid", HistoryKind::State, "running", "{}");
+    store.history().create(&history2).unwrap();
+    drop(store);
+
+    let store = MemStore::open(&options).unwrap();
+    assert!(store.history().exists(&history.id).unwrap());
+    assert!(store.history().exists(&history2.id).unwrap());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn store_mem_snapshot() {
+    let dir = std::env::temp_dir().join(utils::longid());
+    let options = ConfigMemStore {
+        dir: dir.to_string_lossy().to_string(),
+        snapshot_interval_secs: None,
+        sync_journal: None,
+    };
+
+    let store = MemStore::open(&options).unwrap();
+    let history = History::new(&utils::longid(), "tid", HistoryKind::State, "running", "{}");
+    store.history().create(&history).unwrap();
+    store.snapshot().unwrap();
+
+    // the journal is truncated after snapshot
+    let journal = std::fs::read_to_string(dir.join("journal.log")).unwrap();
+    assert!(journal.is_empty());
+
+    let history2 = History::new(&utils::longid(), "tid", HistoryKind::State, "running", "{}");
+    store.history().create(&history2).unwrap();
+
+    let store = MemStore::open(&options).unwrap();
+    assert!(store.history().exists(&history.id).unwrap());
+    assert!(store.history().exists(&history2.id).unwrap());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -1,5 +1,5 @@
 use crate::{
-    MessageState, TaskState, Workflow,
+    Config, MessageState, TaskState, Workflow,
     data::Model,
     scheduler::NodeKind,
     store::{Cond, Store, data, query::Expr, query::*},
@@ -13,7 +13,7 @@ use tokio::sync::OnceCell;
 static STORE: OnceCell<Arc<Store>> = OnceCell::const_new();
 async fn init() -> Arc<Store> {
     let store = Store::new();
-    store.init();
+    store.init(&Config::default()).unwrap();
     Arc::new(store)
 }
 
@@ -598,6 +598,9 @@ async fn store_task_create() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
 
     store.tasks().create(&task).expect("create task");
@@ -628,6 +631,9 @@ async fn store_task_query_by_id() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
 
     store.tasks().create(&task).expect("create task");
@@ -659,6 +665,9 @@ async fn store_task_query_by_offset_count() {
             timestamp: 0,
             data: "{}".to_string(),
             err: None,
+            assignee: String::new(),
+            candidates: String::new(),
+            priority: 0,
         };
         store.tasks().create(&task).expect("create task");
     }
@@ -701,6 +710,9 @@ async fn store_task_query_by_cond_and() {
             timestamp: 0,
             data: "{}".to_string(),
             err: None,
+            assignee: String::new(),
+            candidates: String::new(),
+            priority: 0,
         };
         store.tasks().create(&task).expect("create task");
     }
@@ -743,6 +755,9 @@ async fn store_task_query_by_cond_or() {
             timestamp: 0,
             data: "{}".to_string(),
             err: None,
+            assignee: String::new(),
+            candidates: String::new(),
+            priority: 0,
         };
         store.tasks().create(&task).expect("create task");
     }
@@ -764,6 +779,9 @@ async fn store_task_query_by_cond_or() {
             timestamp: 0,
             data: "{}".to_string(),
             err: None,
+            assignee: String::new(),
+            candidates: String::new(),
+            priority: 0,
         };
         store.tasks().create(&task).expect("create task");
     }
@@ -802,6 +820,9 @@ async fn store_task_query_by_order() {
             timestamp: utils::time::timestamp(),
             data: "{}".to_string(),
             err: None,
+            assignee: String::new(),
+            candidates: String::new(),
+            priority: 0,
         };
         store.tasks().create(&task).expect("create task");
     }
@@ -823,6 +844,73 @@ async fn store_task_query_by_order() {
     assert_eq!(ret.rows.last().unwrap().name, "test-1");
 }
 
+#[tokio::test]
+async fn store_task_query_by_candidates() {
+    let store = store().await;
+    let tasks = store.tasks();
+    let pid = utils::shortid();
+    let items = [
+        (
+            "t1",
+            2,
+            Task::join_candidates(&["u1".to_string()], &["g_1".to_string()]),
+        ),
+        ("t2", 10, Task::join_candidates(&["U1".to_string()], &[])),
+        ("t3", 9, Task::join_candidates(&[], &["g1".to_string()])),
+    ];
+    for (tid, priority, candidates) in items {
+        let task = Task {
+            kind: "act".to_string(),
+            id: utils::shortid(),
+            name: "test".to_string(),
+            pid: pid.to_string(),
+            tid: tid.to_string(),
+            node_data: "nid".to_string(),
+            state: "interrupted".to_string(),
+            prev: None,
+            start_time: 0,
+            end_time: 0,
+            hooks: "{}".to_string(),
+            timestamp: 0,
+            data: "{}".to_string(),
+            err: None,
+            assignee: String::new(),
+            candidates,
+            priority,
+        };
+        tasks.create(&task).unwrap();
+    }
+
+    // the candidates are matched exactly and case-sensitively
+    let q = Query::new()
+        .push(Cond::and().push(Expr::eq("pid", pid.clone())))
+        .push(Cond::or().push(Expr::contains("candidates", &Task::candidate_user("u1"))));
+    let items = tasks.query(&q).unwrap();
+    assert_eq!(items.rows.len(), 1);
+    assert_eq!(items.rows[0].tid, "t1");
+
+    let q = Query::new()
+        .push(Cond::and().push(Expr::eq("pid", pid.clone())))
+        .push(Cond::or().push(Expr::contains("candidates", &Task::candidate_group("g1"))));
+    let items = tasks.query(&q).unwrap();
+    assert_eq!(items.rows.len(), 1);
+    assert_eq!(items.rows[0].tid, "t3");
+
+    // the priority is ordered by number
+    let q = Query::new()
+        .push(Cond::and().push(Expr::eq("pid", pid.clone())))
+        .push_order("priority", true);
+    let items = tasks.query(&q).unwrap();
+    assert_eq!(
+        items
+            .rows
+            .iter()
+            .map(|t| t.tid.as_str())
+            .collect::<Vec<_>>(),
+        vec!["t2", "t3", "t1"]
+    );
+}
+
 #[tokio::test]
 async fn store_task_update() {
     let store = store().await;
@@ -845,6 +933,9 @@ async fn store_task_update() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
 
     store.tasks().create(&task).expect("create task");
@@ -880,6 +971,9 @@ async fn store_task_remove() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
 
     store.tasks().create(&task).expect("create task");
@@ -1598,3 +1692,25 @@ fn create_proc(id: &str, state: TaskState, model: &Workflow) -> Proc {
         err: None,
     }
 }
+
+#[tokio::test]
+async fn store_read_barrier() {
+    let store = store().await;
+    let workflow = create_workflow();
+    let pid = utils::longid();
+
+    let handle = store.read_barrier(|| {
+        let id = pid.clone();
+        let handle = std::thread::spawn(move || {
+            let proc = create_proc(&id, TaskState::None, &workflow);
+            store.procs().create(&proc).unwrap();
+        });
+        std::thread::sleep(std::time::Duration::from_millis(100));
+
+        // the write is blocked until the barrier is released
+        assert!(!store.procs().exists(&pid).unwrap());
+        handle
+    });
+    handle.join().unwrap();
+    assert!(store.procs().exists(&pid).unwrap());
+}
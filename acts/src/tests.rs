@@ -1,5 +1,6 @@
+use crate::config::{ConfigData, ConfigMemStore};
 use crate::event::EventAction;
-use crate::{Act, Engine, EngineBuilder, MessageState, Vars, Workflow, utils};
+use crate::{Act, ActError, Engine, EngineBuilder, MessageState, Vars, Workflow, utils};
 use serde::Deserialize;
 use serde_json::json;
 
@@ -303,3 +304,105 @@ async fn engine_get_custom_config() {
     assert_eq!(custom.mystr, "myData");
     assert_eq!(custom.my_option, None);
 }
+
+#[tokio::test]
+async fn engine_restore_from_mem_store_dir() {
+    let dir = std::env::temp_dir().join(utils::longid());
+    let config = ConfigData {
+        mem_store: Some(ConfigMemStore {
+            dir: dir.to_string_lossy().to_string(),
+            snapshot_interval_secs: None,
+            sync_journal: None,
+        }),
+        ..Default::default()
+    };
+    let workflow = Workflow::new().with_id(&utils::longid()).with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::irq(|act| act.with_key("act1")))
+    });
+
+    let engine = EngineBuilder::new()
+        .set_config(&config)
+        .build()
+        .await
+        .unwrap()
+        .start();
+    let sig = engine.signal((String::new(), String::new()));
+    let s = sig.clone();
+    engine.channel().on_message(move |e| {
+        if e.is_key("act1") && e.is_state(MessageState::Created) {
+            s.send((e.pid.clone(), e.tid.clone()));
+        }
+    });
+    engine.executor().model().deploy(&workflow).unwrap();
+    engine
+        .executor()
+        .proc()
+        .start(&workflow.id, &Vars::new())
+        .unwrap();
+    let (pid, tid) = sig.recv().await;
+    engine.close();
+    drop(engine);
+
+    // restart the engine with the same dir
+    let engine = EngineBuilder::new()
+        .set_config(&config)
+        .build()
+        .await
+        .unwrap()
+        .start();
+    let sig = engine.signal(false);
+    let s = sig.clone();
+    engine.channel().on_complete(move |_| {
+        s.send(true);
+    });
+    let proc = engine.executor().proc().get_process(&pid).unwrap();
+    assert!(proc.state().is_running());
+    engine
+        .executor()
+        .act()
+        .complete(&pid, &tid, &Vars::new())
+        .unwrap();
+    assert!(sig.recv().await);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn engine_build_mem_store_dir_error() {
+    // the mem store dir is an existing file
+    let file = std::env::temp_dir().join(utils::longid());
+    std::fs::write(&file, "").unwrap();
+    let config = ConfigData {
+        mem_store: Some(ConfigMemStore {
+            dir: file.to_string_lossy().to_string(),
+            snapshot_interval_secs: None,
+            sync_journal: None,
+        }),
+        ..Default::default()
+    };
+
+    let ret = EngineBuilder::new().set_config(&config).build().await;
+    assert!(matches!(ret, Err(ActError::Store(_))));
+    std::fs::remove_file(&file).unwrap();
+}
+
+#[tokio::test]
+async fn engine_start_mem_store_dir_error() {
+    let file = std::env::temp_dir().join(utils::longid());
+    std::fs::write(&file, "").unwrap();
+    let config = crate::config::Config {
+        data: ConfigData {
+            mem_store: Some(ConfigMemStore {
+                dir: file.to_string_lossy().to_string(),
+                snapshot_interval_secs: None,
+                sync_journal: None,
+            }),
+            ..Default::default()
+        },
+        table: toml::Table::new(),
+    };
+
+    let ret = Engine::new_with_config(&config).init();
+    assert!(matches!(ret, Err(ActError::Store(_))));
+    std::fs::remove_file(&file).unwrap();
+}
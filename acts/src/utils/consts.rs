@@ -13,20 +13,56 @@ pub const ACT_PARAMS_KEY: &str = "params";
 
 pub const ACT_ERR_MESSAGE: &str = "message";
 pub const ACT_ERR_CODE: &str = "ecode";
+/// the error code when the outputs are not satisfied with the output declarations
+pub const ACT_ERR_OUTPUTS: &str = "outputs_invalid";
 
 pub const ACT_INDEX: &str = "$index";
 pub const ACT_VALUE: &str = "$value";
+pub const STEP_LOOP_ITEMS: &str = "$loop_items";
+pub const STEP_SWITCH_VALUE: &str = "$switch";
+/// the process vars when the step task is created to rollback when going back
+pub const STEP_VARS_SNAPSHOT: &str = "$vars_snapshot";
 
 pub const TASK_EMIT_DISABLED: &str = "$emit_disabled";
 pub const TASK_AUOT_COMPLETE: &str = "$auto_complete";
 pub const IS_CATCH_PROCESSED: &str = "$is_catch_processed";
 pub const IS_EVENT_PROCESSED: &str = "$is_event_processed";
 pub const IS_TIMEOUT_PROCESSED_PREFIX: &str = "$is_timeout_";
+pub const IS_ESCALATED_PREFIX: &str = "$is_escalated_";
+
+/// the deadline of the task in milliseconds
+pub const TASK_DEADLINE: &str = "$deadline";
+/// the assignment of the irq act
+pub const TASK_ASSIGNMENT: &str = "$assignment";
+/// the deadline in the message inputs
+pub const MESSAGE_DEADLINE: &str = "deadline";
+/// the assignment in the message inputs
+pub const MESSAGE_ASSIGNMENT: &str = "assignment";
+
+/// the remaining task ids to compensate, set to the root task
+pub const COMPENSATE_TASKS: &str = "$compensate_tasks";
+/// the running compensation task id, set to the root task
+pub const COMPENSATE_TASK: &str = "$compensate_task";
+/// the compensated task id, set to the compensation task
+pub const COMPENSATE_FOR: &str = "$compensate_for";
 
 pub const ACT_OUTPUTS: &str = "$outputs";
 pub const ACT_PARAMS_CACHE: &str = "$params";
 
 pub const ACT_SUBFLOW_TO: &str = "to";
+/// the back option to rollback the vars to the snapshot of the target step
+/// it is `true` for all vars or the list of the var names
+pub const ACT_BACK_ROLLBACK: &str = "rollback";
+
+/// the max running child tasks of the act
+pub const ACT_MAX_CONCURRENCY: &str = "$max_concurrency";
+
+/// the collected outputs of each parallel item
+/// it is kept in the parallel act and exposed under the `output` name
+pub const ACT_COLLECT_RESULTS: &str = "results";
+/// the reduced value of the collected results
+/// it is kept in the parallel act and exposed under the `output` name
+pub const ACT_REDUCE_RESULT: &str = "result";
 
 /// global expose var keys
 pub const ACT_GLOBAL_EXPOSE: &str = "expose";
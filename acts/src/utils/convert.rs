@@ -1,4 +1,4 @@
-use crate::{Context, Vars, scheduler::Task};
+use crate::{ActError, Context, Output, Result, Vars, scheduler::Task};
 use regex::Regex;
 use serde_json::Value as JsonValue;
 use std::sync::Arc;
@@ -92,9 +92,48 @@ pub fn fill_inputs(inputs: &Vars, ctx: &Context) -> Vars {
     ret
 }
 
+/// validate the inputs by the json schema
+/// all of the errors are joined to a descriptive model error
+pub fn check_inputs(name: &str, schema: &JsonValue, inputs: &Vars) -> Result<()> {
+    let value: JsonValue = inputs.clone().into();
+    check_schema(schema, &value).map_err(|err| match err {
+        ActError::Model(err) => ActError::Model(format!("invalid inputs of {name}: {err}").into()),
+        err => ActError::Model(format!("invalid input_schema of {name}: {err}").into()),
+    })
+}
+
+/// validate the value by the json schema
+/// returns the model error with all of the validation errors joined by `; `,
+/// the other errors mean the schema itself is invalid
+pub fn check_schema(schema: &JsonValue, value: &JsonValue) -> Result<()> {
+    let validator = jsonschema::validator_for(schema)?;
+    check_validator(&validator, value)
+}
+
+/// validate the value by the built validator to reuse it for the same schema
+pub fn check_validator(validator: &jsonschema::Validator, value: &JsonValue) -> Result<()> {
+    let errors = validator
+        .iter_errors(value)
+        .map(|err| {
+            let path = err.instance_path.to_string();
+            if path.is_empty() {
+                err.to_string()
+            } else {
+                format!("{err} at '{path}'")
+            }
+        })
+        .collect::<Vec<_>>();
+    if !errors.is_empty() {
+        return Err(ActError::Model(errors.join("; ").into()));
+    }
+
+    Ok(())
+}
+
 /// fill the outputs
 /// 1. if the outputs is an expression, just calculate it
 /// 2. if the env and the outputs both has the same key, using the local outputs
+/// 3. if the outputs is a declaration, find the env value or use the default
 pub fn fill_outputs(outputs: &Vars, ctx: &Context) -> Vars {
     // println!("fill_outputs: outputs={outputs}");
     let mut ret = Vars::new();
@@ -115,6 +154,17 @@ pub fn fill_outputs(outputs: &Vars, ctx: &Context) -> Vars {
             }
         }
 
+        // rule 3
+        if let Some(output) = Output::parse(v) {
+            let value = ctx
+                .task()
+                .find::<JsonValue>(k)
+                .filter(|v| !v.is_null())
+                .unwrap_or(output.default);
+            ret.insert(k.to_string(), value);
+            continue;
+        }
+
         // rule 2
         if v.is_null() {
             // the env value
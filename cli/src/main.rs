@@ -0,0 +1,131 @@
+//! acts command line tool
+//!
+//! acts export <file> --store <mem|redb|sqlite|postgres> [--config <path>]
+//! acts import <file> --store <mem|redb|sqlite|postgres> [--config <path>]
+//!
+//! the bundle format is ndjson when the file ends with `.ndjson`, otherwise it is json
+//! the engine is built without starting, so no process is running while exporting or importing
+//! the `mem` store is only allowed when it is persisted by the `mem_store` config
+use acts::{ActError, Engine, EngineBuilder, Result};
+use std::path::Path;
+
+const USAGE: &str =
+    "usage: acts <export|import> <file> --store <mem|redb|sqlite|postgres> [--config <path>]";
+
+struct Args {
+    cmd: String,
+    file: String,
+    config: Option<String>,
+    store: String,
+}
+
+impl Args {
+    fn parse(args: &[String]) -> Result<Self> {
+        let mut ret = Self {
+            cmd: String::new(),
+            file: String::new(),
+            config: None,
+            store: String::new(),
+        };
+
+        let mut positions = Vec::new();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--config" => ret.config = iter.next().cloned(),
+                "--store" => ret.store = iter.next().cloned().unwrap_or_default(),
+                _ => positions.push(arg.clone()),
+            }
+        }
+        if positions.len() != 2 || ret.store.is_empty() {
+            return Err(ActError::Action(USAGE.to_string()));
+        }
+        ret.cmd = positions[0].clone();
+        ret.file = positions[1].clone();
+
+        Ok(ret)
+    }
+
+    fn is_ndjson(&self) -> bool {
+        self.file.ends_with(".ndjson")
+    }
+}
+
+async fn build(args: &Args) -> Result<Engine> {
+    let mut builder = EngineBuilder::new();
+    if let Some(config) = &args.config {
+        builder = builder.set_config_source(Path::new(config));
+    }
+
+    match args.store.as_str() {
+        "mem" => {}
+        #[cfg(feature = "redb")]
+        "redb" => builder = builder.add_plugin(&acts_store_redb::RedbStore),
+        #[cfg(feature = "sqlite")]
+        "sqlite" => builder = builder.add_plugin(&acts_store_sqlite::SqliteStore),
+        #[cfg(feature = "postgres")]
+        "postgres" => builder = builder.add_plugin(&acts_store_postgres::PostgresStore),
+        store => {
+            return Err(ActError::Config(format!(
+                "the store '{store}' is not supported, please check the enabled features"
+            )));
+        }
+    }
+
+    let engine = builder.build().await?;
+    if args.store == "mem" && engine.config().mem_store().is_none() {
+        engine.close();
+        return Err(ActError::Config(
+            "the mem store is not persisted, please set 'mem_store' in the config".to_string(),
+        ));
+    }
+
+    Ok(engine)
+}
+
+async fn run(args: &Args) -> Result<()> {
+    let engine = build(args).await?;
+    let executor = engine.executor();
+    let backup = executor.backup();
+    match args.cmd.as_str() {
+        "export" => {
+            let text = if args.is_ndjson() {
+                backup.export_ndjson()?
+            } else {
+                backup.export_json()?
+            };
+            std::fs::write(&args.file, text)?;
+            println!("exported to {}", args.file);
+        }
+        "import" => {
+            let text = std::fs::read_to_string(&args.file)?;
+            let count = if args.is_ndjson() {
+                backup.import_ndjson(&text)?
+            } else {
+                backup.import_json(&text)?
+            };
+            println!("imported {count} records from {}", args.file);
+        }
+        cmd => {
+            return Err(ActError::Action(format!(
+                "unknown command '{cmd}'\n{USAGE}"
+            )));
+        }
+    }
+
+    engine.close();
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let ret = match Args::parse(&args) {
+        Ok(args) => run(&args).await,
+        Err(err) => Err(err),
+    };
+    if let Err(err) = ret {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,47 @@
+//! export and import the engine state bundle
+//!
+//! cargo run --example backup -- export backup.ndjson
+//! cargo run --example backup -- import backup.ndjson
+//!
+//! the engine loads `config/acts.toml`, so the bundle can be imported to another store
+//! by changing the config (for example, from the mem store to the sqlite store)
+//! the `acts` command line tool in `cli/` does the same with the store plugins
+use acts::EngineBuilder;
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 3 {
+        println!("usage: backup <export|import> <file>");
+        return;
+    }
+    let (cmd, file) = (args[1].as_str(), args[2].as_str());
+    let is_ndjson = file.ends_with(".ndjson");
+
+    let engine = EngineBuilder::new().build().await.unwrap().start();
+    let executor = engine.executor();
+    let backup = executor.backup();
+    match cmd {
+        "export" => {
+            let text = if is_ndjson {
+                backup.export_ndjson()
+            } else {
+                backup.export_json()
+            }
+            .expect("export bundle");
+            std::fs::write(file, text).expect("write bundle");
+            println!("exported to {file}");
+        }
+        "import" => {
+            let text = std::fs::read_to_string(file).expect("read bundle");
+            let count = if is_ndjson {
+                backup.import_ndjson(&text)
+            } else {
+                backup.import_json(&text)
+            }
+            .expect("import bundle");
+            println!("imported {count} records from {file}");
+        }
+        _ => println!("unknown command '{cmd}'"),
+    }
+}
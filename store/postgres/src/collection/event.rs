@@ -69,7 +69,7 @@ impl DbCollection for EventCollection {
     }
 
     fn query(&self, q: &acts::query::Query) -> Result<acts::PageData<Self::Item>> {
-        let filter = into_query(q);
+        let filter = into_query(q)?;
 
         let mut count_query = SeaQuery::select();
         count_query
@@ -223,7 +223,7 @@ impl DbRow for data::Event {
 }
 
 impl DbInit for EventCollection {
-    fn init(&self) {
+    fn init(&self) -> Result<()> {
         let sql = [Table::create()
             .table(CollectionIden::Table)
             .if_not_exists()
@@ -250,7 +250,8 @@ impl DbInit for EventCollection {
             )
             .build(PostgresQueryBuilder)];
 
-        self.conn.batch_execute(&sql).unwrap();
+        self.conn.batch_execute(&sql).map_err(map_db_err)?;
+        Ok(())
     }
 }
 
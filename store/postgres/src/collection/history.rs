@@ -0,0 +1,287 @@
+use crate::database::{DbInit, DbRow};
+use acts::{DbCollection, PageData, Result, data};
+use sea_query::{
+    Alias as SeaAlias, ColumnDef, Expr as SeaExpr, Func as SeaFunc, Iden, Index, Order as SeaOrder,
+    PostgresQueryBuilder, Query as SeaQuery, Table,
+};
+use sea_query_binder::SqlxBinder;
+use sqlx::{Error as DbError, Row, postgres::PgRow};
+
+use super::{DbConnection, into_query, map_db_err};
+
+#[derive(Debug)]
+pub struct HistoryCollection {
+    conn: DbConnection,
+}
+
+#[derive(Iden)]
+#[iden = "history"]
+enum CollectionIden {
+    Table,
+    Id,
+    Pid,
+    Tid,
+    Kind,
+    Name,
+    Actor,
+    Data,
+    CreateTime,
+    Timestamp,
+}
+
+impl DbCollection for HistoryCollection {
+    type Item = data::History;
+
+    fn exists(&self, id: &str) -> Result<bool> {
+        let (sql, values) = SeaQuery::select()
+            .from(CollectionIden::Table)
+            .expr(SeaFunc::count(SeaExpr::col(CollectionIden::Id)))
+            .and_where(SeaExpr::col(CollectionIden::Id).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let count = self
+            .conn
+            .query_one(sql.as_str(), values)
+            .map(|row| row.get::<i64, usize>(0))
+            .map_err(map_db_err)?;
+        Ok(count > 0)
+    }
+
+    fn find(&self, id: &str) -> Result<Self::Item> {
+        let (sql, values) = SeaQuery::select()
+            .from(CollectionIden::Table)
+            .columns([
+                CollectionIden::Id,
+                CollectionIden::Pid,
+                CollectionIden::Tid,
+                CollectionIden::Kind,
+                CollectionIden::Name,
+                CollectionIden::Actor,
+                CollectionIden::Data,
+                CollectionIden::CreateTime,
+                CollectionIden::Timestamp,
+            ])
+            .and_where(SeaExpr::col(CollectionIden::Id).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        self.conn
+            .query_one(sql.as_str(), values)
+            .map(|row| Self::Item::from_row(&row).map_err(map_db_err))
+            .map_err(map_db_err)?
+    }
+
+    fn query(&self, q: &acts::query::Query) -> Result<acts::PageData<Self::Item>> {
+        let filter = into_query(q)?;
+
+        let mut count_query = SeaQuery::select();
+        count_query
+            .from(CollectionIden::Table)
+            .expr(SeaFunc::count(SeaExpr::col(CollectionIden::Id)));
+
+        let mut query = SeaQuery::select();
+        query
+            .columns([
+                CollectionIden::Id,
+                CollectionIden::Pid,
+                CollectionIden::Tid,
+                CollectionIden::Kind,
+                CollectionIden::Name,
+                CollectionIden::Actor,
+                CollectionIden::Data,
+                CollectionIden::CreateTime,
+                CollectionIden::Timestamp,
+            ])
+            .from(CollectionIden::Table);
+
+        if !filter.is_empty() {
+            count_query.cond_where(filter.clone());
+            query.cond_where(filter);
+        }
+
+        if !q.order_by().is_empty() {
+            for (order, rev) in q.order_by().iter() {
+                query.order_by(
+                    SeaAlias::new(order),
+                    if *rev { SeaOrder::Desc } else { SeaOrder::Asc },
+                );
+            }
+        }
+        let (sql, values) = query
+            .limit(q.limit() as u64)
+            .offset(q.offset() as u64)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let (count_sql, count_values) = count_query.build_sqlx(PostgresQueryBuilder);
+        let count = self
+            .conn
+            .query_one(count_sql.as_str(), count_values)
+            .map_err(map_db_err)?
+            .get::<i64, usize>(0) as usize;
+        let page_count = count.div_ceil(q.limit());
+        let page_num = q.offset() / q.limit() + 1;
+        let data = PageData {
+            count,
+            page_size: q.limit(),
+            page_num,
+            page_count,
+            rows: self
+                .conn
+                .query(&sql, values)
+                .map_err(map_db_err)?
+                .iter()
+                .map(|row| Self::Item::from_row(row).unwrap())
+                .collect::<Vec<_>>(),
+        };
+        Ok(data)
+    }
+
+    fn create(&self, data: &Self::Item) -> Result<bool> {
+        let data = data.clone();
+        let (sql, sql_values) = SeaQuery::insert()
+            .into_table(CollectionIden::Table)
+            .columns([
+                CollectionIden::Id,
+                CollectionIden::Pid,
+                CollectionIden::Tid,
+                CollectionIden::Kind,
+                CollectionIden::Name,
+                CollectionIden::Actor,
+                CollectionIden::Data,
+                CollectionIden::CreateTime,
+                CollectionIden::Timestamp,
+            ])
+            .values([
+                data.id.into(),
+                data.pid.into(),
+                data.tid.into(),
+                data.kind.into(),
+                data.name.into(),
+                data.actor.into(),
+                data.data.into(),
+                data.create_time.into(),
+                data.timestamp.into(),
+            ])
+            .map_err(map_db_err)?
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = self
+            .conn
+            .execute(sql.as_str(), sql_values)
+            .map_err(map_db_err)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn update(&self, data: &Self::Item) -> Result<bool> {
+        let model = data.clone();
+        let (sql, sql_values) = SeaQuery::update()
+            .table(CollectionIden::Table)
+            .values([
+                (CollectionIden::Pid, model.pid.into()),
+                (CollectionIden::Tid, model.tid.into()),
+                (CollectionIden::Kind, model.kind.into()),
+                (CollectionIden::Name, model.name.into()),
+                (CollectionIden::Actor, model.actor.into()),
+                (CollectionIden::Data, model.data.into()),
+                (CollectionIden::CreateTime, model.create_time.into()),
+                (CollectionIden::Timestamp, model.timestamp.into()),
+            ])
+            .and_where(SeaExpr::col(CollectionIden::Id).eq(data.id()))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = self
+            .conn
+            .execute(sql.as_str(), sql_values)
+            .map_err(map_db_err)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn delete(&self, id: &str) -> Result<bool> {
+        let (sql, values) = SeaQuery::delete()
+            .from_table(CollectionIden::Table)
+            .and_where(SeaExpr::col(CollectionIden::Id).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = self
+            .conn
+            .execute(sql.as_str(), values)
+            .map_err(map_db_err)?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+impl DbRow for data::History {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn from_row(row: &PgRow) -> std::result::Result<Self, DbError>
+    where
+        Self: Sized,
+    {
+        Ok(Self {
+            id: row.get("id"),
+            pid: row.get("pid"),
+            tid: row.get("tid"),
+            kind: row.get("kind"),
+            name: row.get("name"),
+            actor: row.get("actor"),
+            data: row.get("data"),
+            create_time: row.get("create_time"),
+            timestamp: row.get("timestamp"),
+        })
+    }
+}
+
+impl DbInit for HistoryCollection {
+    fn init(&self) -> Result<()> {
+        let sql = [
+            Table::create()
+                .table(CollectionIden::Table)
+                .if_not_exists()
+                .col(
+                    ColumnDef::new(CollectionIden::Id)
+                        .string()
+                        .not_null()
+                        .primary_key(),
+                )
+                .col(ColumnDef::new(CollectionIden::Pid).string().not_null())
+                .col(ColumnDef::new(CollectionIden::Tid).string().not_null())
+                .col(ColumnDef::new(CollectionIden::Kind).string().not_null())
+                .col(ColumnDef::new(CollectionIden::Name).string().not_null())
+                .col(ColumnDef::new(CollectionIden::Actor).string().not_null())
+                .col(ColumnDef::new(CollectionIden::Data).string())
+                .col(
+                    ColumnDef::new(CollectionIden::CreateTime)
+                        .big_integer()
+                        .default(0),
+                )
+                .col(
+                    ColumnDef::new(CollectionIden::Timestamp)
+                        .big_integer()
+                        .default(0),
+                )
+                .build(PostgresQueryBuilder),
+            Index::create()
+                .name("idx_history_pid")
+                .if_not_exists()
+                .table(CollectionIden::Table)
+                .col(CollectionIden::Pid)
+                .build(PostgresQueryBuilder),
+            Index::create()
+                .name("idx_history_tid")
+                .if_not_exists()
+                .table(CollectionIden::Table)
+                .col(CollectionIden::Tid)
+                .build(PostgresQueryBuilder),
+        ];
+
+        self.conn.batch_execute(&sql).map_err(map_db_err)?;
+        Ok(())
+    }
+}
+
+impl HistoryCollection {
+    pub fn new(conn: &DbConnection) -> Self {
+        Self { conn: conn.clone() }
+    }
+}
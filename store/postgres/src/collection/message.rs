@@ -102,7 +102,7 @@ impl DbCollection for MessageCollection {
     }
 
     fn query(&self, q: &acts::query::Query) -> Result<acts::PageData<Self::Item>> {
-        let filter = into_query(q);
+        let filter = into_query(q)?;
 
         let mut count_query = SeaQuery::select();
         count_query
@@ -337,7 +337,7 @@ impl DbRow for data::Message {
 }
 
 impl DbInit for MessageCollection {
-    fn init(&self) {
+    fn init(&self) -> Result<()> {
         let sql = [
             Table::create()
                 .table(CollectionIden::Table)
@@ -420,7 +420,8 @@ impl DbInit for MessageCollection {
                 .col(CollectionIden::Status)
                 .build(PostgresQueryBuilder),
         ];
-        self.conn.batch_execute(&sql).unwrap();
+        self.conn.batch_execute(&sql).map_err(map_db_err)?;
+        Ok(())
     }
 }
 
@@ -69,7 +69,7 @@ impl DbCollection for ModelCollection {
     }
 
     fn query(&self, q: &acts::query::Query) -> Result<acts::PageData<Self::Item>> {
-        let filter = into_query(q);
+        let filter = into_query(q)?;
 
         let mut count_query = SeaQuery::select();
         count_query
@@ -226,7 +226,7 @@ impl DbRow for data::Model {
 }
 
 impl DbInit for ModelCollection {
-    fn init(&self) {
+    fn init(&self) -> Result<()> {
         let sql = [
             Table::create()
                 .table(CollectionIden::Table)
@@ -265,7 +265,8 @@ impl DbInit for ModelCollection {
                 .build(PostgresQueryBuilder),
         ];
 
-        self.conn.batch_execute(&sql).unwrap();
+        self.conn.batch_execute(&sql).map_err(map_db_err)?;
+        Ok(())
     }
 }
 
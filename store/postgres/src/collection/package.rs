@@ -81,7 +81,7 @@ impl DbCollection for PackageCollection {
     }
 
     fn query(&self, q: &acts::query::Query) -> Result<acts::PageData<Self::Item>> {
-        let filter = into_query(q);
+        let filter = into_query(q)?;
 
         let mut count_query = SeaQuery::select();
         count_query
@@ -264,7 +264,7 @@ impl DbRow for data::Package {
 }
 
 impl DbInit for PackageCollection {
-    fn init(&self) {
+    fn init(&self) -> Result<()> {
         let sql = [
             Table::create()
                 .table(CollectionIden::Table)
@@ -315,7 +315,8 @@ impl DbInit for PackageCollection {
                 .col(CollectionIden::Version)
                 .build(PostgresQueryBuilder),
         ];
-        self.conn.batch_execute(&sql).unwrap();
+        self.conn.batch_execute(&sql).map_err(map_db_err)?;
+        Ok(())
     }
 }
 
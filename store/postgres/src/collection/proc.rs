@@ -73,7 +73,7 @@ impl DbCollection for ProcCollection {
     }
 
     fn query(&self, q: &acts::query::Query) -> Result<acts::PageData<Self::Item>> {
-        let filter = into_query(q);
+        let filter = into_query(q)?;
 
         let mut count_query = SeaQuery::select();
         count_query
@@ -240,7 +240,7 @@ impl DbRow for data::Proc {
 }
 
 impl DbInit for ProcCollection {
-    fn init(&self) {
+    fn init(&self) -> Result<()> {
         let sql = [
             Table::create()
                 .table(CollectionIden::Table)
@@ -286,7 +286,8 @@ impl DbInit for ProcCollection {
                 .col(CollectionIden::Mid)
                 .build(PostgresQueryBuilder),
         ];
-        self.conn.batch_execute(&sql).unwrap();
+        self.conn.batch_execute(&sql).map_err(map_db_err)?;
+        Ok(())
     }
 }
 
@@ -33,6 +33,10 @@ enum CollectionIden {
     EndTime,
     Hooks,
     Timestamp,
+
+    Assignee,
+    Candidates,
+    Priority,
 }
 
 impl DbCollection for TaskCollection {
@@ -72,6 +76,9 @@ impl DbCollection for TaskCollection {
                 CollectionIden::EndTime,
                 CollectionIden::Hooks,
                 CollectionIden::Timestamp,
+                CollectionIden::Assignee,
+                CollectionIden::Candidates,
+                CollectionIden::Priority,
             ])
             .and_where(SeaExpr::col(CollectionIden::Id).eq(id))
             .build_sqlx(PostgresQueryBuilder);
@@ -83,7 +90,7 @@ impl DbCollection for TaskCollection {
     }
 
     fn query(&self, q: &acts::query::Query) -> Result<acts::PageData<Self::Item>> {
-        let filter = into_query(q);
+        let filter = into_query(q)?;
 
         let mut count_query = SeaQuery::select();
         count_query
@@ -107,6 +114,9 @@ impl DbCollection for TaskCollection {
                 CollectionIden::EndTime,
                 CollectionIden::Hooks,
                 CollectionIden::Timestamp,
+                CollectionIden::Assignee,
+                CollectionIden::Candidates,
+                CollectionIden::Priority,
             ])
             .from(CollectionIden::Table);
 
@@ -171,6 +181,9 @@ impl DbCollection for TaskCollection {
                 CollectionIden::EndTime,
                 CollectionIden::Hooks,
                 CollectionIden::Timestamp,
+                CollectionIden::Assignee,
+                CollectionIden::Candidates,
+                CollectionIden::Priority,
             ])
             .values([
                 data.id.into(),
@@ -187,6 +200,9 @@ impl DbCollection for TaskCollection {
                 data.end_time.into(),
                 data.hooks.into(),
                 data.timestamp.into(),
+                data.assignee.into(),
+                data.candidates.into(),
+                data.priority.into(),
             ])
             .map_err(map_db_err)?
             .build_sqlx(PostgresQueryBuilder);
@@ -216,6 +232,9 @@ impl DbCollection for TaskCollection {
                 (CollectionIden::EndTime, model.end_time.into()),
                 (CollectionIden::Hooks, model.hooks.into()),
                 (CollectionIden::Timestamp, model.timestamp.into()),
+                (CollectionIden::Assignee, model.assignee.into()),
+                (CollectionIden::Candidates, model.candidates.into()),
+                (CollectionIden::Priority, model.priority.into()),
             ])
             .and_where(SeaExpr::col(CollectionIden::Id).eq(data.id()))
             .build_sqlx(PostgresQueryBuilder);
@@ -265,48 +284,56 @@ impl DbRow for data::Task {
             end_time: row.get("end_time"),
             hooks: row.get("hooks"),
             timestamp: row.get("timestamp"),
+            assignee: row.get("assignee"),
+            candidates: row.get("candidates"),
+            priority: row.get("priority"),
         })
     }
 }
 
 impl DbInit for TaskCollection {
-    fn init(&self) {
+    fn init(&self) -> Result<()> {
+        let mut table = Table::create();
+        table
+            .table(CollectionIden::Table)
+            .if_not_exists()
+            .col(
+                ColumnDef::new(CollectionIden::Id)
+                    .string()
+                    .not_null()
+                    .primary_key(),
+            )
+            .col(ColumnDef::new(CollectionIden::Pid).string().not_null())
+            .col(ColumnDef::new(CollectionIden::Tid).string().not_null())
+            .col(ColumnDef::new(CollectionIden::NodeData).string())
+            .col(ColumnDef::new(CollectionIden::Kind).string().not_null())
+            .col(ColumnDef::new(CollectionIden::Prev).string())
+            .col(ColumnDef::new(CollectionIden::Name).string())
+            .col(ColumnDef::new(CollectionIden::State).string().not_null())
+            .col(ColumnDef::new(CollectionIden::Data).string())
+            .col(ColumnDef::new(CollectionIden::Err).string())
+            .col(
+                ColumnDef::new(CollectionIden::StartTime)
+                    .big_integer()
+                    .default(0),
+            )
+            .col(
+                ColumnDef::new(CollectionIden::EndTime)
+                    .big_integer()
+                    .default(0),
+            )
+            .col(
+                ColumnDef::new(CollectionIden::Timestamp)
+                    .big_integer()
+                    .default(0),
+            )
+            .col(ColumnDef::new(CollectionIden::Hooks).string());
+        for col in assignment_columns() {
+            table.col(col);
+        }
+
         let sql = [
-            Table::create()
-                .table(CollectionIden::Table)
-                .if_not_exists()
-                .col(
-                    ColumnDef::new(CollectionIden::Id)
-                        .string()
-                        .not_null()
-                        .primary_key(),
-                )
-                .col(ColumnDef::new(CollectionIden::Pid).string().not_null())
-                .col(ColumnDef::new(CollectionIden::Tid).string().not_null())
-                .col(ColumnDef::new(CollectionIden::NodeData).string())
-                .col(ColumnDef::new(CollectionIden::Kind).string().not_null())
-                .col(ColumnDef::new(CollectionIden::Prev).string())
-                .col(ColumnDef::new(CollectionIden::Name).string())
-                .col(ColumnDef::new(CollectionIden::State).string().not_null())
-                .col(ColumnDef::new(CollectionIden::Data).string())
-                .col(ColumnDef::new(CollectionIden::Err).string())
-                .col(
-                    ColumnDef::new(CollectionIden::StartTime)
-                        .big_integer()
-                        .default(0),
-                )
-                .col(
-                    ColumnDef::new(CollectionIden::EndTime)
-                        .big_integer()
-                        .default(0),
-                )
-                .col(
-                    ColumnDef::new(CollectionIden::Timestamp)
-                        .big_integer()
-                        .default(0),
-                )
-                .col(ColumnDef::new(CollectionIden::Hooks).string())
-                .build(PostgresQueryBuilder),
+            table.build(PostgresQueryBuilder),
             Index::create()
                 .name("idx_tasks_pid")
                 .if_not_exists()
@@ -320,11 +347,71 @@ impl DbInit for TaskCollection {
                 .col(CollectionIden::Tid)
                 .build(PostgresQueryBuilder),
         ];
+        self.conn.batch_execute(&sql).map_err(map_db_err)?;
 
-        self.conn.batch_execute(&sql).unwrap();
+        // add the assignment columns to the table created by the earlier versions
+        let (sql, values) = SeaQuery::select()
+            .column(SeaAlias::new("column_name"))
+            .from((
+                SeaAlias::new("information_schema"),
+                SeaAlias::new("columns"),
+            ))
+            .and_where(SeaExpr::col(SeaAlias::new("table_name")).eq("tasks"))
+            .build_sqlx(PostgresQueryBuilder);
+        let names = self
+            .conn
+            .query(&sql, values)
+            .map_err(map_db_err)?
+            .iter()
+            .map(|row| row.try_get::<String, _>("column_name"))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(map_db_err)?;
+        let mut sql = Vec::new();
+        for mut col in assignment_columns() {
+            if names.contains(&col.get_column_name()) {
+                continue;
+            }
+            sql.push(
+                Table::alter()
+                    .table(CollectionIden::Table)
+                    .add_column(&mut col)
+                    .build(PostgresQueryBuilder),
+            );
+        }
+        sql.push(
+            Index::create()
+                .name("idx_tasks_assignee")
+                .if_not_exists()
+                .table(CollectionIden::Table)
+                .col(CollectionIden::Assignee)
+                .build(PostgresQueryBuilder),
+        );
+        self.conn.batch_execute(&sql).map_err(map_db_err)?;
+        Ok(())
     }
 }
 
+/// the assignment columns added after the table is created
+fn assignment_columns() -> Vec<ColumnDef> {
+    vec![
+        ColumnDef::new(CollectionIden::Assignee)
+            .string()
+            .not_null()
+            .default("")
+            .to_owned(),
+        ColumnDef::new(CollectionIden::Candidates)
+            .string()
+            .not_null()
+            .default("")
+            .to_owned(),
+        ColumnDef::new(CollectionIden::Priority)
+            .integer()
+            .not_null()
+            .default(0)
+            .to_owned(),
+    ]
+}
+
 impl TaskCollection {
     pub fn new(conn: &DbConnection) -> Self {
         Self { conn: conn.clone() }
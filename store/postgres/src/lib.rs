@@ -29,7 +29,7 @@ impl ActPlugin for PostgresStore {
             .map_err(|err| ActError::Config(format!("get postgres config error: {}", err)))?;
 
         let db = database::Database::new(&config.database_url);
-        db.init();
+        db.init()?;
 
         engine.extender().register_collection(db.packages());
         engine.extender().register_collection(db.models());
@@ -37,6 +37,8 @@ impl ActPlugin for PostgresStore {
         engine.extender().register_collection(db.tasks());
         engine.extender().register_collection(db.messages());
         engine.extender().register_collection(db.events());
+        engine.extender().register_collection(db.history());
+        engine.extender().register_collection(db.archives());
 
         Ok(())
     }
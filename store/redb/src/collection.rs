@@ -0,0 +1,353 @@
+mod archive;
+mod event;
+mod history;
+mod message;
+mod model;
+mod package;
+mod proc;
+mod task;
+
+pub use archive::ArchiveCollection;
+pub use event::EventCollection;
+pub use history::HistoryCollection;
+pub use message::MessageCollection;
+pub use model::ModelCollection;
+pub use package::PackageCollection;
+pub use proc::ProcCollection;
+pub use task::TaskCollection;
+
+use crate::database::{DbDocument, DbInit};
+use acts::{
+    ActError, DbCollection, PageData, Result,
+    query::{Cond, CondType, ExprOp, Query, cmp_value},
+};
+use redb::{
+    Database, MultimapTableDefinition, MultimapTableHandle, ReadTransaction, ReadableTable,
+    TableDefinition, WriteTransaction,
+};
+use serde_json::Value as JsonValue;
+use std::{cmp::Ordering, collections::HashSet, fmt::Debug, marker::PhantomData, sync::Arc};
+
+pub type DbConnection = Arc<Database>;
+
+fn map_db_err(err: impl std::error::Error) -> ActError {
+    ActError::Store(err.to_string())
+}
+
+#[derive(Debug)]
+pub struct Collection<T> {
+    conn: DbConnection,
+    _t: PhantomData<T>,
+}
+
+impl<T> Collection<T>
+where
+    T: DbDocument,
+{
+    pub fn new(conn: &DbConnection) -> Self {
+        Self {
+            conn: conn.clone(),
+            _t: PhantomData,
+        }
+    }
+
+    fn table() -> TableDefinition<'static, &'static str, &'static str> {
+        TableDefinition::new(T::NAME)
+    }
+
+    /// the index table maps the key value to the doc ids
+    fn index_table(
+        name: &'static str,
+    ) -> MultimapTableDefinition<'static, &'static str, &'static str> {
+        MultimapTableDefinition::new(name)
+    }
+
+    /// move the doc id from the prev key values to the new ones in the index tables
+    fn update_indexes(
+        tx: &WriteTransaction,
+        id: &str,
+        prev: Option<&JsonValue>,
+        doc: Option<&JsonValue>,
+    ) -> Result<()> {
+        for (key, name) in T::INDEXES {
+            let prev = prev.and_then(|doc| doc.get(*key)).map(index_value);
+            let value = doc.and_then(|doc| doc.get(*key)).map(index_value);
+            if prev == value {
+                continue;
+            }
+            let mut index = tx
+                .open_multimap_table(Self::index_table(name))
+                .map_err(map_db_err)?;
+            if let Some(prev) = &prev {
+                index.remove(prev.as_str(), id).map_err(map_db_err)?;
+            }
+            if let Some(value) = &value {
+                index.insert(value.as_str(), id).map_err(map_db_err)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// get the doc ids by the indexes for the query
+    /// returns none when no condition can use the indexes and the table should be scanned
+    fn index_ids(tx: &ReadTransaction, q: &Query) -> Result<Option<HashSet<String>>> {
+        let mut q = q.clone();
+        let mut ret: Option<HashSet<String>> = None;
+        for cond in q.queries().iter() {
+            let Some(ids) = Self::cond_ids(tx, cond)? else {
+                continue;
+            };
+            ret = Some(match ret {
+                Some(ret) => ret.intersection(&ids).cloned().collect(),
+                None => ids,
+            });
+        }
+        Ok(ret)
+    }
+
+    /// the `and` cond uses any `eq` expr on the indexed key
+    /// the `or` cond uses the indexes only when all of the exprs are `eq` on the indexed keys
+    fn cond_ids(tx: &ReadTransaction, cond: &Cond) -> Result<Option<HashSet<String>>> {
+        let indexed = cond
+            .conds()
+            .iter()
+            .filter(|expr| expr.op == ExprOp::EQ)
+            .filter_map(|expr| {
+                T::INDEXES
+                    .iter()
+                    .find(|(key, _)| *key == expr.key())
+                    .map(|(_, name)| (*name, index_value(expr.value())))
+            })
+            .collect::<Vec<_>>();
+        let items = match cond.r#type {
+            CondType::And => indexed.into_iter().take(1).collect::<Vec<_>>(),
+            CondType::Or if indexed.len() == cond.conds().len() => indexed,
+            CondType::Or => Vec::new(),
+        };
+        if items.is_empty() {
+            return Ok(None);
+        }
+
+        let mut ids = HashSet::new();
+        for (name, value) in items {
+            let index = tx
+                .open_multimap_table(Self::index_table(name))
+                .map_err(map_db_err)?;
+            for id in index.get(value.as_str()).map_err(map_db_err)? {
+                ids.insert(id.map_err(map_db_err)?.value().to_string());
+            }
+        }
+        Ok(Some(ids))
+    }
+
+    fn get(&self, id: &str) -> Result<Option<String>> {
+        let tx = self.conn.begin_read().map_err(map_db_err)?;
+        let table = tx.open_table(Self::table()).map_err(map_db_err)?;
+        let value = table.get(id).map_err(map_db_err)?;
+        Ok(value.map(|v| v.value().to_string()))
+    }
+
+    fn put(&self, data: &T) -> Result<bool> {
+        let doc = serde_json::to_value(data).map_err(map_db_err)?;
+        let text = doc.to_string();
+        let tx = self.conn.begin_write().map_err(map_db_err)?;
+        {
+            let mut table = tx.open_table(Self::table()).map_err(map_db_err)?;
+            let prev = table
+                .insert(data.id(), text.as_str())
+                .map_err(map_db_err)?
+                .map(|v| serde_json::from_str::<JsonValue>(v.value()))
+                .transpose()
+                .map_err(map_db_err)?;
+            Self::update_indexes(&tx, data.id(), prev.as_ref(), Some(&doc))?;
+        }
+        tx.commit().map_err(map_db_err)?;
+        Ok(true)
+    }
+}
+
+impl<T> DbCollection for Collection<T>
+where
+    T: DbDocument + Send + Sync + Clone + Debug,
+{
+    type Item = T;
+
+    fn exists(&self, id: &str) -> Result<bool> {
+        Ok(self.get(id)?.is_some())
+    }
+
+    fn find(&self, id: &str) -> Result<Self::Item> {
+        match self.get(id)? {
+            Some(text) => serde_json::from_str(&text).map_err(map_db_err),
+            None => Err(ActError::Store(format!(
+                "cannot find {} by '{}'",
+                T::NAME,
+                id
+            ))),
+        }
+    }
+
+    fn query(&self, q: &Query) -> Result<PageData<Self::Item>> {
+        let tx = self.conn.begin_read().map_err(map_db_err)?;
+        let table = tx.open_table(Self::table()).map_err(map_db_err)?;
+
+        // load the docs by the indexes or scan the table
+        let mut texts = Vec::new();
+        match Self::index_ids(&tx, q)? {
+            Some(ids) => {
+                // keep the same order as scanning the table
+                let mut ids = ids.into_iter().collect::<Vec<_>>();
+                ids.sort();
+                for id in ids.iter() {
+                    if let Some(value) = table.get(id.as_str()).map_err(map_db_err)? {
+                        texts.push(value.value().to_string());
+                    }
+                }
+            }
+            None => {
+                for item in table.iter().map_err(map_db_err)? {
+                    let (_, value) = item.map_err(map_db_err)?;
+                    texts.push(value.value().to_string());
+                }
+            }
+        }
+
+        let mut rows = Vec::new();
+        for text in texts.iter() {
+            let doc: JsonValue = serde_json::from_str(text).map_err(map_db_err)?;
+            if is_match(q, &doc)? {
+                rows.push(doc);
+            }
+        }
+
+        // order the rows
+        if !q.order_by().is_empty() {
+            rows.sort_by(|a, b| {
+                let mut ret = Ordering::Equal;
+                for (order, rev) in q.order_by() {
+                    let (a, b) = if *rev { (b, a) } else { (a, b) };
+                    ret = ret.then(cmp_value(a.get(order), b.get(order)));
+                }
+
+                ret
+            });
+        }
+
+        let count = rows.len();
+        let page_count = count.div_ceil(q.limit());
+        let page_num = q.offset() / q.limit() + 1;
+        let data = PageData {
+            count,
+            page_size: q.limit(),
+            page_num,
+            page_count,
+            rows: rows
+                .into_iter()
+                .skip(q.offset())
+                .take(q.limit())
+                .map(|row| serde_json::from_value(row).map_err(map_db_err))
+                .collect::<Result<Vec<_>>>()?,
+        };
+        Ok(data)
+    }
+
+    fn create(&self, data: &Self::Item) -> Result<bool> {
+        self.put(data)
+    }
+
+    fn update(&self, data: &Self::Item) -> Result<bool> {
+        if !self.exists(data.id())? {
+            return Ok(false);
+        }
+        self.put(data)
+    }
+
+    fn delete(&self, id: &str) -> Result<bool> {
+        let tx = self.conn.begin_write().map_err(map_db_err)?;
+        let removed = {
+            let mut table = tx.open_table(Self::table()).map_err(map_db_err)?;
+            let prev = table
+                .remove(id)
+                .map_err(map_db_err)?
+                .map(|v| serde_json::from_str::<JsonValue>(v.value()))
+                .transpose()
+                .map_err(map_db_err)?;
+            Self::update_indexes(&tx, id, prev.as_ref(), None)?;
+            prev.is_some()
+        };
+        tx.commit().map_err(map_db_err)?;
+        Ok(removed)
+    }
+}
+
+impl<T> DbInit for Collection<T>
+where
+    T: DbDocument,
+{
+    fn init(&self) -> Result<()> {
+        // create the tables if they do not exist
+        let tx = self.conn.begin_write().map_err(map_db_err)?;
+        let exists = tx
+            .list_multimap_tables()
+            .map_err(map_db_err)?
+            .map(|t| t.name().to_string())
+            .collect::<HashSet<_>>();
+        {
+            let table = tx.open_table(Self::table()).map_err(map_db_err)?;
+            for (key, name) in T::INDEXES {
+                if exists.contains(*name) {
+                    continue;
+                }
+
+                // build the new index from the existing docs
+                let mut index = tx
+                    .open_multimap_table(Self::index_table(name))
+                    .map_err(map_db_err)?;
+                for item in table.iter().map_err(map_db_err)? {
+                    let (id, value) = item.map_err(map_db_err)?;
+                    let doc: JsonValue = serde_json::from_str(value.value()).map_err(map_db_err)?;
+                    if let Some(value) = doc.get(*key) {
+                        index
+                            .insert(index_value(value).as_str(), id.value())
+                            .map_err(map_db_err)?;
+                    }
+                }
+            }
+        }
+        tx.commit().map_err(map_db_err)
+    }
+}
+
+/// the text of the value in the index, the strings are kept without quotes
+fn index_value(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// check the doc by query conditions
+/// the conds are combined by `and`, the exprs in cond are combined by the cond type
+fn is_match(q: &Query, doc: &JsonValue) -> Result<bool> {
+    let mut q = q.clone();
+    for cond in q.queries().iter() {
+        let mut results = Vec::new();
+        for expr in cond.conds().iter() {
+            let value = doc.get(expr.key()).ok_or(ActError::Store(format!(
+                "cannot find key `{}` in doc",
+                expr.key(),
+            )))?;
+            results.push(expr.op(value, expr.value()));
+        }
+
+        let ret = match cond.r#type {
+            CondType::And => results.iter().all(|v| *v),
+            CondType::Or => results.iter().any(|v| *v),
+        };
+        if !ret {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
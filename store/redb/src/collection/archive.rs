@@ -0,0 +1,12 @@
+use crate::{collection::Collection, database::DbDocument};
+use acts::data::Archive;
+
+pub type ArchiveCollection = Collection<Archive>;
+
+impl DbDocument for Archive {
+    const NAME: &'static str = "archives";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
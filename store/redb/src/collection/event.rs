@@ -0,0 +1,12 @@
+use crate::{collection::Collection, database::DbDocument};
+use acts::data::Event;
+
+pub type EventCollection = Collection<Event>;
+
+impl DbDocument for Event {
+    const NAME: &'static str = "events";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
@@ -0,0 +1,13 @@
+use crate::{collection::Collection, database::DbDocument};
+use acts::data::History;
+
+pub type HistoryCollection = Collection<History>;
+
+impl DbDocument for History {
+    const NAME: &'static str = "history";
+    const INDEXES: &'static [(&'static str, &'static str)] = &[("pid", "history_pid")];
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
@@ -0,0 +1,13 @@
+use crate::{collection::Collection, database::DbDocument};
+use acts::data::Message;
+
+pub type MessageCollection = Collection<Message>;
+
+impl DbDocument for Message {
+    const NAME: &'static str = "messages";
+    const INDEXES: &'static [(&'static str, &'static str)] = &[("pid", "messages_pid")];
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
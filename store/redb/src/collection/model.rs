@@ -0,0 +1,12 @@
+use crate::{collection::Collection, database::DbDocument};
+use acts::data::Model;
+
+pub type ModelCollection = Collection<Model>;
+
+impl DbDocument for Model {
+    const NAME: &'static str = "models";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
@@ -0,0 +1,12 @@
+use crate::{collection::Collection, database::DbDocument};
+use acts::data::Package;
+
+pub type PackageCollection = Collection<Package>;
+
+impl DbDocument for Package {
+    const NAME: &'static str = "packages";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
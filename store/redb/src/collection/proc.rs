@@ -0,0 +1,13 @@
+use crate::{collection::Collection, database::DbDocument};
+use acts::data::Proc;
+
+pub type ProcCollection = Collection<Proc>;
+
+impl DbDocument for Proc {
+    const NAME: &'static str = "procs";
+    const INDEXES: &'static [(&'static str, &'static str)] = &[("state", "procs_state")];
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
@@ -0,0 +1,14 @@
+use crate::{collection::Collection, database::DbDocument};
+use acts::data::Task;
+
+pub type TaskCollection = Collection<Task>;
+
+impl DbDocument for Task {
+    const NAME: &'static str = "tasks";
+    const INDEXES: &'static [(&'static str, &'static str)] =
+        &[("pid", "tasks_pid"), ("state", "tasks_state")];
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
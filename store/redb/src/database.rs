@@ -0,0 +1,112 @@
+use crate::collection::{
+    ArchiveCollection, DbConnection, EventCollection, HistoryCollection, MessageCollection,
+    ModelCollection, PackageCollection, ProcCollection, TaskCollection,
+};
+use acts::{ActError, DbCollection, Result, data::*};
+use serde::{Serialize, de::DeserializeOwned};
+use std::{fs, path::Path, sync::Arc};
+
+pub trait DbDocument: Serialize + DeserializeOwned {
+    /// the table name
+    const NAME: &'static str;
+    /// the indexed keys with their index table names to query without the full scan
+    const INDEXES: &'static [(&'static str, &'static str)] = &[];
+    fn id(&self) -> &str;
+}
+
+pub trait DbInit {
+    fn init(&self) -> Result<()>;
+}
+
+pub struct Database {
+    models: Arc<ModelCollection>,
+    procs: Arc<ProcCollection>,
+    tasks: Arc<TaskCollection>,
+    packages: Arc<PackageCollection>,
+    messages: Arc<MessageCollection>,
+    events: Arc<EventCollection>,
+    history: Arc<HistoryCollection>,
+    archives: Arc<ArchiveCollection>,
+}
+
+impl Database {
+    pub fn new(db_url: &str) -> Result<Self> {
+        let db_path = db_url.replace("redb://", "");
+
+        let path = Path::new(&db_path);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|err| {
+                ActError::Store(format!("failed to create redb dir {dir:?}: {err}"))
+            })?;
+        }
+
+        let db = redb::Database::create(path).map_err(|err| {
+            ActError::Store(format!("failed to open redb database '{db_path}': {err}"))
+        })?;
+        let conn: DbConnection = Arc::new(db);
+
+        let models = ModelCollection::new(&conn);
+        let procs = ProcCollection::new(&conn);
+        let tasks = TaskCollection::new(&conn);
+        let packages = PackageCollection::new(&conn);
+        let messages = MessageCollection::new(&conn);
+        let events = EventCollection::new(&conn);
+        let history = HistoryCollection::new(&conn);
+        let archives = ArchiveCollection::new(&conn);
+
+        Ok(Self {
+            models: Arc::new(models),
+            procs: Arc::new(procs),
+            tasks: Arc::new(tasks),
+            packages: Arc::new(packages),
+            messages: Arc::new(messages),
+            events: Arc::new(events),
+            history: Arc::new(history),
+            archives: Arc::new(archives),
+        })
+    }
+
+    pub fn tasks(&self) -> Arc<dyn DbCollection<Item = Task> + Send + Sync> {
+        self.tasks.clone()
+    }
+
+    pub fn procs(&self) -> Arc<dyn DbCollection<Item = Proc> + Send + Sync> {
+        self.procs.clone()
+    }
+
+    pub fn packages(&self) -> Arc<dyn DbCollection<Item = Package> + Send + Sync> {
+        self.packages.clone()
+    }
+
+    pub fn models(&self) -> Arc<dyn DbCollection<Item = Model> + Send + Sync> {
+        self.models.clone()
+    }
+
+    pub fn messages(&self) -> Arc<dyn DbCollection<Item = Message> + Send + Sync> {
+        self.messages.clone()
+    }
+
+    pub fn events(&self) -> Arc<dyn DbCollection<Item = Event> + Send + Sync> {
+        self.events.clone()
+    }
+
+    pub fn history(&self) -> Arc<dyn DbCollection<Item = History> + Send + Sync> {
+        self.history.clone()
+    }
+
+    pub fn archives(&self) -> Arc<dyn DbCollection<Item = Archive> + Send + Sync> {
+        self.archives.clone()
+    }
+
+    pub fn init(&self) -> Result<()> {
+        self.packages.init()?;
+        self.models.init()?;
+        self.procs.init()?;
+        self.tasks.init()?;
+        self.messages.init()?;
+        self.events.init()?;
+        self.history.init()?;
+        self.archives.init()?;
+        Ok(())
+    }
+}
@@ -1,21 +1,26 @@
+mod archive;
 mod event;
+mod history;
 mod message;
 mod model;
 mod package;
 mod proc;
 mod task;
 
+pub use archive::ArchiveCollection;
 pub use event::EventCollection;
+pub use history::HistoryCollection;
 pub use message::MessageCollection;
 pub use model::ModelCollection;
 pub use package::PackageCollection;
 pub use proc::ProcCollection;
 pub use task::TaskCollection;
 
-use acts::{ActError, query::*};
+use acts::{ActError, Result, query::*};
 use r2d2_sqlite::SqliteConnectionManager;
 use sea_query::{
-    Alias as SeaAlias, Cond as SeaCond, Condition, Expr as SeaExpr, IntoCondition, Value,
+    Alias as SeaAlias, Cond as SeaCond, Condition, Expr as SeaExpr, Func as SeaFunc, IntoCondition,
+    Value,
 };
 
 pub type DbConnection = r2d2::Pool<SqliteConnectionManager>;
@@ -24,7 +29,7 @@ fn map_db_err(err: impl std::error::Error) -> ActError {
     ActError::Store(err.to_string())
 }
 
-fn into_query(q: &acts::query::Query) -> SeaCond {
+fn into_query(q: &acts::query::Query) -> Result<SeaCond> {
     let mut filter = SeaCond::all();
     if q.is_cond() {
         let mut q = q.clone();
@@ -36,20 +41,20 @@ fn into_query(q: &acts::query::Query) -> SeaCond {
             };
 
             for expr in cond.conds().iter() {
-                let cond = into_cond(expr);
+                let cond = into_cond(expr)?;
                 sea_cond = sea_cond.add(cond);
             }
             filter = filter.add(sea_cond);
         }
     }
 
-    filter
+    Ok(filter)
 }
 
-fn into_cond(expr: &Expr) -> Condition {
+fn into_cond(expr: &Expr) -> Result<Condition> {
     let key = &expr.key;
     let value = json_to_sea_value(expr.value.clone());
-    match expr.op {
+    let cond = match expr.op {
         ExprOp::EQ => {
             if expr.value.is_null() {
                 SeaExpr::col(SeaAlias::new(key)).is_null().into_condition()
@@ -82,7 +87,20 @@ fn into_cond(expr: &Expr) -> Condition {
         ExprOp::GE => SeaExpr::col(SeaAlias::new(key))
             .gte(value.unwrap())
             .into_condition(),
-    }
+        ExprOp::Contains => SeaExpr::expr(
+            SeaFunc::cust(SeaAlias::new("instr"))
+                .arg(SeaExpr::col(SeaAlias::new(key)))
+                .arg(value.unwrap()),
+        )
+        .gt(0)
+        .into_condition(),
+        ref op => {
+            return Err(ActError::Store(format!(
+                "the query op {op:?} is not supported"
+            )));
+        }
+    };
+    Ok(cond)
 }
 
 fn json_to_sea_value(value: serde_json::Value) -> Option<Value> {
@@ -74,7 +74,7 @@ impl DbCollection for EventCollection {
 
     fn query(&self, q: &acts::query::Query) -> Result<acts::PageData<Self::Item>> {
         let conn = self.conn.get().unwrap();
-        let filter = into_query(q);
+        let filter = into_query(q)?;
 
         let mut count_query = SeaQuery::select();
         count_query
@@ -231,7 +231,7 @@ impl DbRow for data::Event {
 }
 
 impl DbInit for EventCollection {
-    fn init(&self) {
+    fn init(&self) -> Result<()> {
         let sql = [Table::create()
             .table(CollectionIden::Table)
             .if_not_exists()
@@ -258,8 +258,9 @@ impl DbInit for EventCollection {
             )
             .build(SqliteQueryBuilder)]
         .join("; ");
-        let conn = self.conn.get().unwrap();
-        conn.execute_batch(&sql).unwrap();
+        let conn = self.conn.get().map_err(map_db_err)?;
+        conn.execute_batch(&sql).map_err(map_db_err)?;
+        Ok(())
     }
 }
 
@@ -106,7 +106,7 @@ impl DbCollection for MessageCollection {
 
     fn query(&self, q: &acts::query::Query) -> Result<acts::PageData<Self::Item>> {
         let conn = self.conn.get().unwrap();
-        let filter = into_query(q);
+        let filter = into_query(q)?;
 
         let mut count_query = SeaQuery::select();
         count_query
@@ -341,7 +341,7 @@ impl DbRow for data::Message {
 }
 
 impl DbInit for MessageCollection {
-    fn init(&self) {
+    fn init(&self) -> Result<()> {
         let sql = [
             Table::create()
                 .table(CollectionIden::Table)
@@ -425,8 +425,9 @@ impl DbInit for MessageCollection {
                 .build(SqliteQueryBuilder),
         ]
         .join("; ");
-        let conn = self.conn.get().unwrap();
-        conn.execute_batch(&sql).unwrap();
+        let conn = self.conn.get().map_err(map_db_err)?;
+        conn.execute_batch(&sql).map_err(map_db_err)?;
+        Ok(())
     }
 }
 
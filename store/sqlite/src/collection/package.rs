@@ -85,7 +85,7 @@ impl DbCollection for PackageCollection {
 
     fn query(&self, q: &acts::query::Query) -> Result<acts::PageData<Self::Item>> {
         let conn = self.conn.get().unwrap();
-        let filter = into_query(q);
+        let filter = into_query(q)?;
 
         let mut count_query = SeaQuery::select();
         count_query
@@ -268,7 +268,7 @@ impl DbRow for data::Package {
 }
 
 impl DbInit for PackageCollection {
-    fn init(&self) {
+    fn init(&self) -> Result<()> {
         let sql = [
             Table::create()
                 .table(CollectionIden::Table)
@@ -320,8 +320,9 @@ impl DbInit for PackageCollection {
                 .build(SqliteQueryBuilder),
         ]
         .join("; ");
-        let conn = self.conn.get().unwrap();
-        conn.execute_batch(&sql).unwrap();
+        let conn = self.conn.get().map_err(map_db_err)?;
+        conn.execute_batch(&sql).map_err(map_db_err)?;
+        Ok(())
     }
 }
 
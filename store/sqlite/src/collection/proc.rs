@@ -78,7 +78,7 @@ impl DbCollection for ProcCollection {
 
     fn query(&self, q: &acts::query::Query) -> Result<acts::PageData<Self::Item>> {
         let conn = self.conn.get().unwrap();
-        let filter = into_query(q);
+        let filter = into_query(q)?;
 
         let mut count_query = SeaQuery::select();
         count_query
@@ -245,7 +245,7 @@ impl DbRow for data::Proc {
 }
 
 impl DbInit for ProcCollection {
-    fn init(&self) {
+    fn init(&self) -> Result<()> {
         let sql = [
             Table::create()
                 .table(CollectionIden::Table)
@@ -292,8 +292,9 @@ impl DbInit for ProcCollection {
                 .build(SqliteQueryBuilder),
         ]
         .join("; ");
-        let conn = self.conn.get().unwrap();
-        conn.execute_batch(&sql).unwrap();
+        let conn = self.conn.get().map_err(map_db_err)?;
+        conn.execute_batch(&sql).map_err(map_db_err)?;
+        Ok(())
     }
 }
 
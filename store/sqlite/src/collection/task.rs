@@ -34,6 +34,10 @@ enum CollectionIden {
     EndTime,
     Hooks,
     Timestamp,
+
+    Assignee,
+    Candidates,
+    Priority,
 }
 
 impl DbCollection for TaskCollection {
@@ -74,6 +78,9 @@ impl DbCollection for TaskCollection {
                 CollectionIden::EndTime,
                 CollectionIden::Hooks,
                 CollectionIden::Timestamp,
+                CollectionIden::Assignee,
+                CollectionIden::Candidates,
+                CollectionIden::Priority,
             ])
             .and_where(SeaExpr::col(CollectionIden::Id).eq(id))
             .build_rusqlite(SqliteQueryBuilder);
@@ -88,7 +95,7 @@ impl DbCollection for TaskCollection {
 
     fn query(&self, q: &acts::query::Query) -> Result<acts::PageData<Self::Item>> {
         let conn = self.conn.get().unwrap();
-        let filter = into_query(q);
+        let filter = into_query(q)?;
 
         let mut count_query = SeaQuery::select();
         count_query
@@ -112,6 +119,9 @@ impl DbCollection for TaskCollection {
                 CollectionIden::EndTime,
                 CollectionIden::Hooks,
                 CollectionIden::Timestamp,
+                CollectionIden::Assignee,
+                CollectionIden::Candidates,
+                CollectionIden::Priority,
             ])
             .from(CollectionIden::Table);
 
@@ -177,6 +187,9 @@ impl DbCollection for TaskCollection {
                 CollectionIden::EndTime,
                 CollectionIden::Hooks,
                 CollectionIden::Timestamp,
+                CollectionIden::Assignee,
+                CollectionIden::Candidates,
+                CollectionIden::Priority,
             ])
             .values([
                 data.id.into(),
@@ -193,6 +206,9 @@ impl DbCollection for TaskCollection {
                 data.end_time.into(),
                 data.hooks.into(),
                 data.timestamp.into(),
+                data.assignee.into(),
+                data.candidates.into(),
+                data.priority.into(),
             ])
             .map_err(map_db_err)?
             .build_rusqlite(SqliteQueryBuilder);
@@ -222,6 +238,9 @@ impl DbCollection for TaskCollection {
                 (CollectionIden::EndTime, model.end_time.into()),
                 (CollectionIden::Hooks, model.hooks.into()),
                 (CollectionIden::Timestamp, model.timestamp.into()),
+                (CollectionIden::Assignee, model.assignee.into()),
+                (CollectionIden::Candidates, model.candidates.into()),
+                (CollectionIden::Priority, model.priority.into()),
             ])
             .and_where(SeaExpr::col(CollectionIden::Id).eq(data.id()))
             .build_rusqlite(SqliteQueryBuilder);
@@ -270,48 +289,56 @@ impl DbRow for data::Task {
             end_time: row.get_unwrap("end_time"),
             hooks: row.get_unwrap("hooks"),
             timestamp: row.get_unwrap("timestamp"),
+            assignee: row.get_unwrap("assignee"),
+            candidates: row.get_unwrap("candidates"),
+            priority: row.get_unwrap("priority"),
         })
     }
 }
 
 impl DbInit for TaskCollection {
-    fn init(&self) {
+    fn init(&self) -> Result<()> {
+        let mut table = Table::create();
+        table
+            .table(CollectionIden::Table)
+            .if_not_exists()
+            .col(
+                ColumnDef::new(CollectionIden::Id)
+                    .string()
+                    .not_null()
+                    .primary_key(),
+            )
+            .col(ColumnDef::new(CollectionIden::Pid).string().not_null())
+            .col(ColumnDef::new(CollectionIden::Tid).string().not_null())
+            .col(ColumnDef::new(CollectionIden::NodeData).string())
+            .col(ColumnDef::new(CollectionIden::Kind).string().not_null())
+            .col(ColumnDef::new(CollectionIden::Prev).string())
+            .col(ColumnDef::new(CollectionIden::Name).string())
+            .col(ColumnDef::new(CollectionIden::State).string().not_null())
+            .col(ColumnDef::new(CollectionIden::Data).string())
+            .col(ColumnDef::new(CollectionIden::Err).string())
+            .col(
+                ColumnDef::new(CollectionIden::StartTime)
+                    .big_integer()
+                    .default(0),
+            )
+            .col(
+                ColumnDef::new(CollectionIden::EndTime)
+                    .big_integer()
+                    .default(0),
+            )
+            .col(
+                ColumnDef::new(CollectionIden::Timestamp)
+                    .big_integer()
+                    .default(0),
+            )
+            .col(ColumnDef::new(CollectionIden::Hooks).string());
+        for col in assignment_columns() {
+            table.col(col);
+        }
+
         let sql = [
-            Table::create()
-                .table(CollectionIden::Table)
-                .if_not_exists()
-                .col(
-                    ColumnDef::new(CollectionIden::Id)
-                        .string()
-                        .not_null()
-                        .primary_key(),
-                )
-                .col(ColumnDef::new(CollectionIden::Pid).string().not_null())
-                .col(ColumnDef::new(CollectionIden::Tid).string().not_null())
-                .col(ColumnDef::new(CollectionIden::NodeData).string())
-                .col(ColumnDef::new(CollectionIden::Kind).string().not_null())
-                .col(ColumnDef::new(CollectionIden::Prev).string())
-                .col(ColumnDef::new(CollectionIden::Name).string())
-                .col(ColumnDef::new(CollectionIden::State).string().not_null())
-                .col(ColumnDef::new(CollectionIden::Data).string())
-                .col(ColumnDef::new(CollectionIden::Err).string())
-                .col(
-                    ColumnDef::new(CollectionIden::StartTime)
-                        .big_integer()
-                        .default(0),
-                )
-                .col(
-                    ColumnDef::new(CollectionIden::EndTime)
-                        .big_integer()
-                        .default(0),
-                )
-                .col(
-                    ColumnDef::new(CollectionIden::Timestamp)
-                        .big_integer()
-                        .default(0),
-                )
-                .col(ColumnDef::new(CollectionIden::Hooks).string())
-                .build(SqliteQueryBuilder),
+            table.build(SqliteQueryBuilder),
             Index::create()
                 .name("idx_tasks_pid")
                 .if_not_exists()
@@ -326,11 +353,60 @@ impl DbInit for TaskCollection {
                 .build(SqliteQueryBuilder),
         ]
         .join("; ");
-        let conn = self.conn.get().unwrap();
-        conn.execute_batch(&sql).unwrap();
+        let conn = self.conn.get().map_err(map_db_err)?;
+        conn.execute_batch(&sql).map_err(map_db_err)?;
+
+        // add the assignment columns to the table created by the earlier versions
+        let names = conn
+            .prepare("select name from pragma_table_info('tasks')")
+            .map_err(map_db_err)?
+            .query_map([], |row| row.get::<usize, String>(0))
+            .map_err(map_db_err)?
+            .collect::<DbResult<Vec<_>, DbError>>()
+            .map_err(map_db_err)?;
+        for mut col in assignment_columns() {
+            if names.contains(&col.get_column_name()) {
+                continue;
+            }
+            let sql = Table::alter()
+                .table(CollectionIden::Table)
+                .add_column(&mut col)
+                .build(SqliteQueryBuilder);
+            conn.execute(&sql, []).map_err(map_db_err)?;
+        }
+
+        let sql = Index::create()
+            .name("idx_tasks_assignee")
+            .if_not_exists()
+            .table(CollectionIden::Table)
+            .col(CollectionIden::Assignee)
+            .build(SqliteQueryBuilder);
+        conn.execute(&sql, []).map_err(map_db_err)?;
+        Ok(())
     }
 }
 
+/// the assignment columns added after the table is created
+fn assignment_columns() -> Vec<ColumnDef> {
+    vec![
+        ColumnDef::new(CollectionIden::Assignee)
+            .string()
+            .not_null()
+            .default("")
+            .to_owned(),
+        ColumnDef::new(CollectionIden::Candidates)
+            .string()
+            .not_null()
+            .default("")
+            .to_owned(),
+        ColumnDef::new(CollectionIden::Priority)
+            .integer()
+            .not_null()
+            .default(0)
+            .to_owned(),
+    ]
+}
+
 impl TaskCollection {
     pub fn new(conn: &DbConnection) -> Self {
         Self { conn: conn.clone() }
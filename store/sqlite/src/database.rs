@@ -1,8 +1,8 @@
 use crate::collection::{
-    EventCollection, MessageCollection, ModelCollection, PackageCollection, ProcCollection,
-    TaskCollection,
+    ArchiveCollection, EventCollection, HistoryCollection, MessageCollection, ModelCollection,
+    PackageCollection, ProcCollection, TaskCollection,
 };
-use acts::{DbCollection, data::*};
+use acts::{DbCollection, Result, data::*};
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{Error as DbError, Result as DbResult, Row};
 use std::{fs, path::Path, sync::Arc};
@@ -15,7 +15,7 @@ pub trait DbRow {
 }
 
 pub trait DbInit {
-    fn init(&self);
+    fn init(&self) -> Result<()>;
 }
 
 pub struct Database {
@@ -25,6 +25,8 @@ pub struct Database {
     packages: Arc<PackageCollection>,
     messages: Arc<MessageCollection>,
     events: Arc<EventCollection>,
+    history: Arc<HistoryCollection>,
+    archives: Arc<ArchiveCollection>,
 }
 
 impl Database {
@@ -45,6 +47,8 @@ impl Database {
         let packages = PackageCollection::new(&conn);
         let messages = MessageCollection::new(&conn);
         let events = EventCollection::new(&conn);
+        let history = HistoryCollection::new(&conn);
+        let archives = ArchiveCollection::new(&conn);
 
         Self {
             models: Arc::new(models),
@@ -53,6 +57,8 @@ impl Database {
             packages: Arc::new(packages),
             messages: Arc::new(messages),
             events: Arc::new(events),
+            history: Arc::new(history),
+            archives: Arc::new(archives),
         }
     }
 
@@ -80,12 +86,23 @@ impl Database {
         self.events.clone()
     }
 
-    pub fn init(&self) {
-        self.packages.init();
-        self.models.init();
-        self.procs.init();
-        self.tasks.init();
-        self.messages.init();
-        self.events.init();
+    pub fn history(&self) -> Arc<dyn DbCollection<Item = History> + Send + Sync> {
+        self.history.clone()
+    }
+
+    pub fn archives(&self) -> Arc<dyn DbCollection<Item = Archive> + Send + Sync> {
+        self.archives.clone()
+    }
+
+    pub fn init(&self) -> Result<()> {
+        self.packages.init()?;
+        self.models.init()?;
+        self.procs.init()?;
+        self.tasks.init()?;
+        self.messages.init()?;
+        self.events.init()?;
+        self.history.init()?;
+        self.archives.init()?;
+        Ok(())
     }
 }
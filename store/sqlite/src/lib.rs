@@ -28,7 +28,7 @@ impl ActPlugin for SqliteStore {
             .map_err(|err| ActError::Config(format!("get sqlite config error: {}", err)))?;
 
         let db = database::Database::new(&config.database_url);
-        db.init();
+        db.init()?;
 
         engine.extender().register_collection(db.packages());
         engine.extender().register_collection(db.models());
@@ -36,6 +36,8 @@ impl ActPlugin for SqliteStore {
         engine.extender().register_collection(db.tasks());
         engine.extender().register_collection(db.messages());
         engine.extender().register_collection(db.events());
+        engine.extender().register_collection(db.history());
+        engine.extender().register_collection(db.archives());
 
         Ok(())
     }
@@ -6,7 +6,7 @@ use tokio::sync::OnceCell;
 static STORE: OnceCell<Database> = OnceCell::const_new();
 async fn init() -> Database {
     let db = Database::new("sqlite://test_data/test.db");
-    db.init();
+    db.init().unwrap();
     db
 }
 
@@ -276,6 +276,9 @@ async fn store_task_create() {
         timestamp: utils::timestamp(),
         data: "{}".to_string(),
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
     tasks.create(&task).unwrap();
     assert!(tasks.exists(&task.id).unwrap());
@@ -301,6 +304,9 @@ async fn store_task_find() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
     tasks.create(&task).unwrap();
     assert_eq!(tasks.find(&tid).unwrap().id, tid);
@@ -327,6 +333,9 @@ async fn store_task_query() {
             timestamp: 0,
             data: "{}".to_string(),
             err: None,
+            assignee: String::new(),
+            candidates: String::new(),
+            priority: 0,
         };
         tasks.create(&task).unwrap();
     }
@@ -338,6 +347,106 @@ async fn store_task_query() {
     assert_eq!(items.rows.len(), 5);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 10)]
+async fn store_task_query_by_candidates() {
+    let store = store().await;
+    let tasks = store.tasks();
+    let pid = utils::shortid();
+    let items = [
+        (
+            "t1",
+            2,
+            Task::join_candidates(&["u1".to_string()], &["g_1".to_string()]),
+        ),
+        ("t2", 10, Task::join_candidates(&["U1".to_string()], &[])),
+        ("t3", 9, Task::join_candidates(&[], &["g1".to_string()])),
+    ];
+    for (tid, priority, candidates) in items {
+        let task = Task {
+            kind: "act".to_string(),
+            id: utils::shortid(),
+            name: "test".to_string(),
+            pid: pid.to_string(),
+            tid: tid.to_string(),
+            node_data: "nid".to_string(),
+            state: "interrupted".to_string(),
+            prev: None,
+            start_time: 0,
+            end_time: 0,
+            hooks: "{}".to_string(),
+            timestamp: 0,
+            data: "{}".to_string(),
+            err: None,
+            assignee: String::new(),
+            candidates,
+            priority,
+        };
+        tasks.create(&task).unwrap();
+    }
+
+    // the candidates are matched exactly and case-sensitively
+    let q = Query::new()
+        .push(Cond::and().push(Expr::eq("pid", pid.clone())))
+        .push(Cond::or().push(Expr::contains("candidates", &Task::candidate_user("u1"))));
+    let items = tasks.query(&q).unwrap();
+    assert_eq!(items.rows.len(), 1);
+    assert_eq!(items.rows[0].tid, "t1");
+
+    let q = Query::new()
+        .push(Cond::and().push(Expr::eq("pid", pid.clone())))
+        .push(Cond::or().push(Expr::contains("candidates", &Task::candidate_group("g1"))));
+    let items = tasks.query(&q).unwrap();
+    assert_eq!(items.rows.len(), 1);
+    assert_eq!(items.rows[0].tid, "t3");
+
+    // the priority is ordered by number
+    let q = Query::new()
+        .push(Cond::and().push(Expr::eq("pid", pid.clone())))
+        .push_order("priority", true);
+    let items = tasks.query(&q).unwrap();
+    assert_eq!(
+        items
+            .rows
+            .iter()
+            .map(|t| t.tid.as_str())
+            .collect::<Vec<_>>(),
+        vec!["t2", "t3", "t1"]
+    );
+}
+
+#[tokio::test]
+async fn store_task_migrate_old_table() {
+    let path = format!("test_data/{}.db", utils::shortid());
+    std::fs::create_dir_all("test_data").unwrap();
+    {
+        // the tasks table layout before the assignment columns
+        let conn = rusqlite::Connection::open(&path).unwrap();
+        conn.execute_batch(
+            "create table tasks (id text not null primary key, pid text not null, tid text not null, \
+            node_data text, kind text not null, prev text, name text, state text not null, data text, \
+            err text, start_time integer default 0, end_time integer default 0, \
+            timestamp integer default 0, hooks text); \
+            insert into tasks (id, pid, tid, node_data, kind, name, state, data, hooks) \
+            values ('t1', 'p1', 't1', 'nid', 'act', 'test', 'interrupted', '{}', '{}');",
+        )
+        .unwrap();
+    }
+
+    let db = Database::new(&format!("sqlite://{path}"));
+    db.init().unwrap();
+    // init again without adding the existing columns
+    db.init().unwrap();
+
+    let task = db.tasks().find("t1").unwrap();
+    assert_eq!(task.assignee, "");
+    assert_eq!(task.candidates, "");
+    assert_eq!(task.priority, 0);
+
+    let q = Query::new().push(Cond::and().push(Expr::eq("assignee", "")));
+    assert_eq!(db.tasks().query(&q).unwrap().count, 1);
+    std::fs::remove_file(&path).unwrap();
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 10)]
 async fn store_task_update() {
     let store = store().await;
@@ -357,6 +466,9 @@ async fn store_task_update() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
     table.create(&task).unwrap();
 
@@ -389,6 +501,9 @@ async fn store_task_delete() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        assignee: String::new(),
+        candidates: String::new(),
+        priority: 0,
     };
     table.create(&task).unwrap();
     table.delete(&task.id).unwrap();
@@ -768,3 +883,118 @@ async fn store_event_remove() {
     let ret = store.events().find(&evt.id);
     assert!(ret.is_err());
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 10)]
+async fn store_history_create() {
+    let store = store().await;
+
+    let history = History {
+        id: utils::longid(),
+        pid: utils::longid(),
+        tid: utils::shortid(),
+        kind: "state".to_string(),
+        name: "completed".to_string(),
+        actor: "u1".to_string(),
+        data: "{}".to_string(),
+        create_time: utils::time_millis(),
+        timestamp: utils::timestamp(),
+    };
+
+    store.history().create(&history).unwrap();
+    let ret = store.history().find(&history.id);
+    assert!(ret.is_ok());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 10)]
+async fn store_history_query() {
+    let store = store().await;
+
+    let pid = utils::longid();
+    for name in ["running", "completed"] {
+        let history = History {
+            id: utils::longid(),
+            pid: pid.clone(),
+            tid: utils::shortid(),
+            kind: "state".to_string(),
+            name: name.to_string(),
+            actor: "".to_string(),
+            data: "{}".to_string(),
+            create_time: 0,
+            timestamp: utils::timestamp(),
+        };
+        store.history().create(&history).unwrap();
+    }
+    let q = Query::new()
+        .push(Cond::and().push(Expr::eq("pid", pid)))
+        .push_order("timestamp", false);
+    let ret = store.history().query(&q).unwrap();
+    assert_eq!(ret.count, 2);
+    assert_eq!(ret.rows[0].name, "running");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 10)]
+async fn store_history_remove() {
+    let store = store().await;
+
+    let history = History {
+        id: utils::longid(),
+        pid: utils::longid(),
+        tid: utils::shortid(),
+        kind: "action".to_string(),
+        name: "next".to_string(),
+        actor: "u1".to_string(),
+        data: "{}".to_string(),
+        create_time: 0,
+        timestamp: 0,
+    };
+    store.history().create(&history).unwrap();
+    store.history().delete(&history.id).unwrap();
+
+    let ret = store.history().find(&history.id);
+    assert!(ret.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 10)]
+async fn store_archive_create() {
+    let store = store().await;
+
+    let archive = Archive {
+        id: utils::longid(),
+        mid: "m1".to_string(),
+        name: "test".to_string(),
+        state: "completed".to_string(),
+        data: "{}".to_string(),
+        end_time: utils::time_millis(),
+        create_time: utils::time_millis(),
+        timestamp: utils::timestamp(),
+    };
+
+    store.archives().create(&archive).unwrap();
+    let ret = store.archives().find(&archive.id);
+    assert!(ret.is_ok());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 10)]
+async fn store_archive_query() {
+    let store = store().await;
+
+    let mid = utils::longid();
+    for end_time in [1, 2, 3] {
+        let archive = Archive {
+            id: utils::longid(),
+            mid: mid.clone(),
+            name: "test".to_string(),
+            state: "completed".to_string(),
+            data: "{}".to_string(),
+            end_time,
+            create_time: 0,
+            timestamp: utils::timestamp(),
+        };
+        store.archives().create(&archive).unwrap();
+    }
+    let q = Query::new()
+        .push(Cond::and().push(Expr::eq("mid", mid)))
+        .push(Cond::and().push(Expr::ge("end_time", 2)));
+    let ret = store.archives().query(&q).unwrap();
+    assert_eq!(ret.count, 2);
+}